@@ -0,0 +1,11 @@
+create table if not exists api_key (
+    id uuid primary key,
+    name varchar(63) not null,
+    prefix varchar(15) not null,
+    key_hash char(64) not null unique,
+    scopes text[] not null default '{}',
+    created_at timestamptz default now(),
+    updated_at timestamptz default now(),
+    last_used_at timestamptz,
+    revoked_at timestamptz
+);
@@ -0,0 +1 @@
+create index if not exists processed_message_processed_at_idx on processed_message (processed_at);
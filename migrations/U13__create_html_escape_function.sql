@@ -0,0 +1,3 @@
+create or replace function html_escape(value text) returns text as $$
+    select replace(replace(replace(replace(replace(value, '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '"', '&quot;'), '''', '&#39;')
+$$ language sql immutable;
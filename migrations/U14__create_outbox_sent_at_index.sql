@@ -0,0 +1 @@
+create index if not exists outbox_sent_at_idx on outbox (sent_at) where sent_at is not null;
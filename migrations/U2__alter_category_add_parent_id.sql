@@ -0,0 +1,4 @@
+alter table category
+    add column if not exists parent_id uuid references category (id);
+
+create index if not exists category_parent_id_idx on category (parent_id);
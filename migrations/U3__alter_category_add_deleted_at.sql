@@ -0,0 +1,2 @@
+alter table category
+    add column if not exists deleted_at timestamptz;
@@ -0,0 +1,11 @@
+create table if not exists outbox (
+    id uuid primary key,
+    aggregate_type varchar(63) not null,
+    aggregate_id uuid not null,
+    event_type varchar(63) not null,
+    payload jsonb not null,
+    created_at timestamptz default now(),
+    sent_at timestamptz
+);
+
+create index if not exists outbox_pending_idx on outbox (created_at) where sent_at is null;
@@ -0,0 +1,4 @@
+create table if not exists processed_message (
+    message_id varchar(255) primary key,
+    processed_at timestamptz default now()
+);
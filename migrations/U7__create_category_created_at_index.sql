@@ -0,0 +1 @@
+create index if not exists category_created_at_id_idx on category (created_at, id);
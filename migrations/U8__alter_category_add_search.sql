@@ -0,0 +1,9 @@
+create extension if not exists pg_trgm;
+
+alter table category add column if not exists search tsvector generated always as (
+    setweight(to_tsvector('simple', coalesce(name, '')), 'A') ||
+    setweight(to_tsvector('simple', coalesce(description, '')), 'B')
+) stored;
+
+create index if not exists category_search_idx on category using gin (search);
+create index if not exists category_name_trgm_idx on category using gin (name gin_trgm_ops);
@@ -0,0 +1 @@
+create unique index if not exists category_name_unique_idx on category (lower(name)) where is_active = true;
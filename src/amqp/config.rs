@@ -12,12 +12,46 @@ pub fn get_config() -> &'static Config {
 #[derive(Debug, Clone)]
 pub struct Config {
     pub amqp_addr: String,
+    pub outbox_poll_interval: u64,
+    pub outbox_batch_size: i64,
+    pub outbox_retention_days: i32,
+    pub retry_max_attempts: u32,
+    pub retry_base_delay: u64,
+    pub cache_ttl: Option<usize>,
+    pub processed_message_retention_days: i32,
 }
 
 impl Config {
     fn from_env() -> Self {
         Self {
             amqp_addr: env::var("AMQP_ADDR").expect("AMQP_ADDR must be set"),
+            outbox_poll_interval: env::var("OUTBOX_POLL_INTERVAL_MS")
+                .unwrap_or_else(|_| String::from("1000"))
+                .parse::<u64>()
+                .expect("OUTBOX_POLL_INTERVAL_MS must be u64"),
+            outbox_batch_size: env::var("OUTBOX_BATCH_SIZE")
+                .unwrap_or_else(|_| String::from("100"))
+                .parse::<i64>()
+                .expect("OUTBOX_BATCH_SIZE must be i64"),
+            outbox_retention_days: env::var("OUTBOX_RETENTION_DAYS")
+                .unwrap_or_else(|_| String::from("7"))
+                .parse::<i32>()
+                .expect("OUTBOX_RETENTION_DAYS must be i32"),
+            retry_max_attempts: env::var("AMQP_RETRY_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| String::from("5"))
+                .parse::<u32>()
+                .expect("AMQP_RETRY_MAX_ATTEMPTS must be u32"),
+            retry_base_delay: env::var("AMQP_RETRY_BASE_DELAY_MS")
+                .unwrap_or_else(|_| String::from("1000"))
+                .parse::<u64>()
+                .expect("AMQP_RETRY_BASE_DELAY_MS must be u64"),
+            cache_ttl: env::var("CACHE_TTL")
+                .ok()
+                .map(|cache_ttl| cache_ttl.parse::<usize>().expect("CACHE_TTL must be usize")),
+            processed_message_retention_days: env::var("LEDGER_RETENTION_DAYS")
+                .unwrap_or_else(|_| String::from("30"))
+                .parse::<i32>()
+                .expect("LEDGER_RETENTION_DAYS must be i32"),
         }
     }
 }
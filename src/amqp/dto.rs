@@ -1,15 +1,113 @@
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationErrors};
 
-use crate::domain::categories::model::CategoryCreateModel;
+use crate::{
+    api::resources::categories::dto::{RequestCreateCategory, RequestUpdateCategory},
+    repository::outbox::OutboxEvent,
+};
 
 #[derive(Debug, Deserialize)]
-pub struct CategoryMessage {
-    pub name: String,
-    pub description: Option<String>,
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum CategoryMessage {
+    Create(RequestCreateCategory),
+    Update(CategoryUpdateMessage),
+    Delete(CategoryDeleteMessage),
 }
+impl CategoryMessage {
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            CategoryMessage::Create(message) => message.validate(),
+            CategoryMessage::Update(message) => message.validate(),
+            CategoryMessage::Delete(_) => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CategoryUpdateMessage {
+    pub id: Uuid,
+    #[serde(flatten)]
+    #[validate]
+    pub category: RequestUpdateCategory,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CategoryDeleteMessage {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventMessage {
+    pub id: Uuid,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub data: serde_json::Value,
+}
+
+impl From<OutboxEvent> for EventMessage {
+    fn from(value: OutboxEvent) -> Self {
+        Self {
+            id: value.id,
+            event_type: value.event_type,
+            aggregate_type: value.aggregate_type,
+            aggregate_id: value.aggregate_id,
+            occurred_at: value.created_at,
+            data: value.payload,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_deserialize_category_messages_by_type() {
+        let id = Uuid::new_v4();
+
+        let create: CategoryMessage =
+            serde_json::from_str(r#"{"type":"create","name":"Burgers"}"#).unwrap();
+        assert!(matches!(create, CategoryMessage::Create(_)));
+
+        let update: CategoryMessage = serde_json::from_str(&format!(
+            r#"{{"type":"update","id":"{id}","name":"Burgers Supreme"}}"#
+        ))
+        .unwrap();
+        match update {
+            CategoryMessage::Update(message) => {
+                assert_eq!(message.id, id);
+                assert_eq!(message.category.name, "Burgers Supreme");
+            }
+            _ => unreachable!(),
+        }
+
+        let delete: CategoryMessage =
+            serde_json::from_str(&format!(r#"{{"type":"delete","id":"{id}"}}"#)).unwrap();
+        assert!(matches!(delete, CategoryMessage::Delete(message) if message.id == id));
+    }
+
+    #[test]
+    fn it_should_reject_update_message_without_id() {
+        let result =
+            serde_json::from_str::<CategoryMessage>(r#"{"type":"update","name":"Burgers"}"#);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn it_should_return_validation_error_with_http_rules() {
+        let message: CategoryMessage = serde_json::from_str(&format!(
+            r#"{{"type":"update","id":"{}","name":"{}"}}"#,
+            Uuid::new_v4(),
+            "a".repeat(65)
+        ))
+        .unwrap();
 
-impl From<CategoryMessage> for CategoryCreateModel {
-    fn from(value: CategoryMessage) -> Self {
-        CategoryCreateModel::new(value.name, value.description)
+        assert!(message.validate().is_err());
     }
 }
@@ -1,33 +1,63 @@
-use std::{error::Error, sync::Arc};
+use std::{error::Error, sync::Arc, time::Duration};
 
 use deadpool_postgres::Pool;
 use futures::StreamExt;
 use lapin::{
+    message::Delivery,
     options::{
-        BasicAckOptions, BasicConsumeOptions, BasicNackOptions, BasicRejectOptions,
-        ExchangeDeclareOptions, QueueBindOptions, QueueDeclareOptions,
+        BasicAckOptions, BasicCancelOptions, BasicConsumeOptions, BasicRejectOptions,
+        ConfirmSelectOptions, ExchangeDeclareOptions, QueueBindOptions, QueueDeclareOptions,
     },
     types::{AMQPValue, FieldTable},
-    Connection, ConnectionProperties,
+    BasicProperties, Channel, Connection, ConnectionProperties,
 };
+use redis::aio::ConnectionManager;
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use crate::{
-    amqp::{config::get_config, dto::CategoryMessage},
-    domain::categories::{self, repository::CategoryRepository},
-    repository::categories::PgCategoryRepository,
+    amqp::{config::get_config, dto::CategoryMessage, outbox, retry},
+    domain::{
+        categories::{self, repository::CategoryRepository},
+        error::DomainError,
+    },
+    metrics,
+    repository::{
+        categories::PgCategoryRepository, categories_cache::CachedCategoryRepository,
+        processed_messages,
+    },
+    telemetry,
 };
 
-pub async fn run(pg_pool: Arc<Pool>) -> Result<(), Box<dyn Error>> {
+const PROCESSED_MESSAGE_PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+const PROCESSED_MESSAGE_PRUNE_BATCH_SIZE: i64 = 10_000;
+
+/// Connects to the broker, a lost connection cancels `shutdown` so the
+/// service stops instead of running without its consumer.
+pub async fn connect(shutdown: CancellationToken) -> Result<Connection, Box<dyn Error>> {
     let config = get_config();
 
     let connection =
-        Arc::new(Connection::connect(&config.amqp_addr, ConnectionProperties::default()).await?);
+        Connection::connect(&config.amqp_addr, ConnectionProperties::default()).await?;
 
-    connection.on_error(|err| {
+    connection.on_error(move |err| {
         log::error!("{}", err);
-        std::process::exit(1);
+        shutdown.cancel();
     });
 
+    Ok(connection)
+}
+
+/// Consumes `categories.queue` until `shutdown` is cancelled. The delivery in
+/// progress is settled before the consumer is cancelled and its channels are
+/// closed, so prefetched deliveries go back to the queue.
+pub async fn run(
+    pg_pool: Arc<Pool>,
+    redis_connection: ConnectionManager,
+    connection: Arc<Connection>,
+    shutdown: CancellationToken,
+) -> Result<(), Box<dyn Error>> {
     let declare_channel = connection.create_channel().await?;
 
     declare_channel
@@ -94,10 +124,38 @@ pub async fn run(pg_pool: Arc<Pool>) -> Result<(), Box<dyn Error>> {
             FieldTable::default(),
         )
         .await?;
+    retry::declare(&declare_channel).await?;
+
+    declare_channel
+        .exchange_declare(
+            outbox::EVENTS_EXCHANGE,
+            lapin::ExchangeKind::Topic,
+            ExchangeDeclareOptions {
+                durable: true,
+                ..Default::default()
+            },
+            FieldTable::default(),
+        )
+        .await?;
     declare_channel.close(0, "declare channel fineshed").await?;
 
-    let category_repository: Arc<dyn CategoryRepository> =
-        Arc::new(PgCategoryRepository::new(pg_pool.clone()));
+    let relay_pg_pool = pg_pool.clone();
+    let relay_connection = connection.clone();
+    let relay_shutdown = shutdown.clone();
+    let relay = tokio::spawn(async move {
+        if let Err(err) = outbox::run(relay_pg_pool, relay_connection, relay_shutdown.clone()).await
+        {
+            log::error!("{}", err);
+            relay_shutdown.cancel();
+        }
+    });
+
+    let prune = tokio::spawn(prune_processed_messages(pg_pool.clone(), shutdown.clone()));
+
+    let retry_channel = connection.create_channel().await?;
+    retry_channel
+        .confirm_select(ConfirmSelectOptions::default())
+        .await?;
 
     let consumer_channel = connection.create_channel().await?;
     let mut consumer = consumer_channel
@@ -110,30 +168,208 @@ pub async fn run(pg_pool: Arc<Pool>) -> Result<(), Box<dyn Error>> {
         .await?;
 
     log::info!("server listener categories.queue");
-    while let Some(result) = consumer.next().await {
+    loop {
+        let result = tokio::select! {
+            biased;
+            _ = shutdown.cancelled() => break,
+            result = consumer.next() => match result {
+                Some(result) => result,
+                None => break,
+            },
+        };
+
         if let Ok(delivery) = result {
-            match serde_json::from_slice::<CategoryMessage>(delivery.data.as_slice()) {
-                Ok(category_message) => {
-                    match categories::resources::create::execute(
-                        category_repository.clone(),
-                        category_message.into(),
-                    )
-                    .await
-                    {
-                        Ok(_) => delivery.ack(BasicAckOptions::default()).await?,
-                        Err(err) => {
-                            log::error!("Nack {}", err);
-                            delivery.nack(BasicNackOptions::default()).await?;
-                        }
-                    }
+            let span = tracing::info_span!(
+                "amqp_consume",
+                otel.name = "categories.queue process",
+                otel.kind = "consumer",
+                messaging.system = "rabbitmq",
+                messaging.destination.name = "categories.queue",
+            );
+            if let Err(err) = span.set_parent(telemetry::extract_amqp(
+                delivery.properties.headers().as_ref(),
+            )) {
+                log::debug!("Error to continue message trace {}", err);
+            }
+
+            consume(&pg_pool, &redis_connection, &retry_channel, delivery)
+                .instrument(span)
+                .await?;
+        }
+    }
+
+    log::info!("stopping consumer of categories.queue");
+    consumer_channel
+        .basic_cancel("consumer", BasicCancelOptions::default())
+        .await?;
+    consumer_channel.close(200, "shutdown").await?;
+    retry_channel.close(200, "shutdown").await?;
+    relay.await?;
+    prune.await?;
+
+    Ok(())
+}
+
+/// Deletes the processed message ledger rows older than
+/// `LEDGER_RETENTION_DAYS` every hour until `shutdown`.
+async fn prune_processed_messages(pg_pool: Arc<Pool>, shutdown: CancellationToken) {
+    let retention_days = get_config().processed_message_retention_days;
+
+    let mut interval = tokio::time::interval(PROCESSED_MESSAGE_PRUNE_INTERVAL);
+    loop {
+        tokio::select! {
+            biased;
+            _ = shutdown.cancelled() => return,
+            _ = interval.tick() => {}
+        }
+
+        match processed_messages::delete_expired(
+            &pg_pool,
+            retention_days,
+            PROCESSED_MESSAGE_PRUNE_BATCH_SIZE,
+        )
+        .await
+        {
+            Ok(deleted) if deleted > 0 => log::info!("Pruned {} processed messages", deleted),
+            Ok(_) => {}
+            Err(err) => log::error!("Error to prune processed messages {}", err),
+        }
+    }
+}
+
+/// Processes a delivery and settles it with an ack, a reject or a retry.
+/// Writes go through the category cache, when enabled, so the API never
+/// serves a category changed by a message from the cache.
+async fn consume(
+    pg_pool: &Arc<Pool>,
+    redis_connection: &ConnectionManager,
+    retry_channel: &Channel,
+    delivery: Delivery,
+) -> Result<(), lapin::Error> {
+    let Some(message_id) = message_id(&delivery.properties) else {
+        log::error!("Reject message without message_id");
+        delivery.reject(BasicRejectOptions::default()).await?;
+        metrics::inc_amqp_consumer_message("reject");
+        return Ok(());
+    };
+
+    match processed_messages::exists(pg_pool, &message_id).await {
+        Ok(true) => {
+            log::info!("Skip already processed message {}", message_id);
+            delivery.ack(BasicAckOptions::default()).await?;
+            metrics::inc_amqp_consumer_message("ack");
+            return Ok(());
+        }
+        Ok(false) => {}
+        Err(err) => {
+            retry::retry_or_dead_letter(retry_channel, &delivery, &err).await?;
+            return Ok(());
+        }
+    }
+    let category_repository =
+        PgCategoryRepository::new(pg_pool.clone()).with_message_id(message_id);
+    let mut category_repository: Arc<dyn CategoryRepository> = Arc::new(category_repository);
+    if let Some(cache_ttl) = get_config().cache_ttl {
+        category_repository = Arc::new(CachedCategoryRepository::new(
+            category_repository,
+            redis_connection.clone(),
+            cache_ttl,
+        ));
+    }
+
+    match serde_json::from_slice::<CategoryMessage>(delivery.data.as_slice()) {
+        Ok(category_message) => {
+            match process(category_repository.clone(), category_message).await {
+                Ok(_) => {
+                    delivery.ack(BasicAckOptions::default()).await?;
+                    metrics::inc_amqp_consumer_message("ack");
                 }
-                Err(err) => {
+                Err(err @ DomainError::AlreadyProcessed(_)) => {
+                    log::info!("Skip {}", err);
+                    delivery.ack(BasicAckOptions::default()).await?;
+                    metrics::inc_amqp_consumer_message("ack");
+                }
+                Err(
+                    err @ (DomainError::BadRequest(_)
+                    | DomainError::Validation(_)
+                    | DomainError::NotFound(_)
+                    | DomainError::Conflict(_)),
+                ) => {
                     log::error!("Reject {}", err);
                     delivery.reject(BasicRejectOptions::default()).await?;
+                    metrics::inc_amqp_consumer_message("reject");
+                }
+                Err(err) => {
+                    retry::retry_or_dead_letter(retry_channel, &delivery, &err).await?;
                 }
             }
         }
+        Err(err) => {
+            log::error!("Reject {}", err);
+            delivery.reject(BasicRejectOptions::default()).await?;
+            metrics::inc_amqp_consumer_message("reject");
+        }
+    }
+
+    Ok(())
+}
+
+/// Id recorded in the processed message ledger. Publishers must set the
+/// `message_id` property, a message without one cannot be told apart from a
+/// legitimate repeat of the same body and is rejected.
+fn message_id(properties: &BasicProperties) -> Option<String> {
+    properties
+        .message_id()
+        .as_ref()
+        .map(|message_id| message_id.to_string())
+        .filter(|message_id| !message_id.is_empty())
+}
+
+async fn process(
+    category_repository: Arc<dyn CategoryRepository>,
+    category_message: CategoryMessage,
+) -> Result<(), DomainError> {
+    category_message.validate()?;
+
+    match category_message {
+        CategoryMessage::Create(message) => {
+            categories::resources::create::execute(category_repository, message.into()).await?;
+        }
+        CategoryMessage::Update(message) => {
+            categories::resources::update_by_id::execute(
+                category_repository,
+                message.id,
+                message.category.into(),
+                None,
+            )
+            .await?;
+        }
+        CategoryMessage::Delete(message) => {
+            categories::resources::delete_by_id::execute(category_repository, message.id, None)
+                .await?;
+        }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_keep_publisher_message_id() {
+        let properties = BasicProperties::default().with_message_id("c5d0e7a2".into());
+
+        assert_eq!(message_id(&properties).as_deref(), Some("c5d0e7a2"));
+    }
+
+    #[test]
+    fn it_should_not_accept_missing_or_empty_message_id() {
+        assert_eq!(message_id(&BasicProperties::default()), None);
+        assert_eq!(
+            message_id(&BasicProperties::default().with_message_id("".into())),
+            None
+        );
+    }
+}
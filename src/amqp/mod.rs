@@ -1,3 +1,5 @@
 pub mod config;
 pub mod dto;
 pub mod lib;
+pub mod outbox;
+pub mod retry;
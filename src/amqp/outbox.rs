@@ -0,0 +1,148 @@
+use std::{error::Error, sync::Arc, time::Duration};
+
+use deadpool_postgres::Pool;
+use lapin::{
+    options::{BasicPublishOptions, ConfirmSelectOptions},
+    types::FieldTable,
+    BasicProperties, Channel, Connection,
+};
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::{
+    amqp::{config::get_config, dto::EventMessage},
+    domain::error::DomainError,
+    repository::outbox::{self, OutboxEvent},
+    telemetry,
+};
+
+pub const EVENTS_EXCHANGE: &str = "categories-events.exchange";
+
+const OUTBOX_PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+const OUTBOX_PRUNE_BATCH_SIZE: i64 = 10_000;
+
+/// Relays committed outbox rows to the events exchange. Rows are locked while
+/// they are published and only marked as sent after the broker confirms them,
+/// so a crash between publish and commit leads to a redelivery, never a loss.
+/// A batch in progress is committed before `shutdown` stops the relay. Every
+/// hour the rows sent more than `OUTBOX_RETENTION_DAYS` ago are deleted.
+pub async fn run(
+    pg_pool: Arc<Pool>,
+    connection: Arc<Connection>,
+    shutdown: CancellationToken,
+) -> Result<(), Box<dyn Error>> {
+    let config = get_config();
+
+    let channel = connection.create_channel().await?;
+    channel
+        .confirm_select(ConfirmSelectOptions::default())
+        .await?;
+
+    log::info!("outbox relay publishing to {EVENTS_EXCHANGE}");
+    let mut interval = tokio::time::interval(Duration::from_millis(config.outbox_poll_interval));
+    let mut prune_interval = tokio::time::interval(OUTBOX_PRUNE_INTERVAL);
+    loop {
+        tokio::select! {
+            biased;
+            _ = shutdown.cancelled() => break,
+            _ = prune_interval.tick() => {
+                prune(&pg_pool, config.outbox_retention_days).await;
+                continue;
+            }
+            _ = interval.tick() => {}
+        }
+
+        if let Err(err) = relay(&pg_pool, &channel, config.outbox_batch_size).await {
+            log::error!("Outbox relay {}", err);
+        }
+    }
+
+    channel.close(200, "shutdown").await?;
+
+    Ok(())
+}
+
+async fn relay(pg_pool: &Pool, channel: &Channel, batch_size: i64) -> Result<(), DomainError> {
+    let mut client = pg_pool.get().await?;
+    let transaction = client.transaction().await?;
+
+    let events = outbox::find_pending(&transaction, batch_size).await?;
+
+    let mut sent = vec![];
+    for event in events {
+        let id = event.id;
+        if let Err(err) = publish(channel, event).await {
+            log::error!("Outbox publish {} {}", id, err);
+            break;
+        }
+        sent.push(id);
+    }
+
+    outbox::mark_sent(&transaction, &sent).await?;
+    transaction.commit().await?;
+
+    Ok(())
+}
+
+async fn prune(pg_pool: &Pool, retention_days: i32) {
+    match outbox::delete_sent(pg_pool, retention_days, OUTBOX_PRUNE_BATCH_SIZE).await {
+        Ok(deleted) if deleted > 0 => log::info!("Pruned {} sent outbox events", deleted),
+        Ok(_) => {}
+        Err(err) => log::error!("Error to prune outbox {}", err),
+    }
+}
+
+/// Publishes the event in a producer span continuing the trace stored with
+/// it, whose context is sent in the message headers.
+async fn publish(channel: &Channel, event: OutboxEvent) -> Result<(), DomainError> {
+    let span = tracing::info_span!(
+        "amqp_publish",
+        otel.name = %format!("{EVENTS_EXCHANGE} publish"),
+        otel.kind = "producer",
+        messaging.system = "rabbitmq",
+        messaging.destination.name = EVENTS_EXCHANGE,
+        messaging.message.id = %event.id,
+    );
+    if let Err(err) = span.set_parent(telemetry::extract_map(&event.trace_context)) {
+        log::debug!("Error to continue event trace {}", err);
+    }
+
+    publish_event(channel, event).instrument(span).await
+}
+
+async fn publish_event(channel: &Channel, event: OutboxEvent) -> Result<(), DomainError> {
+    let mut headers = FieldTable::default();
+    telemetry::inject_amqp(&mut headers);
+
+    let routing_key = event.event_type.clone();
+    let properties = BasicProperties::default()
+        .with_message_id(event.id.to_string().into())
+        .with_kind(event.event_type.clone().into())
+        .with_content_type("application/json".into())
+        .with_delivery_mode(2)
+        .with_timestamp(event.created_at.timestamp() as u64)
+        .with_headers(headers);
+
+    let payload = serde_json::to_vec(&EventMessage::from(event))
+        .map_err(|err| DomainError::InternalServerError(err.to_string()))?;
+
+    let confirmation = channel
+        .basic_publish(
+            EVENTS_EXCHANGE,
+            &routing_key,
+            BasicPublishOptions::default(),
+            &payload,
+            properties,
+        )
+        .await?
+        .await?;
+
+    if !confirmation.is_ack() {
+        return Err(DomainError::InternalServerError(String::from(
+            "event not confirmed by the broker",
+        )));
+    }
+
+    Ok(())
+}
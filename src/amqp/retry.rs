@@ -0,0 +1,254 @@
+use chrono::Utc;
+use lapin::{
+    message::Delivery,
+    options::{
+        BasicAckOptions, BasicNackOptions, BasicPublishOptions, ExchangeDeclareOptions,
+        QueueBindOptions, QueueDeclareOptions,
+    },
+    types::{AMQPValue, FieldArray, FieldTable},
+    Channel,
+};
+
+use crate::{amqp::config::get_config, domain::error::DomainError, metrics};
+
+pub const RETRY_EXCHANGE: &str = "categories-retry.exchange";
+pub const RETRY_COUNT_HEADER: &str = "x-retry-count";
+pub const RETRY_HISTORY_HEADER: &str = "x-retry-history";
+
+/// Declares one delay queue per attempt. Messages wait in the queue of their
+/// attempt until their TTL expires and are then dead-lettered back to the main
+/// exchange. Every queue holds a single delay, so expirations never block
+/// behind a longer TTL at the head of the queue.
+pub async fn declare(channel: &Channel) -> Result<(), lapin::Error> {
+    let config = get_config();
+
+    channel
+        .exchange_declare(
+            RETRY_EXCHANGE,
+            lapin::ExchangeKind::Direct,
+            ExchangeDeclareOptions {
+                durable: true,
+                ..Default::default()
+            },
+            FieldTable::default(),
+        )
+        .await?;
+
+    for attempt in 1..=config.retry_max_attempts {
+        let queue = retry_queue(attempt);
+
+        let mut queue_field = FieldTable::default();
+        queue_field.insert(
+            "x-dead-letter-exchange".into(),
+            AMQPValue::LongString("categories.exchange".into()),
+        );
+        queue_field.insert(
+            "x-dead-letter-routing-key".into(),
+            AMQPValue::LongString("".into()),
+        );
+
+        channel
+            .queue_declare(
+                &queue,
+                QueueDeclareOptions {
+                    durable: true,
+                    ..Default::default()
+                },
+                queue_field,
+            )
+            .await?;
+        channel
+            .queue_bind(
+                &queue,
+                RETRY_EXCHANGE,
+                &attempt.to_string(),
+                QueueBindOptions::default(),
+                FieldTable::default(),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Schedules the delivery for another attempt, or moves it to the dead letter
+/// exchange once the maximum number of attempts is reached. The original is
+/// only acked after the broker confirms the republished message.
+pub async fn retry_or_dead_letter(
+    channel: &Channel,
+    delivery: &Delivery,
+    err: &DomainError,
+) -> Result<(), lapin::Error> {
+    let config = get_config();
+
+    let headers = delivery.properties.headers().clone().unwrap_or_default();
+    let attempt = retry_count(&headers) + 1;
+    let headers = with_attempt(headers, attempt, &err.to_string());
+
+    let (exchange, routing_key, properties, outcome) = if attempt > config.retry_max_attempts {
+        log::error!("Dead letter after {} attempts {}", attempt - 1, err);
+        (
+            "categories-dead-letter.exchange",
+            String::new(),
+            delivery.properties.clone().with_headers(headers),
+            "dead_letter",
+        )
+    } else {
+        let delay = retry_delay(attempt, config.retry_base_delay);
+        log::warn!("Retry {} in {}ms {}", attempt, delay, err);
+        (
+            RETRY_EXCHANGE,
+            attempt.to_string(),
+            delivery
+                .properties
+                .clone()
+                .with_headers(headers)
+                .with_expiration(delay.to_string().into()),
+            "retry",
+        )
+    };
+
+    let confirmation = channel
+        .basic_publish(
+            exchange,
+            &routing_key,
+            BasicPublishOptions::default(),
+            &delivery.data,
+            properties,
+        )
+        .await?
+        .await?;
+
+    match settlement(confirmation.is_ack(), outcome) {
+        Settlement::Ack(outcome) => {
+            delivery.ack(BasicAckOptions::default()).await?;
+            metrics::inc_amqp_consumer_message(outcome);
+        }
+        Settlement::Nack(options) => {
+            log::error!("Nack retry not confirmed by the broker");
+            delivery.nack(options).await?;
+            metrics::inc_amqp_consumer_message("nack");
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+enum Settlement {
+    Ack(&'static str),
+    Nack(BasicNackOptions),
+}
+
+/// The original is acked once the republished message is confirmed. An
+/// unconfirmed publish requeues it, so a broker hiccup costs a redelivery
+/// instead of the remaining attempts.
+fn settlement(confirmed: bool, outcome: &'static str) -> Settlement {
+    if confirmed {
+        return Settlement::Ack(outcome);
+    }
+
+    Settlement::Nack(BasicNackOptions {
+        requeue: true,
+        ..Default::default()
+    })
+}
+
+fn retry_queue(attempt: u32) -> String {
+    format!("categories-retry.{attempt}.queue")
+}
+
+fn retry_delay(attempt: u32, base_delay: u64) -> u64 {
+    base_delay.saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1)))
+}
+
+fn retry_count(headers: &FieldTable) -> u32 {
+    match headers.inner().get(RETRY_COUNT_HEADER) {
+        Some(AMQPValue::ShortShortUInt(value)) => *value as u32,
+        Some(AMQPValue::ShortUInt(value)) => *value as u32,
+        Some(AMQPValue::LongUInt(value)) => *value,
+        Some(AMQPValue::ShortShortInt(value)) => (*value).max(0) as u32,
+        Some(AMQPValue::ShortInt(value)) => (*value).max(0) as u32,
+        Some(AMQPValue::LongInt(value)) => (*value).max(0) as u32,
+        Some(AMQPValue::LongLongInt(value)) => (*value).clamp(0, u32::MAX as i64) as u32,
+        _ => 0,
+    }
+}
+
+fn with_attempt(mut headers: FieldTable, attempt: u32, error: &str) -> FieldTable {
+    let mut history = headers
+        .inner()
+        .get(RETRY_HISTORY_HEADER)
+        .and_then(|value| value.as_array())
+        .cloned()
+        .unwrap_or_else(|| FieldArray::from(vec![]));
+
+    let mut entry = FieldTable::default();
+    entry.insert("attempt".into(), AMQPValue::LongLongInt(attempt as i64));
+    entry.insert("error".into(), AMQPValue::LongString(error.into()));
+    entry.insert(
+        "failed_at".into(),
+        AMQPValue::Timestamp(Utc::now().timestamp() as u64),
+    );
+    history.push(AMQPValue::FieldTable(entry));
+
+    headers.insert(
+        RETRY_COUNT_HEADER.into(),
+        AMQPValue::LongLongInt(attempt as i64),
+    );
+    headers.insert(RETRY_HISTORY_HEADER.into(), AMQPValue::FieldArray(history));
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_return_exponential_retry_delay() {
+        assert_eq!(retry_delay(1, 1000), 1000);
+        assert_eq!(retry_delay(2, 1000), 2000);
+        assert_eq!(retry_delay(4, 1000), 8000);
+    }
+
+    #[test]
+    fn it_should_requeue_delivery_when_publish_is_not_confirmed() {
+        assert_eq!(settlement(true, "retry"), Settlement::Ack("retry"));
+        assert_eq!(
+            settlement(false, "retry"),
+            Settlement::Nack(BasicNackOptions {
+                multiple: false,
+                requeue: true,
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_track_retry_count_and_history_across_attempts() {
+        let headers = FieldTable::default();
+        assert_eq!(retry_count(&headers), 0);
+
+        let headers = with_attempt(headers, 1, "first error");
+        let headers = with_attempt(headers, 2, "second error");
+
+        assert_eq!(retry_count(&headers), 2);
+
+        let history = headers
+            .inner()
+            .get(RETRY_HISTORY_HEADER)
+            .and_then(|value| value.as_array())
+            .unwrap();
+        assert_eq!(history.as_slice().len(), 2);
+
+        let last = history.as_slice()[1].as_field_table().unwrap();
+        assert_eq!(
+            last.inner()
+                .get("error")
+                .unwrap()
+                .as_long_string()
+                .unwrap()
+                .to_string(),
+            "second error"
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use lazy_static::lazy_static;
-use std::env;
+use std::{collections::HashMap, env, net::IpAddr};
 
 lazy_static! {
     static ref CONFIG: Config = Config::from_env();
@@ -14,6 +14,21 @@ pub struct Config {
     pub web_addr: String,
     pub page_size_default: u32,
     pub page_size_max: u32,
+    pub cache_ttl: Option<usize>,
+    pub idempotency_ttl: usize,
+    pub bulk_max_items: usize,
+    pub jwt_secret: Option<String>,
+    pub jwt_jwks: Option<String>,
+    pub jwt_issuer: Option<String>,
+    pub jwt_audience: Option<String>,
+    pub auth_disabled: bool,
+    pub api_key_cache_ttl: usize,
+    pub rate_limit_requests: Option<u32>,
+    pub rate_limit_window: u64,
+    pub rate_limit_routes: HashMap<String, u32>,
+    pub rate_limit_trusted_proxies: Vec<IpAddr>,
+    pub health_check_timeout: u64,
+    pub shutdown_timeout: u64,
 }
 
 impl Config {
@@ -28,6 +43,87 @@ impl Config {
                 .expect("PAGE_SIZE_MAX must be set")
                 .parse::<u32>()
                 .expect("PAGE_SIZE_MAX must be u32"),
+            cache_ttl: env::var("CACHE_TTL")
+                .ok()
+                .map(|cache_ttl| cache_ttl.parse::<usize>().expect("CACHE_TTL must be usize")),
+            idempotency_ttl: env::var("IDEMPOTENCY_TTL")
+                .unwrap_or_else(|_| String::from("86400"))
+                .parse::<usize>()
+                .expect("IDEMPOTENCY_TTL must be usize"),
+            bulk_max_items: env::var("BULK_MAX_ITEMS")
+                .unwrap_or_else(|_| String::from("1000"))
+                .parse::<usize>()
+                .expect("BULK_MAX_ITEMS must be usize"),
+            jwt_secret: env::var("JWT_SECRET").ok(),
+            jwt_jwks: env::var("JWT_JWKS").ok(),
+            jwt_issuer: env::var("JWT_ISSUER").ok(),
+            jwt_audience: env::var("JWT_AUDIENCE").ok(),
+            auth_disabled: env::var("AUTH_DISABLED")
+                .map(|auth_disabled| {
+                    auth_disabled
+                        .parse::<bool>()
+                        .expect("AUTH_DISABLED must be bool")
+                })
+                .unwrap_or(false),
+            api_key_cache_ttl: env::var("API_KEY_CACHE_TTL")
+                .unwrap_or_else(|_| String::from("60"))
+                .parse::<usize>()
+                .expect("API_KEY_CACHE_TTL must be usize"),
+            rate_limit_requests: env::var("RATE_LIMIT_REQUESTS").ok().map(|requests| {
+                requests
+                    .parse::<u32>()
+                    .expect("RATE_LIMIT_REQUESTS must be u32")
+            }),
+            rate_limit_window: env::var("RATE_LIMIT_WINDOW")
+                .unwrap_or_else(|_| String::from("60"))
+                .parse::<u64>()
+                .expect("RATE_LIMIT_WINDOW must be u64"),
+            rate_limit_routes: env::var("RATE_LIMIT_ROUTES")
+                .map(|routes| parse_rate_limit_routes(&routes))
+                .unwrap_or_default(),
+            rate_limit_trusted_proxies: env::var("RATE_LIMIT_TRUSTED_PROXIES")
+                .map(|proxies| {
+                    proxies
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|proxy| !proxy.is_empty())
+                        .map(|proxy| {
+                            proxy
+                                .parse::<IpAddr>()
+                                .expect("RATE_LIMIT_TRUSTED_PROXIES must be IP addresses")
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            health_check_timeout: env::var("HEALTH_CHECK_TIMEOUT_MS")
+                .unwrap_or_else(|_| String::from("1000"))
+                .parse::<u64>()
+                .expect("HEALTH_CHECK_TIMEOUT_MS must be u64"),
+            shutdown_timeout: env::var("SHUTDOWN_TIMEOUT")
+                .unwrap_or_else(|_| String::from("30"))
+                .parse::<u64>()
+                .expect("SHUTDOWN_TIMEOUT must be u64"),
         }
     }
 }
+
+/// Parses `RATE_LIMIT_ROUTES`, a comma separated list of `METHOD /route=limit`
+/// where the route is the pattern the handler is registered with, e.g.
+/// `POST /categories=10,GET /categories/{category_id}=100`.
+fn parse_rate_limit_routes(routes: &str) -> HashMap<String, u32> {
+    routes
+        .split(',')
+        .map(str::trim)
+        .filter(|route| !route.is_empty())
+        .map(|route| {
+            let (route, limit) = route
+                .rsplit_once('=')
+                .expect("RATE_LIMIT_ROUTES must be METHOD /route=limit");
+            let limit = limit
+                .trim()
+                .parse::<u32>()
+                .expect("RATE_LIMIT_ROUTES limit must be u32");
+            (route.trim().to_owned(), limit)
+        })
+        .collect()
+}
@@ -1,41 +1,197 @@
-use actix_web::{http::StatusCode, HttpResponse};
+use std::collections::{BTreeMap, HashMap};
+
+use actix_web::{error::InternalError, http::StatusCode, HttpRequest, HttpResponse, ResponseError};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use validator::{ValidationErrors, ValidationErrorsKind};
 
 use crate::domain::error::DomainError;
 
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+
+/// RFC 7807 problem details.
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
-    pub message: String,
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    /// Path of the request that failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Id of the request that failed, also sent in the `X-Request-Id` header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Invalid fields, nested fields are joined with `.` and list items
+    /// with `[index]`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub errors: Option<BTreeMap<String, Vec<ErrorField>>>,
 }
 impl ErrorResponse {
-    pub fn new(message: &str) -> Self {
+    pub fn new(status: StatusCode, detail: &str) -> Self {
         Self {
-            message: message.to_owned(),
+            problem_type: String::from("about:blank"),
+            title: status.canonical_reason().unwrap_or_default().to_owned(),
+            status: status.as_u16(),
+            detail: detail.to_owned(),
+            instance: None,
+            request_id: None,
+            errors: None,
         }
     }
+
+    pub fn with_instance(mut self, instance: &str) -> Self {
+        self.instance = Some(instance.to_owned());
+        self
+    }
+
+    pub fn with_request_id(mut self, request_id: &str) -> Self {
+        self.request_id = Some(request_id.to_owned());
+        self
+    }
+
+    pub fn with_errors(mut self, errors: &ValidationErrors) -> Self {
+        let mut fields = BTreeMap::new();
+        flatten_errors(&mut fields, None, errors);
+        self.errors = Some(fields);
+        self
+    }
+
+    pub fn into_response(self) -> HttpResponse {
+        HttpResponse::build(StatusCode::from_u16(self.status).unwrap_or_default())
+            .content_type(PROBLEM_JSON_CONTENT_TYPE)
+            .json(self)
+    }
 }
-impl actix_web::error::ResponseError for DomainError {
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorField {
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[schema(value_type = Object)]
+    pub params: HashMap<String, serde_json::Value>,
+}
+
+fn flatten_errors(
+    fields: &mut BTreeMap<String, Vec<ErrorField>>,
+    prefix: Option<&str>,
+    errors: &ValidationErrors,
+) {
+    for (field, kind) in errors.errors() {
+        let path = match prefix {
+            Some(prefix) => format!("{prefix}.{field}"),
+            None => field.to_string(),
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                fields
+                    .entry(path)
+                    .or_default()
+                    .extend(errors.iter().map(|error| {
+                        ErrorField {
+                            code: error.code.to_string(),
+                            message: error.message.as_ref().map(|message| message.to_string()),
+                            params: error
+                                .params
+                                .iter()
+                                .map(|(key, value)| (key.to_string(), value.clone()))
+                                .collect(),
+                        }
+                    }))
+            }
+            ValidationErrorsKind::Struct(errors) => flatten_errors(fields, Some(&path), errors),
+            ValidationErrorsKind::List(items) => {
+                for (index, errors) in items {
+                    flatten_errors(fields, Some(&format!("{path}[{index}]")), errors);
+                }
+            }
+        }
+    }
+}
+
+/// Error handler of the JSON, query and path extractors.
+pub fn bad_request_handler<E>(err: E, req: &HttpRequest) -> actix_web::Error
+where
+    E: std::fmt::Debug + std::fmt::Display + 'static,
+{
+    let response = ErrorResponse::new(StatusCode::BAD_REQUEST, &err.to_string())
+        .with_instance(req.path())
+        .into_response();
+    InternalError::from_response(err, response).into()
+}
+
+impl ResponseError for DomainError {
     fn error_response(&self) -> HttpResponse {
         match self {
-            DomainError::NotFound(msg) => HttpResponse::NotFound().json(ErrorResponse::new(msg)),
-
-            DomainError::BadRequest(msg) => {
-                HttpResponse::BadRequest().json(ErrorResponse::new(msg))
+            DomainError::Validation(errors) => {
+                ErrorResponse::new(self.status_code(), "Invalid fields")
+                    .with_errors(errors)
+                    .into_response()
             }
-            err => {
-                log::error!("{}", err);
-                HttpResponse::InternalServerError()
-                    .json(ErrorResponse::new("Internal Server Error"))
+
+            DomainError::InternalServerError(_) => {
+                log::error!("{}", self);
+                ErrorResponse::new(self.status_code(), "Internal Server Error").into_response()
             }
+
+            err => ErrorResponse::new(self.status_code(), &err.to_string()).into_response(),
         }
     }
 
     fn status_code(&self) -> StatusCode {
         match self {
             DomainError::NotFound(_) => StatusCode::NOT_FOUND,
-            DomainError::BadRequest(_) => StatusCode::BAD_REQUEST,
-            _ => StatusCode::INTERNAL_SERVER_ERROR,
+            DomainError::BadRequest(_) | DomainError::Validation(_) => StatusCode::BAD_REQUEST,
+            DomainError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            DomainError::Conflict(_) | DomainError::AlreadyProcessed(_) => StatusCode::CONFLICT,
+            DomainError::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+    use validator::Validate;
+
+    use super::*;
+
+    #[derive(Serialize, Validate)]
+    struct Item {
+        #[validate(length(max = 2))]
+        name: String,
+    }
+
+    #[derive(Serialize, Validate)]
+    struct Items {
+        #[validate]
+        items: Vec<Item>,
+    }
+
+    #[test]
+    fn it_should_flatten_nested_field_errors() {
+        let items = Items {
+            items: vec![
+                Item {
+                    name: String::from("ok"),
+                },
+                Item {
+                    name: String::from("too long"),
+                },
+            ],
+        };
+
+        let response = ErrorResponse::new(StatusCode::BAD_REQUEST, "Invalid fields")
+            .with_errors(&items.validate().unwrap_err());
+
+        let errors = response.errors.unwrap();
+        let field = errors.get("items[1].name").unwrap();
+        assert_eq!(field[0].code, "length");
+        assert_eq!(field[0].params.get("max").unwrap(), 2);
+        assert_eq!(response.title, "Bad Request");
+    }
+}
@@ -1,86 +1,147 @@
 use actix_web::{
-    error::InternalError,
-    middleware::Logger,
     web::{self, Data},
-    App, HttpResponse, HttpServer,
+    App, HttpServer,
 };
 use deadpool_postgres::Pool;
-use redis::Client;
+use lapin::Connection;
+use redis::aio::ConnectionManager;
 use serde_qs::actix::QsQueryConfig;
 use std::{error::Error, sync::Arc};
+use tokio_util::sync::CancellationToken;
 
 use crate::{
     api::{
         config,
-        error::ErrorResponse,
+        error::bad_request_handler,
         middleware,
-        resources::{health, swagger, categories},
+        resources::{api_keys, categories, dead_letters, health, metrics, swagger},
+    },
+    domain::{
+        api_keys::repository::ApiKeyRepository, categories::repository::CategoryRepository,
+        dead_letters::repository::DeadLetterRepository, health::repository::HealthRepository,
+    },
+    repository::{
+        api_keys::PgApiKeyRepository, api_keys_cache::CachedApiKeyRepository,
+        categories::PgCategoryRepository, categories_cache::CachedCategoryRepository,
+        dead_letters::AmqpDeadLetterRepository, health::PgHealthRepository, postgres,
     },
-    domain::{categories::repository::CategoryRepository, health::repository::HealthRepository},
-    repository::{categories::PgCategoryRepository, health::PgHealthRepository, postgres},
 };
 
+/// Largest request body, shared by the JSON extractor and the raw body read by
+/// the idempotency middleware.
+pub const PAYLOAD_LIMIT: usize = 2 * 1024 * 1024;
+
 pub struct AppState {
     pub health_repository: Arc<dyn HealthRepository>,
     pub category_repository: Arc<dyn CategoryRepository>,
+    pub dead_letter_repository: Arc<dyn DeadLetterRepository>,
+    pub api_key_repository: Arc<dyn ApiKeyRepository>,
 }
 
-pub async fn run(pg_pool: Arc<Pool>, redis_client: Arc<Client>) -> Result<(), Box<dyn Error>> {
+/// Serves HTTP until `shutdown` is cancelled, then stops accepting connections
+/// and waits up to `SHUTDOWN_TIMEOUT` seconds for the requests in progress.
+pub async fn run(
+    pg_pool: Arc<Pool>,
+    redis_connection: ConnectionManager,
+    amqp_connection: Arc<Connection>,
+    shutdown: CancellationToken,
+) -> Result<(), Box<dyn Error>> {
     postgres::run_migrations().await?;
 
-    let json_config = web::JsonConfig::default().error_handler(|err, _| {
-        let http_error =
-            HttpResponse::BadRequest().json(ErrorResponse::new(err.to_string().as_str()));
-        InternalError::from_response(err, http_error).into()
-    });
+    crate::metrics::register_pool(pg_pool.clone())?;
 
-    let query_config = web::QueryConfig::default().error_handler(|err, _req| {
-        let http_error =
-            HttpResponse::BadRequest().json(ErrorResponse::new(err.to_string().as_str()));
-        InternalError::from_response(err, http_error).into()
-    });
+    let json_config = web::JsonConfig::default()
+        .limit(PAYLOAD_LIMIT)
+        .error_handler(bad_request_handler);
 
-    let path_config = web::PathConfig::default().error_handler(|err, _req| {
-        let http_error =
-            HttpResponse::BadRequest().json(ErrorResponse::new(err.to_string().as_str()));
-        InternalError::from_response(err, http_error).into()
-    });
+    let payload_config = web::PayloadConfig::new(PAYLOAD_LIMIT);
+
+    let query_config = web::QueryConfig::default().error_handler(bad_request_handler);
+
+    let path_config = web::PathConfig::default().error_handler(bad_request_handler);
+
+    let mut category_repository: Arc<dyn CategoryRepository> =
+        Arc::new(PgCategoryRepository::new(pg_pool.clone()));
+    if let Some(cache_ttl) = config::get_config().cache_ttl {
+        category_repository = Arc::new(CachedCategoryRepository::new(
+            category_repository,
+            redis_connection.clone(),
+            cache_ttl,
+        ));
+    }
+
+    let api_key_repository: Arc<dyn ApiKeyRepository> = Arc::new(CachedApiKeyRepository::new(
+        Arc::new(PgApiKeyRepository::new(pg_pool.clone())),
+        redis_connection.clone(),
+        config::get_config().api_key_cache_ttl,
+    ));
 
     let repositories = Data::new(AppState {
         health_repository: Arc::new(PgHealthRepository::new(
             pg_pool.clone(),
-            redis_client.clone(),
+            redis_connection.clone(),
+            amqp_connection.clone(),
         )),
-        category_repository: Arc::new(PgCategoryRepository::new(pg_pool.clone())),
+        category_repository,
+        dead_letter_repository: Arc::new(AmqpDeadLetterRepository::new(amqp_connection.clone())),
+        api_key_repository: api_key_repository.clone(),
     });
 
+    let jwt_validator = middleware::auth::JwtValidator::from_config(config::get_config())
+        .await?
+        .map(Arc::new);
+
     let web_addr = &config::get_config().web_addr;
     println!("server listener in: {web_addr}");
 
-    HttpServer::new(move || {
+    let server = HttpServer::new(move || {
         let qs_config = QsQueryConfig::default()
-            .error_handler(|err, _| {
-                let http_error =
-                    HttpResponse::BadRequest().json(ErrorResponse::new(err.to_string().as_str()));
-                InternalError::from_response(err, http_error).into()
-            })
+            .error_handler(bad_request_handler)
             .qs_config(serde_qs::Config::new(5, false));
 
         App::new()
-            .wrap(Logger::default())
+            .wrap(middleware::idempotency::Idempotency::new(
+                redis_connection.clone(),
+                config::get_config().idempotency_ttl,
+            ))
+            .wrap(middleware::api_key::ApiKeyAuthentication::new(
+                api_key_repository.clone(),
+            ))
+            .wrap(middleware::auth::Authentication::new(jwt_validator.clone()))
+            .wrap(middleware::rate_limit::RateLimit::new(
+                redis_connection.clone(),
+                middleware::rate_limit::RateLimitPolicy::from_config(config::get_config()),
+            ))
+            .wrap(middleware::problem::ProblemInstance)
+            .wrap(middleware::metrics::Metrics)
+            .wrap(middleware::trace::RequestTrace)
+            .wrap(middleware::request_id::RequestIdentifier)
             .wrap(middleware::cors::default())
             .app_data(json_config.to_owned())
+            .app_data(payload_config.to_owned())
             .app_data(qs_config)
             .app_data(query_config.to_owned())
             .app_data(path_config.to_owned())
             .app_data(repositories.to_owned())
             .configure(swagger::routes::init_routes)
             .configure(health::routes::init_routes)
+            .configure(metrics::routes::init_routes)
             .configure(categories::routes::init_routes)
+            .configure(dead_letters::routes::init_routes)
+            .configure(api_keys::routes::init_routes)
     })
+    .disable_signals()
+    .shutdown_timeout(config::get_config().shutdown_timeout)
     .bind(web_addr)?
-    .run()
-    .await?;
+    .run();
+
+    let server_handle = server.handle();
+    actix_web::rt::spawn(async move {
+        shutdown.cancelled().await;
+        server_handle.stop(true).await;
+    });
+
+    server.await?;
 
     Ok(())
 }
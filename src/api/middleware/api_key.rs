@@ -0,0 +1,101 @@
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+    sync::Arc,
+};
+
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error, HttpMessage, ResponseError,
+};
+use futures::future::LocalBoxFuture;
+
+use crate::{
+    api::middleware::auth::{unauthorized, Principal},
+    domain::{api_keys, api_keys::repository::ApiKeyRepository},
+};
+
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+
+/// Authenticates machine-to-machine callers sending an `X-Api-Key` header and
+/// stores the key scopes as the request `Principal`. Unknown or revoked keys
+/// are answered with 401, requests without the header go through untouched.
+pub struct ApiKeyAuthentication {
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+}
+impl ApiKeyAuthentication {
+    pub fn new(api_key_repository: Arc<dyn ApiKeyRepository>) -> Self {
+        Self { api_key_repository }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ApiKeyAuthentication
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = ApiKeyAuthenticationMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ApiKeyAuthenticationMiddleware {
+            service: Rc::new(service),
+            api_key_repository: self.api_key_repository.clone(),
+        }))
+    }
+}
+
+pub struct ApiKeyAuthenticationMiddleware<S> {
+    service: Rc<S>,
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+}
+
+impl<S, B> Service<ServiceRequest> for ApiKeyAuthenticationMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let api_key_repository = self.api_key_repository.clone();
+
+        Box::pin(async move {
+            let key = req
+                .headers()
+                .get(API_KEY_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from);
+
+            let Some(key) = key else {
+                return Ok(service.call(req).await?.map_into_boxed_body());
+            };
+
+            match api_keys::resources::authenticate::execute(api_key_repository, &key).await {
+                Ok(Some(api_key)) => {
+                    log::debug!("Authenticated API key {}", api_key.prefix);
+                    req.extensions_mut().insert(Principal {
+                        subject: api_key.id.to_string(),
+                        scopes: api_key.scopes.into_iter().collect(),
+                    });
+                }
+                Ok(None) => {
+                    let response = unauthorized("Invalid API key", req.path());
+                    return Ok(req.into_response(response));
+                }
+                Err(err) => return Ok(req.into_response(err.error_response())),
+            }
+
+            Ok(service.call(req).await?.map_into_boxed_body())
+        })
+    }
+}
@@ -0,0 +1,412 @@
+use std::{
+    collections::HashSet,
+    error::Error as StdError,
+    future::{ready, Ready},
+    marker::PhantomData,
+    rc::Rc,
+    sync::Arc,
+};
+
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform},
+    error::InternalError,
+    http::{header, StatusCode},
+    Error, FromRequest, HttpMessage, HttpRequest, HttpResponse,
+};
+use futures::future::LocalBoxFuture;
+use jsonwebtoken::{
+    decode, decode_header,
+    jwk::{AlgorithmParameters, JwkSet},
+    Algorithm, DecodingKey, Validation,
+};
+use serde::Deserialize;
+
+use crate::api::{config::Config, error::ErrorResponse};
+
+/// Keys and claims used to validate bearer tokens. HS256 tokens are checked
+/// with the shared secret and RS256 tokens with the key of the JWKS matching
+/// the `kid` header.
+pub struct JwtValidator {
+    secret: Option<DecodingKey>,
+    jwks: Option<JwkSet>,
+    issuer: Option<String>,
+    audience: Option<String>,
+}
+impl JwtValidator {
+    pub fn new(
+        secret: Option<&str>,
+        jwks: Option<JwkSet>,
+        issuer: Option<String>,
+        audience: Option<String>,
+    ) -> Self {
+        Self {
+            secret: secret.map(|secret| DecodingKey::from_secret(secret.as_bytes())),
+            jwks,
+            issuer,
+            audience,
+        }
+    }
+
+    /// Builds the validator from `JWT_SECRET` and `JWT_JWKS`, the JWKS is read
+    /// once from a local file or downloaded when it is an URL. Returns `None`
+    /// only when `AUTH_DISABLED=true`, without it a missing secret and JWKS is
+    /// an error so the service never starts unauthenticated by accident.
+    pub async fn from_config(config: &Config) -> Result<Option<Self>, Box<dyn StdError>> {
+        if config.auth_disabled {
+            return Ok(None);
+        }
+        if config.jwt_secret.is_none() && config.jwt_jwks.is_none() {
+            return Err("JWT_SECRET or JWT_JWKS must be set, or AUTH_DISABLED=true".into());
+        }
+
+        let jwks = match &config.jwt_jwks {
+            Some(jwks) if jwks.starts_with("http://") || jwks.starts_with("https://") => {
+                Some(reqwest::get(jwks).await?.error_for_status()?.json().await?)
+            }
+            Some(jwks) => Some(serde_json::from_slice(&std::fs::read(jwks)?)?),
+            None => None,
+        };
+
+        Ok(Some(Self::new(
+            config.jwt_secret.as_deref(),
+            jwks,
+            config.jwt_issuer.clone(),
+            config.jwt_audience.clone(),
+        )))
+    }
+
+    pub fn validate(&self, token: &str) -> Result<Principal, String> {
+        let token_header = decode_header(token).map_err(|err| err.to_string())?;
+
+        let key = match token_header.alg {
+            Algorithm::HS256 => self.secret.clone(),
+            Algorithm::RS256 => self.jwks_key(token_header.kid.as_deref())?,
+            alg => return Err(format!("Unsupported token algorithm {alg:?}")),
+        };
+        let key = key.ok_or_else(|| format!("No key to validate {:?}", token_header.alg))?;
+
+        let mut validation = Validation::new(token_header.alg);
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        if let Some(audience) = &self.audience {
+            validation.set_audience(&[audience]);
+        }
+
+        let claims = decode::<Claims>(token, &key, &validation)
+            .map_err(|err| err.to_string())?
+            .claims;
+
+        Ok(Principal {
+            subject: claims.sub,
+            scopes: claims.scope.split_whitespace().map(String::from).collect(),
+        })
+    }
+
+    fn jwks_key(&self, kid: Option<&str>) -> Result<Option<DecodingKey>, String> {
+        let Some(jwks) = &self.jwks else {
+            return Ok(None);
+        };
+
+        let jwk = match kid {
+            Some(kid) => jwks.find(kid),
+            None => jwks.keys.first(),
+        };
+
+        match jwk {
+            Some(jwk) if matches!(jwk.algorithm, AlgorithmParameters::RSA(_)) => {
+                DecodingKey::from_jwk(jwk)
+                    .map(Some)
+                    .map_err(|err| err.to_string())
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Claims {
+    sub: String,
+    /// Space separated scopes, as in OAuth 2.0.
+    #[serde(default)]
+    scope: String,
+}
+
+/// Caller of the request. Only the scopes in `scopes` are granted, callers
+/// without any are refused by every route requiring a scope.
+#[derive(Debug, Clone)]
+pub struct Principal {
+    pub subject: String,
+    pub scopes: HashSet<String>,
+}
+impl Principal {
+    /// Caller of every request when `AUTH_DISABLED=true`, explicitly granted
+    /// the scopes listed in `SCOPES`.
+    fn anonymous() -> Self {
+        Self {
+            subject: String::from("anonymous"),
+            scopes: SCOPES.iter().map(|scope| scope.to_string()).collect(),
+        }
+    }
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.contains(scope)
+    }
+}
+
+/// Validates the `Authorization: Bearer` token and stores the `Principal` in
+/// the request extensions. Requests without token go through so public routes
+/// keep working, routes requiring a scope reject them with `Authorized`.
+pub struct Authentication {
+    validator: Option<Arc<JwtValidator>>,
+}
+impl Authentication {
+    pub fn new(validator: Option<Arc<JwtValidator>>) -> Self {
+        if validator.is_none() {
+            log::warn!("Authentication disabled by AUTH_DISABLED, every request is allowed");
+        }
+        Self { validator }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Authentication
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = AuthenticationMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuthenticationMiddleware {
+            service: Rc::new(service),
+            validator: self.validator.clone(),
+        }))
+    }
+}
+
+pub struct AuthenticationMiddleware<S> {
+    service: Rc<S>,
+    validator: Option<Arc<JwtValidator>>,
+}
+
+impl<S, B> Service<ServiceRequest> for AuthenticationMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let validator = self.validator.clone();
+
+        Box::pin(async move {
+            let Some(validator) = validator else {
+                req.extensions_mut().insert(Principal::anonymous());
+                return Ok(service.call(req).await?.map_into_boxed_body());
+            };
+
+            let token = req
+                .headers()
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| {
+                    value
+                        .strip_prefix("Bearer ")
+                        .ok_or_else(|| String::from("Authorization must be a Bearer token"))
+                });
+
+            match token {
+                Some(Ok(token)) => match validator.validate(token) {
+                    Ok(principal) => {
+                        log::debug!("Authenticated {}", principal.subject);
+                        req.extensions_mut().insert(principal);
+                    }
+                    Err(err) => {
+                        let response =
+                            unauthorized(&format!("Invalid bearer token, {err}"), req.path());
+                        return Ok(req.into_response(response));
+                    }
+                },
+                Some(Err(err)) => {
+                    let response = unauthorized(&err, req.path());
+                    return Ok(req.into_response(response));
+                }
+                None => {}
+            }
+
+            Ok(service.call(req).await?.map_into_boxed_body())
+        })
+    }
+}
+
+pub fn unauthorized(detail: &str, path: &str) -> HttpResponse {
+    let mut response = ErrorResponse::new(StatusCode::UNAUTHORIZED, detail)
+        .with_instance(path)
+        .into_response();
+    response.headers_mut().insert(
+        header::WWW_AUTHENTICATE,
+        header::HeaderValue::from_static("Bearer"),
+    );
+    response
+}
+
+/// Scopes that can be granted to tokens and API keys.
+pub const SCOPES: [&str; 7] = [
+    CategoriesRead::NAME,
+    CategoriesWrite::NAME,
+    CategoriesAdmin::NAME,
+    DeadLettersRead::NAME,
+    DeadLettersWrite::NAME,
+    ApiKeysRead::NAME,
+    ApiKeysWrite::NAME,
+];
+
+/// Scope required by a route.
+pub trait Scope {
+    const NAME: &'static str;
+}
+
+pub struct CategoriesRead;
+impl Scope for CategoriesRead {
+    const NAME: &'static str = "categories:read";
+}
+
+pub struct CategoriesWrite;
+impl Scope for CategoriesWrite {
+    const NAME: &'static str = "categories:write";
+}
+
+/// Irreversible category operations, such as purging a category.
+pub struct CategoriesAdmin;
+impl Scope for CategoriesAdmin {
+    const NAME: &'static str = "categories:admin";
+}
+
+pub struct DeadLettersRead;
+impl Scope for DeadLettersRead {
+    const NAME: &'static str = "dead_letters:read";
+}
+
+pub struct DeadLettersWrite;
+impl Scope for DeadLettersWrite {
+    const NAME: &'static str = "dead_letters:write";
+}
+
+pub struct ApiKeysRead;
+impl Scope for ApiKeysRead {
+    const NAME: &'static str = "api_keys:read";
+}
+
+pub struct ApiKeysWrite;
+impl Scope for ApiKeysWrite {
+    const NAME: &'static str = "api_keys:write";
+}
+
+/// Guards a handler with the scope `S`, answering 401 when the request has no
+/// token and 403 when the token lacks the scope. Handlers needing the caller
+/// read the `Principal` with `ReqData`.
+pub struct Authorized<S: Scope>(PhantomData<S>);
+
+impl<S: Scope> FromRequest for Authorized<S> {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let principal = req.extensions().get::<Principal>().cloned();
+
+        let result = match principal {
+            Some(principal) if principal.has_scope(S::NAME) => Ok(Self(PhantomData)),
+            Some(_) => {
+                let detail = format!("Scope {} is required", S::NAME);
+                let response = ErrorResponse::new(StatusCode::FORBIDDEN, &detail)
+                    .with_instance(req.path())
+                    .into_response();
+                Err(InternalError::from_response(detail, response).into())
+            }
+            None => {
+                let detail = String::from("Bearer token is required");
+                let response = unauthorized(&detail, req.path());
+                Err(InternalError::from_response(detail, response).into())
+            }
+        };
+
+        ready(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonwebtoken::{encode, get_current_timestamp, EncodingKey, Header};
+    use serde_json::json;
+
+    use super::*;
+
+    fn token(secret: &str, claims: serde_json::Value) -> String {
+        encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn it_should_validate_hs256_token_scopes() {
+        let validator = JwtValidator::new(Some("secret"), None, Some(String::from("issuer")), None);
+
+        let principal = validator
+            .validate(&token(
+                "secret",
+                json!({
+                    "sub": "user",
+                    "iss": "issuer",
+                    "scope": "categories:read categories:write",
+                    "exp": get_current_timestamp() + 60,
+                }),
+            ))
+            .unwrap();
+
+        assert_eq!(principal.subject, "user");
+        assert!(principal.has_scope("categories:write"));
+        assert!(!principal.has_scope("dead_letters:read"));
+    }
+
+    #[test]
+    fn it_should_not_grant_scopes_missing_from_principal() {
+        let principal = Principal {
+            subject: String::from("user"),
+            scopes: HashSet::new(),
+        };
+
+        assert!(SCOPES.iter().all(|scope| !principal.has_scope(scope)));
+    }
+
+    #[test]
+    fn it_should_reject_token_with_wrong_secret_or_issuer() {
+        let validator = JwtValidator::new(Some("secret"), None, Some(String::from("issuer")), None);
+        let exp = get_current_timestamp() + 60;
+
+        assert!(validator
+            .validate(&token(
+                "other",
+                json!({"sub": "user", "iss": "issuer", "exp": exp})
+            ))
+            .is_err());
+        assert!(validator
+            .validate(&token(
+                "secret",
+                json!({"sub": "user", "iss": "other", "exp": exp})
+            ))
+            .is_err());
+    }
+}
@@ -0,0 +1,344 @@
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+};
+
+use actix_http::h1;
+use actix_web::{
+    body::{self, BoxBody, MessageBody},
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{self, HeaderValue},
+        Method, StatusCode,
+    },
+    web::Bytes,
+    Error, HttpMessage, HttpResponse,
+};
+use futures::future::LocalBoxFuture;
+use redis::{aio::ConnectionManager, AsyncCommands};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    api::{error::ErrorResponse, middleware::auth::Principal},
+    metrics,
+};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+/// Route patterns whose POST requests honor `Idempotency-Key`.
+const IDEMPOTENT_ROUTES: [&str; 2] = ["/categories", "/categories/bulk"];
+
+/// Stored under the idempotency key. `status` stays empty while the first
+/// request is still being handled.
+#[derive(Debug, Serialize, Deserialize)]
+struct IdempotencyRecord {
+    fingerprint: String,
+    status: Option<u16>,
+    content_type: Option<String>,
+    body: String,
+}
+
+/// Replays the first response of authenticated POST requests to
+/// `IDEMPOTENT_ROUTES` carrying an `Idempotency-Key` header. Keys are scoped to
+/// the principal subject, reused with a different payload they are answered
+/// with 422 and while their first request is in flight with 409. Server errors
+/// and `Cache-Control: no-store` responses are not stored so the request can be
+/// retried with the same key.
+pub struct Idempotency {
+    redis_connection: ConnectionManager,
+    ttl: usize,
+}
+impl Idempotency {
+    pub fn new(redis_connection: ConnectionManager, ttl: usize) -> Self {
+        Self {
+            redis_connection,
+            ttl,
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Idempotency
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = IdempotencyMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(IdempotencyMiddleware {
+            service: Rc::new(service),
+            redis_connection: self.redis_connection.clone(),
+            ttl: self.ttl,
+        }))
+    }
+}
+
+pub struct IdempotencyMiddleware<S> {
+    service: Rc<S>,
+    redis_connection: ConnectionManager,
+    ttl: usize,
+}
+
+impl<S, B> Service<ServiceRequest> for IdempotencyMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let mut connection = self.redis_connection.clone();
+        let ttl = self.ttl;
+
+        Box::pin(async move {
+            let idempotency_key = req
+                .headers()
+                .get(IDEMPOTENCY_KEY_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from);
+
+            let subject = req
+                .extensions()
+                .get::<Principal>()
+                .map(|principal| principal.subject.clone());
+            let idempotent_route = req.method() == Method::POST
+                && req
+                    .match_pattern()
+                    .is_some_and(|pattern| IDEMPOTENT_ROUTES.contains(&pattern.as_str()));
+
+            let (idempotency_key, subject) = match (idempotency_key, subject) {
+                (Some(idempotency_key), Some(subject)) if idempotent_route => {
+                    (idempotency_key, subject)
+                }
+                _ => return Ok(service.call(req).await?.map_into_boxed_body()),
+            };
+
+            let payload = req.extract::<Bytes>().await?;
+            let target = req
+                .uri()
+                .path_and_query()
+                .map_or(req.path(), |path_and_query| path_and_query.as_str());
+            let fingerprint = fingerprint(target, &payload);
+
+            let (_, mut restored_payload) = h1::Payload::create(true);
+            restored_payload.unread_data(payload);
+            req.set_payload(restored_payload.into());
+
+            let key = storage_key(&subject, &idempotency_key);
+
+            let pending = IdempotencyRecord {
+                fingerprint: fingerprint.clone(),
+                status: None,
+                content_type: None,
+                body: String::new(),
+            };
+            let acquired: Result<bool, _> = metrics::time_redis(
+                "set",
+                redis::cmd("SET")
+                    .arg(&key)
+                    .arg(serde_json::to_vec(&pending)?)
+                    .arg("NX")
+                    .arg("EX")
+                    .arg(ttl)
+                    .query_async::<_, Option<String>>(&mut connection),
+            )
+            .await
+            .map(|result| result.is_some());
+
+            match acquired {
+                Ok(true) => {}
+                Ok(false) => {
+                    let stored: Option<Vec<u8>> = metrics::time_redis("get", connection.get(&key))
+                        .await
+                        .unwrap_or(None);
+                    let record = stored.and_then(|stored| {
+                        serde_json::from_slice::<IdempotencyRecord>(&stored).ok()
+                    });
+
+                    let response = match record {
+                        Some(record) if record.fingerprint != fingerprint => ErrorResponse::new(
+                            StatusCode::UNPROCESSABLE_ENTITY,
+                            "Idempotency-Key was already used with a different payload",
+                        )
+                        .with_instance(req.path())
+                        .into_response(),
+                        Some(record) => replay(record, req.path()),
+                        None => ErrorResponse::new(
+                            StatusCode::CONFLICT,
+                            "Idempotency-Key request is expired or unreadable, retry",
+                        )
+                        .with_instance(req.path())
+                        .into_response(),
+                    };
+
+                    return Ok(req.into_response(response));
+                }
+                Err(err) => {
+                    log::error!("Idempotency disabled, redis unavailable {}", err);
+                    return Ok(service.call(req).await?.map_into_boxed_body());
+                }
+            }
+
+            let res = match service.call(req).await {
+                Ok(res) => res,
+                Err(err) => {
+                    let _ = metrics::time_redis("del", connection.del::<_, ()>(&key)).await;
+                    return Err(err);
+                }
+            };
+
+            let status = res.status();
+            if status.is_server_error() || no_store(&res) {
+                let _ = metrics::time_redis("del", connection.del::<_, ()>(&key)).await;
+                return Ok(res.map_into_boxed_body());
+            }
+
+            let content_type = res
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from);
+
+            let (req, res) = res.into_parts();
+            let (res, res_body) = res.into_parts();
+            let res_body = match body::to_bytes(res_body).await {
+                Ok(res_body) => res_body,
+                Err(_) => {
+                    let _ = metrics::time_redis("del", connection.del::<_, ()>(&key)).await;
+                    let response = ErrorResponse::new(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "Internal Server Error",
+                    )
+                    .with_instance(req.path())
+                    .into_response();
+                    return Ok(ServiceResponse::new(req, response));
+                }
+            };
+
+            let record = IdempotencyRecord {
+                fingerprint,
+                status: Some(status.as_u16()),
+                content_type,
+                body: String::from_utf8_lossy(&res_body).to_string(),
+            };
+            if let Err(err) = metrics::time_redis(
+                "set",
+                connection.set_ex::<_, _, ()>(&key, serde_json::to_vec(&record)?, ttl),
+            )
+            .await
+            {
+                log::error!("Error to store idempotent response {}", err);
+            }
+
+            Ok(ServiceResponse::new(
+                req,
+                res.set_body(BoxBody::new(res_body)),
+            ))
+        })
+    }
+}
+
+/// The subject is hashed so subjects containing `:` cannot collide with the
+/// key of another subject.
+fn storage_key(subject: &str, idempotency_key: &str) -> String {
+    let subject = hex::encode(Sha256::digest(subject.as_bytes()));
+    format!("idempotency:{subject}:{idempotency_key}")
+}
+
+fn no_store<B>(res: &ServiceResponse<B>) -> bool {
+    res.headers()
+        .get_all(header::CACHE_CONTROL)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|directive| directive.trim().eq_ignore_ascii_case("no-store"))
+}
+
+/// The query string is part of the target, `?atomic=true` and
+/// `?atomic=false` are different requests.
+fn fingerprint(target: &str, payload: &Bytes) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(target.as_bytes());
+    hasher.update(payload);
+    hex::encode(hasher.finalize())
+}
+
+fn replay(record: IdempotencyRecord, path: &str) -> HttpResponse {
+    let status = match record
+        .status
+        .and_then(|status| StatusCode::from_u16(status).ok())
+    {
+        Some(status) => status,
+        None => {
+            return ErrorResponse::new(
+                StatusCode::CONFLICT,
+                "Idempotency-Key request is still being processed",
+            )
+            .with_instance(path)
+            .into_response()
+        }
+    };
+
+    let mut response = HttpResponse::build(status);
+    response.insert_header((IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true")));
+    if let Some(content_type) = record.content_type {
+        response.insert_header((header::CONTENT_TYPE, content_type));
+    }
+
+    response.body(record.body)
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::header::CacheControl, http::header::CacheDirective, test::TestRequest};
+
+    use super::*;
+
+    #[test]
+    fn it_should_scope_storage_key_to_subject() {
+        assert_ne!(storage_key("a:b", "c"), storage_key("a", "b:c"));
+        assert_ne!(storage_key("alice", "key"), storage_key("bob", "key"));
+        assert_eq!(storage_key("alice", "key"), storage_key("alice", "key"));
+    }
+
+    #[test]
+    fn it_should_fingerprint_the_query_string() {
+        let payload = Bytes::from_static(b"[]");
+        assert_ne!(
+            fingerprint("/categories/bulk?atomic=true", &payload),
+            fingerprint("/categories/bulk?atomic=false", &payload)
+        );
+        assert_eq!(
+            fingerprint("/categories/bulk?atomic=true", &payload),
+            fingerprint("/categories/bulk?atomic=true", &payload)
+        );
+    }
+
+    #[test]
+    fn it_should_detect_no_store_response() {
+        let req = TestRequest::default().to_http_request();
+        let res = ServiceResponse::new(
+            req.clone(),
+            HttpResponse::Created()
+                .insert_header(CacheControl(vec![
+                    CacheDirective::Private,
+                    CacheDirective::NoStore,
+                ]))
+                .finish(),
+        );
+        assert!(no_store(&res));
+
+        let res = ServiceResponse::new(req, HttpResponse::Created().finish());
+        assert!(!no_store(&res));
+    }
+}
@@ -1 +1,9 @@
+pub mod api_key;
+pub mod auth;
 pub mod cors;
+pub mod idempotency;
+pub mod metrics;
+pub mod problem;
+pub mod rate_limit;
+pub mod request_id;
+pub mod trace;
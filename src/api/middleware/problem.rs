@@ -0,0 +1,115 @@
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+};
+
+use actix_web::{
+    body::{self, BoxBody, MessageBody},
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{header, StatusCode},
+    Error, HttpMessage, HttpRequest,
+};
+use futures::future::LocalBoxFuture;
+
+use crate::api::{
+    error::{ErrorResponse, PROBLEM_JSON_CONTENT_TYPE},
+    middleware::request_id::RequestId,
+};
+
+/// Sets the request path as `instance` and the `RequestId` as `request_id` of
+/// problem details responses built without access to the request, such as the
+/// ones of `DomainError`.
+pub struct ProblemInstance;
+
+impl<S, B> Transform<S, ServiceRequest> for ProblemInstance
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = ProblemInstanceMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ProblemInstanceMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct ProblemInstanceMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for ProblemInstanceMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            let res = service.call(req).await?;
+
+            let is_problem = res
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .map(|value| value == PROBLEM_JSON_CONTENT_TYPE)
+                .unwrap_or(false);
+            if !is_problem {
+                return Ok(res.map_into_boxed_body());
+            }
+
+            let (req, res) = res.into_parts();
+            let (res, res_body) = res.into_parts();
+            let res_body = match body::to_bytes(res_body).await {
+                Ok(res_body) => res_body,
+                Err(_) => {
+                    let response = complete(
+                        ErrorResponse::new(
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            "Internal Server Error",
+                        ),
+                        &req,
+                    )
+                    .into_response();
+                    return Ok(ServiceResponse::new(req, response));
+                }
+            };
+
+            let res_body = match serde_json::from_slice::<ErrorResponse>(&res_body) {
+                Ok(problem) if problem.instance.is_none() || problem.request_id.is_none() => {
+                    serde_json::to_vec(&complete(problem, &req))?.into()
+                }
+                _ => res_body,
+            };
+
+            Ok(ServiceResponse::new(
+                req,
+                res.set_body(BoxBody::new(res_body)),
+            ))
+        })
+    }
+}
+
+/// Fills the `instance` and `request_id` the problem was built without.
+fn complete(mut problem: ErrorResponse, req: &HttpRequest) -> ErrorResponse {
+    if problem.instance.is_none() {
+        problem = problem.with_instance(req.path());
+    }
+    if problem.request_id.is_none() {
+        if let Some(RequestId(request_id)) = req.extensions().get::<RequestId>() {
+            problem = problem.with_request_id(request_id);
+        }
+    }
+    problem
+}
@@ -0,0 +1,516 @@
+use std::{
+    collections::HashMap,
+    future::{ready, Ready},
+    net::IpAddr,
+    rc::Rc,
+    sync::Arc,
+};
+
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{self, HeaderMap, HeaderName, HeaderValue},
+        StatusCode,
+    },
+    Error,
+};
+use futures::future::LocalBoxFuture;
+use lazy_static::lazy_static;
+use redis::{aio::ConnectionManager, Script};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    api::{config::Config, error::ErrorResponse, middleware::api_key::API_KEY_HEADER},
+    metrics,
+};
+
+pub const RATE_LIMIT_LIMIT_HEADER: HeaderName = HeaderName::from_static("ratelimit-limit");
+pub const RATE_LIMIT_REMAINING_HEADER: HeaderName = HeaderName::from_static("ratelimit-remaining");
+pub const RATE_LIMIT_RESET_HEADER: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+lazy_static! {
+    /// GCRA over the theoretical arrival time stored in `KEYS[1]`, using the
+    /// redis clock so every replica shares the same time. The request is only
+    /// counted when `ARGV[3]` is 1. Returns whether the request is allowed,
+    /// the remaining requests and the milliseconds until the quota is
+    /// restored and until the next request is allowed.
+    static ref GCRA_SCRIPT: Script = Script::new(
+        r"
+        local emission = tonumber(ARGV[1])
+        local window = tonumber(ARGV[2])
+        local time = redis.call('TIME')
+        local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+        local tat = tonumber(redis.call('GET', KEYS[1]))
+        if not tat or tat < now then
+            tat = now
+        end
+
+        local new_tat = tat + emission
+        if new_tat - now > window then
+            return {0, 0, tat - now, new_tat - now - window}
+        end
+
+        if ARGV[3] == '1' then
+            redis.call('SET', KEYS[1], new_tat, 'PX', new_tat - now)
+        end
+        return {1, math.floor((window - (new_tat - now)) / emission), new_tat - now, 0}
+        "
+    );
+}
+
+/// Probes and metric scrapes are never limited by the default limit.
+const EXEMPT_ROUTES: [&str; 3] = ["/health/live", "/health/ready", "/metrics"];
+
+/// Limits applied by `RateLimit`. Routes are identified by their method and
+/// registered pattern, routes without their own limit share the default one,
+/// except the `EXEMPT_ROUTES`.
+/// `X-Forwarded-For` is only read from the `trusted_proxies`.
+#[derive(Debug, Clone)]
+pub struct RateLimitPolicy {
+    window: u64,
+    default_limit: Option<u32>,
+    routes: HashMap<String, u32>,
+    trusted_proxies: Vec<IpAddr>,
+}
+impl RateLimitPolicy {
+    pub fn new(window: u64, default_limit: Option<u32>, routes: HashMap<String, u32>) -> Self {
+        Self {
+            window,
+            default_limit,
+            routes,
+            trusted_proxies: Vec::new(),
+        }
+    }
+
+    pub fn with_trusted_proxies(mut self, trusted_proxies: Vec<IpAddr>) -> Self {
+        self.trusted_proxies = trusted_proxies;
+        self
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(
+            config.rate_limit_window,
+            config.rate_limit_requests,
+            config.rate_limit_routes.clone(),
+        )
+        .with_trusted_proxies(config.rate_limit_trusted_proxies.clone())
+    }
+
+    /// Returns the bucket of the route and its limit, or `None` when the
+    /// route is not limited.
+    fn limit_for(&self, method: &str, pattern: Option<&str>) -> Option<(String, u32)> {
+        if let Some(pattern) = pattern {
+            let route = format!("{method} {pattern}");
+            if let Some(limit) = self.routes.get(&route) {
+                return Some((route, *limit));
+            }
+            if EXEMPT_ROUTES.contains(&pattern) {
+                return None;
+            }
+        }
+
+        self.default_limit
+            .map(|limit| (String::from("default"), limit))
+    }
+}
+
+#[derive(Debug)]
+struct RateLimitDecision {
+    allowed: bool,
+    remaining: u64,
+    reset: u64,
+    retry_after: u64,
+}
+
+/// Limits requests per caller across every replica with a GCRA stored in
+/// redis. It runs before authentication, so callers are identified by a hash
+/// of the API key or bearer token they present, falling back to the client IP.
+/// Requests rejected with 401 are also counted per client IP, and once that
+/// limit is reached every request presenting credentials from the IP is
+/// limited, so guessing credentials is limited too. Limited requests are
+/// answered with 429 and a `Retry-After` header, and redis failures let the
+/// request go through.
+pub struct RateLimit {
+    redis_connection: ConnectionManager,
+    policy: Arc<RateLimitPolicy>,
+}
+impl RateLimit {
+    pub fn new(redis_connection: ConnectionManager, policy: RateLimitPolicy) -> Self {
+        Self {
+            redis_connection,
+            policy: Arc::new(policy),
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = RateLimitMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitMiddleware {
+            service: Rc::new(service),
+            redis_connection: self.redis_connection.clone(),
+            policy: self.policy.clone(),
+        }))
+    }
+}
+
+pub struct RateLimitMiddleware<S> {
+    service: Rc<S>,
+    redis_connection: ConnectionManager,
+    policy: Arc<RateLimitPolicy>,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let mut connection = self.redis_connection.clone();
+        let policy = self.policy.clone();
+
+        Box::pin(async move {
+            let pattern = req.match_pattern();
+            let Some((route, limit)) = policy.limit_for(req.method().as_str(), pattern.as_deref())
+            else {
+                return Ok(service.call(req).await?.map_into_boxed_body());
+            };
+
+            let ip = client(&req, &policy.trusted_proxies);
+            let credential = credential(&req);
+            let failures_key = format!("rate_limit:{route}:failed:{ip}");
+
+            let decision = async {
+                if credential.is_some() {
+                    let failures =
+                        check(&mut connection, &failures_key, limit, policy.window, false).await?;
+                    if !failures.allowed {
+                        return Ok(failures);
+                    }
+                }
+
+                let key = match &credential {
+                    Some(credential) => format!("rate_limit:{route}:credential:{credential}"),
+                    None => format!("rate_limit:{route}:{ip}"),
+                };
+                check(&mut connection, &key, limit, policy.window, true).await
+            }
+            .await;
+            let decision = match decision {
+                Ok(decision) => decision,
+                Err(err) => {
+                    log::error!("Rate limit disabled, redis unavailable {}", err);
+                    return Ok(service.call(req).await?.map_into_boxed_body());
+                }
+            };
+
+            if !decision.allowed {
+                let mut response = ErrorResponse::new(
+                    StatusCode::TOO_MANY_REQUESTS,
+                    &format!("Rate limit of {limit} requests exceeded, retry later"),
+                )
+                .with_instance(req.path())
+                .into_response();
+
+                insert_headers(response.headers_mut(), limit, &decision);
+                response.headers_mut().insert(
+                    header::RETRY_AFTER,
+                    HeaderValue::from(to_seconds(decision.retry_after)),
+                );
+
+                return Ok(req.into_response(response));
+            }
+
+            let mut res = service.call(req).await?.map_into_boxed_body();
+            insert_headers(res.headers_mut(), limit, &decision);
+
+            if credential.is_some() && res.status() == StatusCode::UNAUTHORIZED {
+                if let Err(err) =
+                    check(&mut connection, &failures_key, limit, policy.window, true).await
+                {
+                    log::error!("Error to count failed credentials {}", err);
+                }
+            }
+
+            Ok(res)
+        })
+    }
+}
+
+/// The API key or bearer token presented, hashed so credentials are never
+/// stored in redis.
+fn credential(req: &ServiceRequest) -> Option<String> {
+    req.headers()
+        .get(API_KEY_HEADER)
+        .or_else(|| req.headers().get(header::AUTHORIZATION))
+        .map(|value| hex::encode(Sha256::digest(value.as_bytes())))
+}
+
+fn client(req: &ServiceRequest, trusted_proxies: &[IpAddr]) -> String {
+    let forwarded_for = req
+        .headers()
+        .get_all(HeaderName::from_static("x-forwarded-for"))
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",");
+    let ip = client_ip(
+        req.peer_addr().map(|addr| addr.ip()),
+        &forwarded_for,
+        trusted_proxies,
+    );
+
+    match ip {
+        Some(ip) => format!("ip:{ip}"),
+        None => String::from("ip:unknown"),
+    }
+}
+
+/// The peer address, unless it is a trusted proxy. Then `X-Forwarded-For` is
+/// walked from the right, the address appended by the closest proxy, and the
+/// first address that is not a trusted proxy is the client. Entries left of it
+/// were sent by the client and are never trusted.
+fn client_ip(
+    peer: Option<IpAddr>,
+    forwarded_for: &str,
+    trusted_proxies: &[IpAddr],
+) -> Option<IpAddr> {
+    let mut ip = peer?;
+
+    for forwarded in forwarded_for.rsplit(',') {
+        if !trusted_proxies.contains(&ip) {
+            break;
+        }
+        match forwarded.trim().parse::<IpAddr>() {
+            Ok(forwarded) => ip = forwarded,
+            Err(_) => break,
+        }
+    }
+
+    Some(ip)
+}
+
+async fn check(
+    connection: &mut ConnectionManager,
+    key: &str,
+    limit: u32,
+    window: u64,
+    count: bool,
+) -> Result<RateLimitDecision, redis::RedisError> {
+    let window = window * 1000;
+    let emission = (window / limit.max(1) as u64).max(1);
+
+    let (allowed, remaining, reset, retry_after): (u8, u64, u64, u64) = metrics::time_redis(
+        "evalsha",
+        GCRA_SCRIPT
+            .key(key)
+            .arg(emission)
+            .arg(window)
+            .arg(u8::from(count))
+            .invoke_async(connection),
+    )
+    .await?;
+
+    Ok(RateLimitDecision {
+        allowed: allowed == 1,
+        remaining,
+        reset,
+        retry_after,
+    })
+}
+
+fn insert_headers(headers: &mut HeaderMap, limit: u32, decision: &RateLimitDecision) {
+    headers.insert(RATE_LIMIT_LIMIT_HEADER, HeaderValue::from(limit));
+    headers.insert(
+        RATE_LIMIT_REMAINING_HEADER,
+        HeaderValue::from(decision.remaining),
+    );
+    headers.insert(
+        RATE_LIMIT_RESET_HEADER,
+        HeaderValue::from(to_seconds(decision.reset)),
+    );
+}
+
+fn to_seconds(milliseconds: u64) -> u64 {
+    milliseconds.div_ceil(1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        get,
+        test::{call_service, init_service, TestRequest},
+        App, HttpResponse,
+    };
+    use std::net::SocketAddr;
+    use uuid::Uuid;
+
+    use crate::repository::redis;
+
+    use super::*;
+
+    #[get("/limited")]
+    async fn limited() -> HttpResponse {
+        HttpResponse::Ok().finish()
+    }
+
+    #[get("/unauthorized")]
+    async fn unauthorized() -> HttpResponse {
+        HttpResponse::Unauthorized().finish()
+    }
+
+    #[test]
+    fn it_should_prefer_route_limit_over_default() {
+        let routes = HashMap::from([(String::from("POST /categories"), 2)]);
+
+        let policy = RateLimitPolicy::new(60, Some(100), routes);
+        assert_eq!(
+            policy.limit_for("POST", Some("/categories")),
+            Some((String::from("POST /categories"), 2))
+        );
+        assert_eq!(
+            policy.limit_for("GET", Some("/categories")),
+            Some((String::from("default"), 100))
+        );
+
+        let policy = RateLimitPolicy::new(60, None, HashMap::new());
+        assert_eq!(policy.limit_for("GET", None), None);
+    }
+
+    #[test]
+    fn it_should_exempt_probes_and_metrics_from_default_limit() {
+        let routes = HashMap::from([(String::from("GET /metrics"), 5)]);
+
+        let policy = RateLimitPolicy::new(60, Some(100), routes);
+        assert_eq!(policy.limit_for("GET", Some("/health/live")), None);
+        assert_eq!(policy.limit_for("GET", Some("/health/ready")), None);
+        assert_eq!(
+            policy.limit_for("GET", Some("/metrics")),
+            Some((String::from("GET /metrics"), 5))
+        );
+    }
+
+    #[test]
+    fn it_should_only_read_forwarded_for_from_trusted_proxies() {
+        let client: IpAddr = "203.0.113.7".parse().unwrap();
+        let proxy: IpAddr = "10.0.0.2".parse().unwrap();
+        let spoofed = "198.51.100.1";
+
+        assert_eq!(client_ip(Some(client), spoofed, &[proxy]), Some(client));
+        assert_eq!(
+            client_ip(Some(proxy), &format!("{spoofed}, {client}"), &[proxy]),
+            Some(client)
+        );
+        assert_eq!(
+            client_ip(
+                Some(proxy),
+                &format!("{spoofed}, {client}, {proxy}"),
+                &[proxy]
+            ),
+            Some(client)
+        );
+        assert_eq!(client_ip(Some(proxy), "", &[proxy]), Some(proxy));
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_too_many_requests_when_limit_is_exceeded() {
+        dotenv::from_filename(".env.test").ok();
+
+        let app = init_service(
+            App::new()
+                .wrap(RateLimit::new(
+                    redis::init().await.unwrap(),
+                    RateLimitPolicy::new(60, Some(2), HashMap::new()),
+                ))
+                .service(limited),
+        )
+        .await;
+
+        let peer = SocketAddr::new(IpAddr::from(Uuid::new_v4().as_u128().to_be_bytes()), 443);
+        for remaining in ["1", "0"] {
+            let req = TestRequest::get()
+                .uri("/limited")
+                .peer_addr(peer)
+                .to_request();
+            let res = call_service(&app, req).await;
+
+            assert_eq!(res.status().as_u16(), StatusCode::OK);
+            assert_eq!(res.headers().get(RATE_LIMIT_LIMIT_HEADER).unwrap(), "2");
+            assert_eq!(
+                res.headers().get(RATE_LIMIT_REMAINING_HEADER).unwrap(),
+                remaining
+            );
+        }
+
+        let req = TestRequest::get()
+            .uri("/limited")
+            .peer_addr(peer)
+            .insert_header(("X-Forwarded-For", Uuid::new_v4().to_string()))
+            .to_request();
+        let res = call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(res.headers().contains_key(header::RETRY_AFTER));
+        assert!(res.headers().contains_key(RATE_LIMIT_RESET_HEADER));
+    }
+
+    #[actix_web::test]
+    async fn it_should_limit_credentials_from_a_client_after_failed_attempts() {
+        dotenv::from_filename(".env.test").ok();
+
+        let app = init_service(
+            App::new()
+                .wrap(RateLimit::new(
+                    redis::init().await.unwrap(),
+                    RateLimitPolicy::new(60, Some(2), HashMap::new()),
+                ))
+                .service(unauthorized)
+                .service(limited),
+        )
+        .await;
+
+        let peer = SocketAddr::new(IpAddr::from(Uuid::new_v4().as_u128().to_be_bytes()), 443);
+        for _ in 0..2 {
+            let req = TestRequest::get()
+                .uri("/unauthorized")
+                .peer_addr(peer)
+                .insert_header((API_KEY_HEADER, Uuid::new_v4().to_string()))
+                .to_request();
+            let res = call_service(&app, req).await;
+
+            assert_eq!(res.status().as_u16(), StatusCode::UNAUTHORIZED);
+        }
+
+        let req = TestRequest::get()
+            .uri("/limited")
+            .peer_addr(peer)
+            .insert_header((API_KEY_HEADER, Uuid::new_v4().to_string()))
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::TOO_MANY_REQUESTS);
+
+        let req = TestRequest::get()
+            .uri("/limited")
+            .peer_addr(peer)
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::OK);
+    }
+}
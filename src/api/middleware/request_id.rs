@@ -0,0 +1,171 @@
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+};
+
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    error::InternalError,
+    http::header::{HeaderName, HeaderValue},
+    Error, HttpMessage,
+};
+use futures::future::LocalBoxFuture;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+const REQUEST_ID_MAX_LEN: usize = 128;
+
+/// Id of the current request, stored in the request extensions by
+/// `RequestIdentifier`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestId(pub String);
+impl RequestId {
+    /// Keeps the id sent by the caller when it is short and made of
+    /// printable characters, otherwise generates a new one.
+    fn from_header(value: Option<&HeaderValue>) -> Self {
+        let id = value
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|id| {
+                !id.is_empty()
+                    && id.len() <= REQUEST_ID_MAX_LEN
+                    && id.chars().all(|char| char.is_ascii_graphic())
+            });
+
+        match id {
+            Some(id) => Self(id.to_owned()),
+            None => Self(Uuid::new_v4().to_string()),
+        }
+    }
+}
+
+/// Accepts the `X-Request-Id` header of the caller or generates one, stores it
+/// as `RequestId` for the inner middlewares and echoes it in the response,
+/// including error responses.
+pub struct RequestIdentifier;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestIdentifier
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = RequestIdentifierMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdentifierMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct RequestIdentifierMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdentifierMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            let request_id = RequestId::from_header(req.headers().get(REQUEST_ID_HEADER));
+            req.extensions_mut().insert(request_id.clone());
+
+            let header_value = HeaderValue::from_str(&request_id.0).ok();
+            match service.call(req).await {
+                Ok(res) => {
+                    let mut res = res.map_into_boxed_body();
+                    if let Some(value) = header_value {
+                        res.headers_mut().insert(REQUEST_ID_HEADER, value);
+                    }
+                    Ok(res)
+                }
+                Err(err) => {
+                    let mut response = err.error_response();
+                    if let Some(value) = header_value {
+                        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+                    }
+                    Err(InternalError::from_response(err, response).into())
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        get,
+        http::StatusCode,
+        test::{call_service, init_service, read_body_json, TestRequest},
+        App,
+    };
+
+    use crate::{
+        api::{error::ErrorResponse, middleware::problem::ProblemInstance},
+        domain::error::DomainError,
+    };
+
+    use super::*;
+
+    #[get("/missing")]
+    async fn missing() -> Result<String, DomainError> {
+        Err(DomainError::NotFound(String::from("Category not found")))
+    }
+
+    #[actix_web::test]
+    async fn it_should_echo_request_id_in_error_response() {
+        let app = init_service(
+            App::new()
+                .wrap(ProblemInstance)
+                .wrap(RequestIdentifier)
+                .service(missing),
+        )
+        .await;
+
+        let req = TestRequest::get()
+            .uri("/missing")
+            .insert_header((REQUEST_ID_HEADER, "5b0c1d2e-request"))
+            .to_request();
+        let res = call_service(&app, req).await;
+
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            res.headers().get(REQUEST_ID_HEADER).unwrap(),
+            "5b0c1d2e-request"
+        );
+
+        let body: ErrorResponse = read_body_json(res).await;
+        assert_eq!(body.request_id.as_deref(), Some("5b0c1d2e-request"));
+        assert_eq!(body.instance.as_deref(), Some("/missing"));
+    }
+
+    #[actix_web::test]
+    async fn it_should_generate_request_id_when_header_is_invalid() {
+        let app = init_service(App::new().wrap(RequestIdentifier).service(missing)).await;
+
+        let req = TestRequest::get()
+            .uri("/missing")
+            .insert_header((REQUEST_ID_HEADER, "a".repeat(REQUEST_ID_MAX_LEN + 1)))
+            .to_request();
+        let res = call_service(&app, req).await;
+
+        let request_id = res.headers().get(REQUEST_ID_HEADER).unwrap();
+        assert!(Uuid::parse_str(request_id.to_str().unwrap()).is_ok());
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+    time::Instant,
+};
+
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error, HttpMessage,
+};
+use futures::future::LocalBoxFuture;
+use tracing::{field::Empty, Instrument};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::{api::middleware::request_id::RequestId, telemetry};
+
+/// Opens a server span for every request, continuing the trace of the W3C
+/// `traceparent` header when the caller sends one. Spans are named after the
+/// route pattern so raw paths never become span names. The span carries the
+/// `RequestId`, so every log line of the request has it, and a last line
+/// reports the status and latency.
+pub struct RequestTrace;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestTrace
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = RequestTraceMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestTraceMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct RequestTraceMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestTraceMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let started_at = Instant::now();
+        let request_id = req
+            .extensions()
+            .get::<RequestId>()
+            .map(|RequestId(request_id)| request_id.clone());
+        let route = req
+            .match_pattern()
+            .unwrap_or_else(|| String::from("unmatched"));
+
+        let span = tracing::info_span!(
+            "http_request",
+            otel.name = %format!("{} {}", req.method(), route),
+            otel.kind = "server",
+            otel.status_code = Empty,
+            http.request.method = %req.method(),
+            http.route = %route,
+            url.path = %req.path(),
+            http.response.status_code = Empty,
+            request_id = request_id.as_deref(),
+            latency_ms = Empty,
+        );
+        if let Err(err) = span.set_parent(telemetry::extract_http(req.headers())) {
+            log::debug!("Error to continue request trace {}", err);
+        }
+
+        let fut = {
+            let _entered = span.enter();
+            self.service.call(req)
+        };
+
+        Box::pin(
+            async move {
+                let result = fut.await;
+                let status = match &result {
+                    Ok(res) => res.status(),
+                    Err(err) => err.as_response_error().status_code(),
+                };
+
+                let span = tracing::Span::current();
+                span.record("http.response.status_code", status.as_u16());
+                span.record("latency_ms", started_at.elapsed().as_millis() as u64);
+                if status.is_server_error() {
+                    span.record("otel.status_code", "ERROR");
+                }
+                tracing::info!("request completed");
+
+                let res = result?;
+                Ok(res.map_into_boxed_body())
+            }
+            .instrument(span),
+        )
+    }
+}
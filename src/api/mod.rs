@@ -1,10 +1,10 @@
 pub mod lib;
 
-mod config;
+pub mod config;
 pub mod error;
 mod middleware;
-mod resources;
+pub mod resources;
 pub mod utils;
 
 #[cfg(test)]
-mod tests;
+pub mod tests;
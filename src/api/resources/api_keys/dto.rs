@@ -0,0 +1,88 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    api::utils::validator::{validate_page_size_max, validate_scopes},
+    domain::api_keys::model::{ApiKeyCreateModel, ApiKeyIssuedModel, ApiKeyModel},
+};
+
+#[cfg_attr(test, derive(Serialize))]
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct RequestCreateApiKey {
+    #[validate(length(min = 1, max = 63))]
+    pub name: String,
+    /// Scopes granted to the key, e.g. `categories:read`.
+    #[validate(custom = "validate_scopes")]
+    pub scopes: Vec<String>,
+}
+impl From<RequestCreateApiKey> for ApiKeyCreateModel {
+    fn from(value: RequestCreateApiKey) -> Self {
+        ApiKeyCreateModel::new(value.name, value.scopes)
+    }
+}
+#[cfg(test)]
+impl RequestCreateApiKey {
+    pub fn mock_default() -> Self {
+        Self {
+            name: String::from("Orders service"),
+            scopes: vec![String::from("categories:read")],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, IntoParams)]
+pub struct RequestFindApiKeys {
+    #[validate(range(min = 1))]
+    pub page: Option<u32>,
+    #[validate(custom = "validate_page_size_max")]
+    pub page_size: Option<u32>,
+}
+
+#[cfg_attr(test, derive(Deserialize))]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResponseApiKey {
+    pub id: Uuid,
+    pub name: String,
+    /// First characters of the key, to tell keys apart.
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+impl From<ApiKeyModel> for ResponseApiKey {
+    fn from(value: ApiKeyModel) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            prefix: value.prefix,
+            scopes: value.scopes,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            last_used_at: value.last_used_at,
+            revoked_at: value.revoked_at,
+        }
+    }
+}
+
+#[cfg_attr(test, derive(Deserialize))]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResponseApiKeyIssued {
+    pub api_key: ResponseApiKey,
+    /// Secret to send in the `X-Api-Key` header. It is only returned once.
+    pub key: String,
+}
+impl From<ApiKeyIssuedModel> for ResponseApiKeyIssued {
+    fn from(value: ApiKeyIssuedModel) -> Self {
+        Self {
+            api_key: value.api_key.into(),
+            key: value.key,
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use actix_web::{
+    http::header::{CacheControl, CacheDirective},
+    post,
+    web::{self, Data},
+    HttpResponse,
+};
+use validator::Validate;
+
+use crate::{
+    api::{
+        lib::AppState,
+        middleware::auth::{ApiKeysWrite, Authorized},
+        resources::api_keys::dto::{self, ResponseApiKeyIssued},
+        utils::response::ApiResponse,
+    },
+    domain::{api_keys, error::DomainError},
+};
+
+#[utoipa::path(
+    post,
+    operation_id = "create_api_keys",
+    path = "/admin/api-keys",
+    tag = "admin",
+    request_body = RequestCreateApiKey,
+    security(("bearer_auth" = ["api_keys:write"])),
+    responses(
+         (status = 201, description = "API key created, the key is only returned once",  body = ApiResponseApiKeyIssued),
+         (status = 400, description = "Invalid payload",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing api_keys:write scope",  body = ErrorResponse),
+    ),
+ )]
+#[post("/admin/api-keys")]
+#[tracing::instrument(name = "api::api_keys::create", skip_all)]
+async fn handler(
+    _: Authorized<ApiKeysWrite>,
+    state: Data<AppState>,
+    body: web::Json<dto::RequestCreateApiKey>,
+) -> Result<HttpResponse, DomainError> {
+    body.validate()?;
+
+    let api_key =
+        api_keys::resources::create::execute(state.api_key_repository.clone(), body.0.into())
+            .await?;
+
+    let response = ApiResponse::<ResponseApiKeyIssued>::new(vec![api_key.into()], None, None, None);
+
+    Ok(HttpResponse::Created()
+        .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+        .json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        http::{header, StatusCode},
+        test,
+    };
+
+    use crate::api::{
+        middleware::{
+            api_key::API_KEY_HEADER,
+            idempotency::{IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER},
+        },
+        resources::api_keys::{dto, routes::init_routes},
+        tests::utils::{bearer_token, get_app, get_app_with_auth},
+        utils::response::ApiResponse,
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_api_key_created() {
+        let (_, app) = get_app(init_routes).await;
+
+        let req = test::TestRequest::post()
+            .uri("/admin/api-keys")
+            .set_json(dto::RequestCreateApiKey::mock_default())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::CREATED);
+
+        let body = test::read_body(res).await;
+        let response: ApiResponse<dto::ResponseApiKeyIssued> =
+            serde_json::from_slice(&body).unwrap();
+
+        let issued = response.records.first().unwrap();
+        assert!(issued.key.starts_with(&issued.api_key.prefix));
+        assert_eq!(issued.api_key.scopes, vec!["categories:read"]);
+    }
+
+    #[actix_web::test]
+    async fn it_should_not_replay_issued_key_with_same_idempotency_key() {
+        let (_, app) = get_app(init_routes).await;
+        let idempotency_key = uuid::Uuid::new_v4().to_string();
+
+        let mut keys = Vec::new();
+        for _ in 0..2 {
+            let req = test::TestRequest::post()
+                .uri("/admin/api-keys")
+                .insert_header((IDEMPOTENCY_KEY_HEADER, idempotency_key.clone()))
+                .set_json(dto::RequestCreateApiKey::mock_default())
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status().as_u16(), StatusCode::CREATED);
+            assert!(!res.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER));
+            assert_eq!(
+                res.headers().get(header::CACHE_CONTROL).unwrap(),
+                "no-store"
+            );
+
+            let body = test::read_body(res).await;
+            let response: ApiResponse<dto::ResponseApiKeyIssued> =
+                serde_json::from_slice(&body).unwrap();
+            keys.push(response.records.first().unwrap().key.clone());
+        }
+
+        assert_ne!(keys[0], keys[1]);
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_field_errors_when_scope_is_unknown() {
+        let (_, app) = get_app(init_routes).await;
+
+        let mut api_key = dto::RequestCreateApiKey::mock_default();
+        api_key.scopes = vec![String::from("everything")];
+
+        let req = test::TestRequest::post()
+            .uri("/admin/api-keys")
+            .set_json(api_key)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn it_should_authorize_requests_with_api_key_scopes() {
+        let (_, app) = get_app_with_auth(init_routes).await;
+
+        let mut api_key = dto::RequestCreateApiKey::mock_default();
+        api_key.scopes = vec![String::from("api_keys:read")];
+
+        let req = test::TestRequest::post()
+            .uri("/admin/api-keys")
+            .insert_header((header::AUTHORIZATION, bearer_token("api_keys:write")))
+            .set_json(api_key)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::CREATED);
+
+        let body = test::read_body(res).await;
+        let response: ApiResponse<dto::ResponseApiKeyIssued> =
+            serde_json::from_slice(&body).unwrap();
+        let issued = response.records.first().unwrap();
+
+        let req = test::TestRequest::get()
+            .uri("/admin/api-keys")
+            .insert_header((API_KEY_HEADER, issued.key.clone()))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::OK);
+
+        let req = test::TestRequest::post()
+            .uri("/admin/api-keys")
+            .insert_header((API_KEY_HEADER, issued.key.clone()))
+            .set_json(dto::RequestCreateApiKey::mock_default())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::get()
+            .uri("/admin/api-keys")
+            .insert_header((API_KEY_HEADER, "ak_invalid"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,106 @@
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+
+use validator::Validate;
+
+use crate::{
+    api::{
+        config,
+        lib::AppState,
+        middleware::auth::{ApiKeysRead, Authorized},
+        resources::api_keys::dto::{self, ResponseApiKey},
+        utils::response::ApiResponse,
+    },
+    domain::{api_keys, error::DomainError},
+};
+
+#[utoipa::path(
+    get,
+    operation_id = "find_api_keys",
+    path = "/admin/api-keys",
+    tag = "admin",
+    params(
+        dto::RequestFindApiKeys
+    ),
+    security(("bearer_auth" = ["api_keys:read"])),
+    responses(
+         (status = 200, description = "API keys, revoked ones included",  body = ApiResponseApiKey),
+         (status = 204, description = "no content API keys"),
+         (status = 400, description = "Invalid query parameters",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing api_keys:read scope",  body = ErrorResponse),
+    ),
+ )]
+#[get("/admin/api-keys")]
+#[tracing::instrument(name = "api::api_keys::find", skip_all)]
+async fn handler(
+    _: Authorized<ApiKeysRead>,
+    state: Data<AppState>,
+    query: Query<dto::RequestFindApiKeys>,
+) -> Result<HttpResponse, DomainError> {
+    query.validate()?;
+
+    let page = query.page.unwrap_or(1);
+    let page_size = query
+        .page_size
+        .unwrap_or(config::get_config().page_size_default);
+
+    let result =
+        api_keys::resources::find::execute(state.api_key_repository.clone(), page, page_size)
+            .await?;
+
+    if let Some((api_keys, count)) = result {
+        let response = ApiResponse::<ResponseApiKey>::new(
+            api_keys.into_iter().map(|i| i.into()).collect(),
+            Some(page),
+            Some(count),
+            Some(page_size),
+        );
+        return Ok(HttpResponse::Ok().json(response));
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test;
+
+    use crate::{
+        api::{
+            resources::api_keys::{dto, routes::init_routes},
+            tests::utils::get_app,
+            utils::response::ApiResponse,
+        },
+        domain::api_keys::{model::ApiKeyCreateModel, repository::ApiKeyRepository},
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_api_keys_finded() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let api_key_model = ApiKeyCreateModel::mock_default();
+        repositories
+            .api_key_repository
+            .insert(&api_key_model)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::get()
+            .uri("/admin/api-keys?page=1&page_size=12")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let response_api_keys: ApiResponse<dto::ResponseApiKey> =
+            serde_json::from_slice(&body).unwrap();
+
+        assert!(!response_api_keys.records.is_empty());
+    }
+}
@@ -0,0 +1,13 @@
+use actix_web::web;
+
+pub mod create;
+pub mod find;
+pub mod revoke_by_id;
+pub mod rotate_by_id;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(create::handler);
+    config.service(find::handler);
+    config.service(rotate_by_id::handler);
+    config.service(revoke_by_id::handler);
+}
@@ -0,0 +1,79 @@
+use actix_web::{
+    delete,
+    web::{self, Data},
+    HttpResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        lib::AppState,
+        middleware::auth::{ApiKeysWrite, Authorized},
+    },
+    domain::{api_keys, error::DomainError},
+};
+
+#[utoipa::path(
+    delete,
+    operation_id = "revoke_api_keys",
+    path = "/admin/api-keys/{api_key_id}",
+    tag = "admin",
+    params(
+        ("api_key_id" = Uuid, Path, description = "API key uuid"),
+    ),
+    security(("bearer_auth" = ["api_keys:write"])),
+    responses(
+         (status = 204, description = "API key revoked"),
+         (status = 400, description = "Invalid API key id",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing api_keys:write scope",  body = ErrorResponse),
+         (status = 404, description = "API key not found or already revoked",  body = ErrorResponse),
+    ),
+ )]
+#[delete("/admin/api-keys/{api_key_id}")]
+#[tracing::instrument(name = "api::api_keys::revoke_by_id", skip_all)]
+async fn handler(
+    _: Authorized<ApiKeysWrite>,
+    state: Data<AppState>,
+    param: web::Path<Uuid>,
+) -> Result<HttpResponse, DomainError> {
+    api_keys::resources::revoke_by_id::execute(state.api_key_repository.clone(), param.to_owned())
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test};
+
+    use crate::{
+        api::{resources::api_keys::routes::init_routes, tests::utils::get_app},
+        domain::api_keys::{model::ApiKeyCreateModel, repository::ApiKeyRepository},
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_void_api_key_revoked() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let api_key_model = ApiKeyCreateModel::mock_default();
+        repositories
+            .api_key_repository
+            .insert(&api_key_model)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/admin/api-keys/{}", api_key_model.id))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::NO_CONTENT);
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/admin/api-keys/{}", api_key_model.id))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::NOT_FOUND);
+    }
+}
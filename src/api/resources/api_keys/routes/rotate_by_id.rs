@@ -0,0 +1,114 @@
+use actix_web::{
+    http::header::{CacheControl, CacheDirective},
+    post,
+    web::{self, Data},
+    HttpResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        lib::AppState,
+        middleware::auth::{ApiKeysWrite, Authorized},
+        resources::api_keys::dto::ResponseApiKeyIssued,
+        utils::response::ApiResponse,
+    },
+    domain::{api_keys, error::DomainError},
+};
+
+#[utoipa::path(
+    post,
+    operation_id = "rotate_api_keys",
+    path = "/admin/api-keys/{api_key_id}/rotate",
+    tag = "admin",
+    params(
+        ("api_key_id" = Uuid, Path, description = "API key uuid"),
+    ),
+    security(("bearer_auth" = ["api_keys:write"])),
+    responses(
+         (status = 200, description = "API key rotated, the previous key stops working",  body = ApiResponseApiKeyIssued),
+         (status = 400, description = "Invalid API key id",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing api_keys:write scope",  body = ErrorResponse),
+         (status = 404, description = "API key not found or revoked",  body = ErrorResponse),
+    ),
+ )]
+#[post("/admin/api-keys/{api_key_id}/rotate")]
+#[tracing::instrument(name = "api::api_keys::rotate_by_id", skip_all)]
+async fn handler(
+    _: Authorized<ApiKeysWrite>,
+    state: Data<AppState>,
+    param: web::Path<Uuid>,
+) -> Result<HttpResponse, DomainError> {
+    let api_key = api_keys::resources::rotate_by_id::execute(
+        state.api_key_repository.clone(),
+        param.to_owned(),
+    )
+    .await?;
+
+    let response = ApiResponse::<ResponseApiKeyIssued>::new(vec![api_key.into()], None, None, None);
+
+    Ok(HttpResponse::Ok()
+        .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+        .json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test};
+    use uuid::Uuid;
+
+    use crate::{
+        api::{
+            resources::api_keys::{dto, routes::init_routes},
+            tests::utils::get_app,
+            utils::response::ApiResponse,
+        },
+        domain::api_keys::{model::ApiKeyCreateModel, repository::ApiKeyRepository},
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_api_key_rotated() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let api_key_model = ApiKeyCreateModel::mock_default();
+        repositories
+            .api_key_repository
+            .insert(&api_key_model)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/admin/api-keys/{}/rotate", api_key_model.id))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let response: ApiResponse<dto::ResponseApiKeyIssued> =
+            serde_json::from_slice(&body).unwrap();
+        let issued = response.records.first().unwrap();
+        assert_ne!(issued.key, api_key_model.secret.key);
+
+        let previous = repositories
+            .api_key_repository
+            .find_by_hash(&api_key_model.secret.key_hash)
+            .await
+            .unwrap();
+        assert!(previous.is_none());
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_not_found_error_when_api_key_does_not_exist() {
+        let (_, app) = get_app(init_routes).await;
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/admin/api-keys/{}/rotate", Uuid::new_v4()))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::NOT_FOUND);
+    }
+}
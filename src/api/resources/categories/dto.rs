@@ -1,12 +1,21 @@
+use actix_web::{http::StatusCode, ResponseError};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
     api::utils::validator::validate_page_size_max,
-    domain::categories::model::{CategoryCreateModel, CategoryModel, CategoryUpdateModel},
+    domain::{
+        categories::model::{
+            CategoryBulkItemModel, CategoryCreateModel, CategoryCursorDirection,
+            CategoryCursorModel, CategoryModel, CategoryPatchModel, CategorySearchModel,
+            CategorySortField, CategorySortModel, CategoryTreeModel, CategoryUpdateModel,
+        },
+        error::DomainError,
+    },
 };
 
 #[cfg_attr(test, derive(Serialize))]
@@ -16,18 +25,20 @@ pub struct RequestCreateCategory {
     pub name: String,
     #[validate(length(max = 512))]
     pub description: Option<String>,
+    pub parent_id: Option<Uuid>,
 }
 impl From<RequestCreateCategory> for CategoryCreateModel {
     fn from(value: RequestCreateCategory) -> Self {
-        CategoryCreateModel::new(value.name, value.description)
+        CategoryCreateModel::new(value.name, value.description, value.parent_id)
     }
 }
 #[cfg(test)]
 impl RequestCreateCategory {
     pub fn mock_default() -> Self {
         Self {
-            name: "Burgers".to_string(),
+            name: format!("Burgers {}", Uuid::new_v4()),
             description: Some("The Big Burgers".to_string()),
+            parent_id: None,
         }
     }
 }
@@ -39,18 +50,20 @@ pub struct RequestUpdateCategory {
     pub name: String,
     #[validate(length(max = 512))]
     pub description: Option<String>,
+    pub parent_id: Option<Uuid>,
 }
 impl From<RequestUpdateCategory> for CategoryUpdateModel {
     fn from(value: RequestUpdateCategory) -> Self {
-        CategoryUpdateModel::new(value.name, value.description)
+        CategoryUpdateModel::new(value.name, value.description, value.parent_id)
     }
 }
 #[cfg(test)]
 impl RequestUpdateCategory {
     pub fn mock_default() -> Self {
         Self {
-            name: "French fries".to_string(),
+            name: format!("French fries {}", Uuid::new_v4()),
             description: Some("The French fries".to_string()),
+            parent_id: None,
         }
     }
 
@@ -58,15 +71,160 @@ impl RequestUpdateCategory {
         self.name = name.to_string();
         self
     }
+
+    pub fn with_parent_id(mut self, parent_id: Uuid) -> Self {
+        self.parent_id = Some(parent_id);
+        self
+    }
+}
+
+/// Merge patch (RFC 7396). Absent fields are left untouched and `null`
+/// clears `description` or `parent_id`. `name` cannot be null.
+#[cfg_attr(test, derive(Serialize))]
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct RequestPatchCategory {
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[cfg_attr(test, serde(skip_serializing_if = "Option::is_none"))]
+    #[validate(length(max = 64))]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[cfg_attr(test, serde(skip_serializing_if = "Option::is_none"))]
+    #[schema(value_type = Option<String>, nullable)]
+    #[validate(length(max = 512))]
+    pub description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[cfg_attr(test, serde(skip_serializing_if = "Option::is_none"))]
+    #[schema(value_type = Option<Uuid>, nullable)]
+    pub parent_id: Option<Option<Uuid>>,
+}
+impl From<RequestPatchCategory> for CategoryPatchModel {
+    fn from(value: RequestPatchCategory) -> Self {
+        CategoryPatchModel::new(value.name, value.description, value.parent_id)
+    }
+}
+
+/// Wraps any present value in `Some`, so an explicit `null` becomes
+/// `Some(None)` while a missing field falls back to `None` through `default`.
+fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
 }
 
 #[derive(Debug, Clone, Deserialize, Validate, IntoParams)]
 pub struct RequestFindCategories {
+    /// Categories whose name contains the value.
     #[validate(length(max = 64))]
     pub name: Option<String>,
+    /// Includes deleted categories, ignored when `is_active` is set.
+    pub include_inactive: Option<bool>,
+    pub is_active: Option<bool>,
+    /// Exact ids, sent as `ids[]=<id>&ids[]=<id>`.
+    #[validate(length(max = 100))]
+    pub ids: Option<Vec<Uuid>>,
+    /// Inclusive lower bound of the creation date.
+    pub created_at_from: Option<DateTime<Utc>>,
+    /// Inclusive upper bound of the creation date.
+    pub created_at_to: Option<DateTime<Utc>>,
+    /// Inclusive lower bound of the last update date.
+    pub updated_at_from: Option<DateTime<Utc>>,
+    /// Inclusive upper bound of the last update date.
+    pub updated_at_to: Option<DateTime<Utc>>,
+    /// Comma separated `name`, `created_at` or `updated_at`, prefixed with
+    /// `-` for descending order, e.g. `-created_at,name`. Defaults to
+    /// `created_at`.
+    pub sort: Option<String>,
+    #[validate(range(min = 1))]
     pub page: Option<u32>,
     #[validate(custom = "validate_page_size_max")]
     pub page_size: Option<u32>,
+    /// Opaque `meta.next_cursor` or `meta.prev_cursor` of a previous page,
+    /// sent with the same `sort` and can not be combined with `page`.
+    pub cursor: Option<String>,
+    /// Defaults to true with `page` and to false with `cursor`.
+    pub with_count: Option<bool>,
+}
+
+pub fn parse_sort(sort: &str) -> Result<Vec<CategorySortModel>, DomainError> {
+    sort.split(',')
+        .map(|item| {
+            let item = item.trim();
+            let (field, descending) = match item.strip_prefix('-') {
+                Some(field) => (field, true),
+                None => (item, false),
+            };
+
+            let field = match field {
+                "name" => CategorySortField::Name,
+                "created_at" => CategorySortField::CreatedAt,
+                "updated_at" => CategorySortField::UpdatedAt,
+                _ => {
+                    return Err(DomainError::BadRequest(format!(
+                        "sort by {field} is not supported"
+                    )))
+                }
+            };
+
+            Ok(CategorySortModel::new(field, descending))
+        })
+        .collect()
+}
+
+#[derive(Serialize, Deserialize)]
+struct Cursor {
+    id: Uuid,
+    name: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    prev: bool,
+}
+
+pub fn encode_cursor(cursor: &CategoryCursorModel) -> String {
+    let cursor = Cursor {
+        id: cursor.id,
+        name: cursor.name.clone(),
+        created_at: cursor.created_at,
+        updated_at: cursor.updated_at,
+        prev: cursor.direction == CategoryCursorDirection::Prev,
+    };
+
+    URL_SAFE_NO_PAD.encode(serde_json::to_vec(&cursor).unwrap_or_default())
+}
+
+pub fn decode_cursor(cursor: &str) -> Result<CategoryCursorModel, DomainError> {
+    let cursor: Cursor = URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|cursor| serde_json::from_slice(&cursor).ok())
+        .ok_or_else(|| DomainError::BadRequest(String::from("invalid cursor")))?;
+
+    Ok(CategoryCursorModel {
+        id: cursor.id,
+        name: cursor.name,
+        created_at: cursor.created_at,
+        updated_at: cursor.updated_at,
+        direction: match cursor.prev {
+            true => CategoryCursorDirection::Prev,
+            false => CategoryCursorDirection::Next,
+        },
+    })
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RequestBulkCategories {
+    /// Rolls back every item when one fails. Defaults to true.
+    pub atomic: Option<bool>,
+}
+
+#[cfg_attr(test, derive(Serialize))]
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct RequestBulkUpdateCategory {
+    pub id: Uuid,
+    #[serde(flatten)]
+    #[validate]
+    pub category: RequestUpdateCategory,
 }
 
 #[cfg_attr(test, derive(Deserialize))]
@@ -76,9 +234,14 @@ pub struct ResponseCategory {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Uuid>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub version: i32,
 }
 impl From<CategoryModel> for ResponseCategory {
     fn from(value: CategoryModel) -> Self {
@@ -86,9 +249,162 @@ impl From<CategoryModel> for ResponseCategory {
             id: value.id,
             name: value.name,
             description: value.description,
+            parent_id: value.parent_id,
             is_active: value.is_active,
             created_at: value.created_at,
             updated_at: value.updated_at,
+            deleted_at: value.deleted_at,
+            version: value.version,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, IntoParams)]
+pub struct RequestSearchCategories {
+    /// Words to search in name and description, supports `"quoted phrases"`,
+    /// `or` and `-excluded` words. Names with typos are matched by similarity.
+    #[validate(length(min = 1, max = 64))]
+    pub q: String,
+    #[validate(range(min = 1))]
+    pub page: Option<u32>,
+    #[validate(custom = "validate_page_size_max")]
+    pub page_size: Option<u32>,
+}
+
+#[cfg_attr(test, derive(Deserialize))]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResponseCategorySearch {
+    pub category: ResponseCategory,
+    pub rank: f32,
+    pub highlight: ResponseCategoryHighlight,
+}
+impl From<CategorySearchModel> for ResponseCategorySearch {
+    fn from(value: CategorySearchModel) -> Self {
+        Self {
+            category: value.category.into(),
+            rank: value.rank,
+            highlight: ResponseCategoryHighlight {
+                name: value.name_highlight,
+                description: value.description_highlight,
+            },
         }
     }
 }
+
+/// HTML escaped fields with the matched words wrapped in `<mark>` tags.
+#[cfg_attr(test, derive(Deserialize))]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResponseCategoryHighlight {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[cfg_attr(test, derive(Deserialize))]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResponseCategoryTree {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Uuid>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub version: i32,
+    pub children: Vec<ResponseCategoryTree>,
+}
+impl From<CategoryTreeModel> for ResponseCategoryTree {
+    fn from(value: CategoryTreeModel) -> Self {
+        Self {
+            id: value.category.id,
+            name: value.category.name,
+            description: value.category.description,
+            parent_id: value.category.parent_id,
+            is_active: value.category.is_active,
+            created_at: value.category.created_at,
+            updated_at: value.category.updated_at,
+            deleted_at: value.category.deleted_at,
+            version: value.category.version,
+            children: value.children.into_iter().map(|i| i.into()).collect(),
+        }
+    }
+}
+
+#[cfg_attr(test, derive(Deserialize))]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResponseBulkCategories {
+    pub items: Vec<ResponseBulkCategory>,
+}
+impl ResponseBulkCategories {
+    /// Items rolled back with their atomic batch are reported as 424 Failed
+    /// Dependency.
+    pub fn new<T>(
+        items: Vec<CategoryBulkItemModel<T>>,
+        applied_status: StatusCode,
+        record: impl Fn(T) -> Option<ResponseCategory>,
+    ) -> Self {
+        let items = items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| match item {
+                CategoryBulkItemModel::Applied(value) => ResponseBulkCategory {
+                    index,
+                    status: applied_status.as_u16(),
+                    category: record(value),
+                    error: None,
+                },
+                CategoryBulkItemModel::Failed(err) => {
+                    let status = err.status_code();
+                    let error = if status.is_server_error() {
+                        log::error!("{}", err);
+                        String::from("Internal Server Error")
+                    } else {
+                        err.to_string()
+                    };
+
+                    ResponseBulkCategory {
+                        index,
+                        status: status.as_u16(),
+                        category: None,
+                        error: Some(error),
+                    }
+                }
+                CategoryBulkItemModel::RolledBack => ResponseBulkCategory {
+                    index,
+                    status: StatusCode::FAILED_DEPENDENCY.as_u16(),
+                    category: None,
+                    error: Some(String::from("Rolled back because another item failed")),
+                },
+            })
+            .collect();
+
+        Self { items }
+    }
+
+    pub fn status(&self, applied_status: StatusCode) -> StatusCode {
+        if self
+            .items
+            .iter()
+            .all(|item| item.status == applied_status.as_u16())
+        {
+            return applied_status;
+        }
+
+        StatusCode::MULTI_STATUS
+    }
+}
+
+#[cfg_attr(test, derive(Deserialize))]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResponseBulkCategory {
+    pub index: usize,
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<ResponseCategory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
@@ -0,0 +1,161 @@
+use actix_web::{
+    http::StatusCode,
+    post,
+    web::{self, Data, Query},
+    HttpResponse,
+};
+use validator::Validate;
+
+use crate::{
+    api::{
+        lib::AppState,
+        middleware::auth::{Authorized, CategoriesWrite},
+        resources::categories::dto::{self, ResponseBulkCategories},
+        utils::validator::validate_bulk_size,
+    },
+    domain::{categories, error::DomainError},
+};
+
+#[utoipa::path(
+    post,
+    operation_id = "bulk_create_categories",
+    path = "/categories/bulk",
+    tag = "categories",
+    params(
+        dto::RequestBulkCategories
+    ),
+    request_body = Vec<RequestCreateCategory>,
+    security(("bearer_auth" = ["categories:write"])),
+    responses(
+         (status = 201, description = "categories created",  body = ResponseBulkCategories),
+         (status = 207, description = "categories partially created",  body = ResponseBulkCategories),
+         (status = 400, description = "Invalid payload",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:write scope",  body = ErrorResponse),
+    ),
+ )]
+#[post("/categories/bulk")]
+#[tracing::instrument(name = "api::categories::bulk_create", skip_all)]
+async fn handler(
+    _: Authorized<CategoriesWrite>,
+    state: Data<AppState>,
+    query: Query<dto::RequestBulkCategories>,
+    body: web::Json<Vec<dto::RequestCreateCategory>>,
+) -> Result<HttpResponse, DomainError> {
+    validate_bulk_size(body.len())?;
+
+    let items = body
+        .0
+        .into_iter()
+        .map(|item| item.validate().map(|_| item.into()).map_err(Into::into))
+        .collect();
+
+    let result = categories::resources::bulk_create::execute(
+        state.category_repository.clone(),
+        items,
+        query.atomic.unwrap_or(true),
+    )
+    .await?;
+
+    let response = ResponseBulkCategories::new(result, StatusCode::CREATED, |category| {
+        Some(category.into())
+    });
+
+    Ok(HttpResponse::build(response.status(StatusCode::CREATED)).json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test};
+
+    use crate::{
+        api::{
+            resources::categories::{dto, routes::init_routes},
+            tests::utils::get_app,
+        },
+        domain::categories::repository::CategoryRepository,
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_categories_created() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        let req = test::TestRequest::post()
+            .uri("/categories/bulk")
+            .set_json(vec![
+                dto::RequestCreateCategory::mock_default(),
+                dto::RequestCreateCategory::mock_default(),
+            ])
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::CREATED);
+
+        let body = test::read_body(res).await;
+        let response: dto::ResponseBulkCategories = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(response.items.len(), 2);
+        let category_id = response.items[1].category.as_ref().unwrap().id;
+        assert!(repositories
+            .category_repository
+            .find_by_id(&category_id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[actix_web::test]
+    async fn it_should_rollback_every_category_when_atomic_item_fails() {
+        let (_, app) = get_app(init_routes).await;
+
+        let mut invalid = dto::RequestCreateCategory::mock_default();
+        invalid.name = "a".repeat(65);
+
+        let req = test::TestRequest::post()
+            .uri("/categories/bulk")
+            .set_json(vec![dto::RequestCreateCategory::mock_default(), invalid])
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::MULTI_STATUS);
+
+        let body = test::read_body(res).await;
+        let response: dto::ResponseBulkCategories = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            response.items[0].status,
+            StatusCode::FAILED_DEPENDENCY.as_u16()
+        );
+        assert_eq!(response.items[1].status, StatusCode::BAD_REQUEST.as_u16());
+    }
+
+    #[actix_web::test]
+    async fn it_should_commit_valid_categories_when_not_atomic() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        let mut invalid = dto::RequestCreateCategory::mock_default();
+        invalid.parent_id = Some(uuid::Uuid::new_v4());
+
+        let req = test::TestRequest::post()
+            .uri("/categories/bulk?atomic=false")
+            .set_json(vec![dto::RequestCreateCategory::mock_default(), invalid])
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::MULTI_STATUS);
+
+        let body = test::read_body(res).await;
+        let response: dto::ResponseBulkCategories = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(response.items[0].status, StatusCode::CREATED.as_u16());
+        assert_eq!(response.items[1].status, StatusCode::BAD_REQUEST.as_u16());
+
+        let category_id = response.items[0].category.as_ref().unwrap().id;
+        assert!(repositories
+            .category_repository
+            .find_by_id(&category_id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+}
@@ -0,0 +1,147 @@
+use actix_web::{
+    delete,
+    http::StatusCode,
+    web::{self, Data, Query},
+    HttpResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        lib::AppState,
+        middleware::auth::{Authorized, CategoriesWrite},
+        resources::categories::dto::{self, ResponseBulkCategories},
+        utils::validator::validate_bulk_size,
+    },
+    domain::{categories, error::DomainError},
+};
+
+#[utoipa::path(
+    delete,
+    operation_id = "bulk_delete_categories",
+    path = "/categories/bulk",
+    tag = "categories",
+    params(
+        dto::RequestBulkCategories
+    ),
+    request_body = Vec<Uuid>,
+    security(("bearer_auth" = ["categories:write"])),
+    responses(
+         (status = 200, description = "categories deleted",  body = ResponseBulkCategories),
+         (status = 207, description = "categories partially deleted",  body = ResponseBulkCategories),
+         (status = 400, description = "Invalid payload",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:write scope",  body = ErrorResponse),
+    ),
+ )]
+#[delete("/categories/bulk")]
+#[tracing::instrument(name = "api::categories::bulk_delete", skip_all)]
+async fn handler(
+    _: Authorized<CategoriesWrite>,
+    state: Data<AppState>,
+    query: Query<dto::RequestBulkCategories>,
+    body: web::Json<Vec<Uuid>>,
+) -> Result<HttpResponse, DomainError> {
+    validate_bulk_size(body.len())?;
+
+    let result = categories::resources::bulk_delete::execute(
+        state.category_repository.clone(),
+        body.0,
+        query.atomic.unwrap_or(true),
+    )
+    .await?;
+
+    let response = ResponseBulkCategories::new(result, StatusCode::OK, |_| None);
+
+    Ok(HttpResponse::build(response.status(StatusCode::OK)).json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test};
+    use uuid::Uuid;
+
+    use crate::{
+        api::{
+            resources::categories::{dto, routes::init_routes},
+            tests::utils::get_app,
+        },
+        domain::categories::{model::CategoryCreateModel, repository::CategoryRepository},
+    };
+
+    #[actix_web::test]
+    async fn it_should_keep_categories_when_atomic_item_is_not_found() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&category_model)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::delete()
+            .uri("/categories/bulk")
+            .set_json(vec![category_model.id, Uuid::new_v4()])
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::MULTI_STATUS);
+
+        let body = test::read_body(res).await;
+        let response: dto::ResponseBulkCategories = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            response.items[0].status,
+            StatusCode::FAILED_DEPENDENCY.as_u16()
+        );
+        assert_eq!(response.items[1].status, StatusCode::NOT_FOUND.as_u16());
+        assert!(repositories
+            .category_repository
+            .find_by_id(&category_model.id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_categories_deleted() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&category_model)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::delete()
+            .uri("/categories/bulk")
+            .set_json(vec![category_model.id])
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::OK);
+        assert!(repositories
+            .category_repository
+            .find_by_id(&category_model.id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_bad_request_error_when_bulk_is_empty() {
+        let (_, app) = get_app(init_routes).await;
+
+        let req = test::TestRequest::delete()
+            .uri("/categories/bulk")
+            .set_json(Vec::<Uuid>::new())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
+    }
+}
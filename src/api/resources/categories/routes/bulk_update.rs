@@ -0,0 +1,150 @@
+use actix_web::{
+    http::StatusCode,
+    put,
+    web::{self, Data, Query},
+    HttpResponse,
+};
+use validator::Validate;
+
+use crate::{
+    api::{
+        lib::AppState,
+        middleware::auth::{Authorized, CategoriesWrite},
+        resources::categories::dto::{self, ResponseBulkCategories},
+        utils::validator::validate_bulk_size,
+    },
+    domain::{categories, error::DomainError},
+};
+
+#[utoipa::path(
+    put,
+    operation_id = "bulk_update_categories",
+    path = "/categories/bulk",
+    tag = "categories",
+    params(
+        dto::RequestBulkCategories
+    ),
+    request_body = Vec<RequestBulkUpdateCategory>,
+    security(("bearer_auth" = ["categories:write"])),
+    responses(
+         (status = 200, description = "categories updated",  body = ResponseBulkCategories),
+         (status = 207, description = "categories partially updated",  body = ResponseBulkCategories),
+         (status = 400, description = "Invalid payload",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:write scope",  body = ErrorResponse),
+    ),
+ )]
+#[put("/categories/bulk")]
+#[tracing::instrument(name = "api::categories::bulk_update", skip_all)]
+async fn handler(
+    _: Authorized<CategoriesWrite>,
+    state: Data<AppState>,
+    query: Query<dto::RequestBulkCategories>,
+    body: web::Json<Vec<dto::RequestBulkUpdateCategory>>,
+) -> Result<HttpResponse, DomainError> {
+    validate_bulk_size(body.len())?;
+
+    let items = body
+        .0
+        .into_iter()
+        .map(|item| {
+            item.validate()
+                .map(|_| (item.id, item.category.into()))
+                .map_err(Into::into)
+        })
+        .collect();
+
+    let result = categories::resources::bulk_update::execute(
+        state.category_repository.clone(),
+        items,
+        query.atomic.unwrap_or(true),
+    )
+    .await?;
+
+    let response =
+        ResponseBulkCategories::new(result, StatusCode::OK, |category| Some(category.into()));
+
+    Ok(HttpResponse::build(response.status(StatusCode::OK)).json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test};
+    use uuid::Uuid;
+
+    use crate::{
+        api::{
+            resources::categories::{dto, routes::init_routes},
+            tests::utils::get_app,
+        },
+        domain::categories::{model::CategoryCreateModel, repository::CategoryRepository},
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_categories_updated() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&category_model)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::put()
+            .uri("/categories/bulk")
+            .set_json(vec![dto::RequestBulkUpdateCategory {
+                id: category_model.id,
+                category: dto::RequestUpdateCategory::mock_default().with_name("Burgers Supreme"),
+            }])
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::OK);
+
+        let body = test::read_body(res).await;
+        let response: dto::ResponseBulkCategories = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            response.items[0].category.as_ref().unwrap().name,
+            "Burgers Supreme"
+        );
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_not_found_item_when_not_atomic() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&category_model)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::put()
+            .uri("/categories/bulk?atomic=false")
+            .set_json(vec![
+                dto::RequestBulkUpdateCategory {
+                    id: category_model.id,
+                    category: dto::RequestUpdateCategory::mock_default(),
+                },
+                dto::RequestBulkUpdateCategory {
+                    id: Uuid::new_v4(),
+                    category: dto::RequestUpdateCategory::mock_default(),
+                },
+            ])
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::MULTI_STATUS);
+
+        let body = test::read_body(res).await;
+        let response: dto::ResponseBulkCategories = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(response.items[0].status, StatusCode::OK.as_u16());
+        assert_eq!(response.items[1].status, StatusCode::NOT_FOUND.as_u16());
+    }
+}
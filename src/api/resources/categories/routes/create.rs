@@ -9,6 +9,7 @@ use validator::Validate;
 use crate::{
     api::{
         lib::AppState,
+        middleware::auth::{Authorized, CategoriesWrite},
         resources::categories::dto::{self, ResponseCategory},
         utils::response::ApiResponse,
     },
@@ -21,13 +22,23 @@ use crate::{
     path = "/categories",
     tag = "categories",
     request_body = RequestCreateCategory,
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the first response for repeated requests")
+    ),
+    security(("bearer_auth" = ["categories:write"])),
     responses(
          (status = 201, description = "category created",  body = ApiResponseCategory),
          (status = 400, description = "Invalid payload",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:write scope",  body = ErrorResponse),
+         (status = 409, description = "Category name already exists or Idempotency-Key request still in progress",  body = ErrorResponse),
+         (status = 422, description = "Idempotency-Key reused with a different payload",  body = ErrorResponse),
     ),
  )]
 #[post("/categories")]
+#[tracing::instrument(name = "api::categories::create", skip_all)]
 async fn handler(
+    _: Authorized<CategoriesWrite>,
     state: Data<AppState>,
     body: web::Json<dto::RequestCreateCategory>,
 ) -> Result<HttpResponse, DomainError> {
@@ -44,11 +55,17 @@ async fn handler(
 
 #[cfg(test)]
 mod tests {
-    use actix_web::{http::StatusCode, test};
+    use actix_web::{
+        http::{header, StatusCode},
+        test,
+    };
 
     use crate::api::{
+        error::ErrorResponse,
+        middleware::idempotency::{IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER},
         resources::categories::{dto, routes::init_routes},
-        tests::utils::get_app,
+        tests::utils::{bearer_token, get_app, get_app_with_auth},
+        utils::response::ApiResponse,
     };
 
     #[actix_web::test]
@@ -64,4 +81,187 @@ mod tests {
 
         assert_eq!(res.status().as_u16(), StatusCode::CREATED);
     }
+
+    #[actix_web::test]
+    async fn it_should_return_field_errors_when_payload_is_invalid() {
+        let (_, app) = get_app(init_routes).await;
+
+        let mut category = dto::RequestCreateCategory::mock_default();
+        category.name = "a".repeat(65);
+
+        let req = test::TestRequest::post()
+            .uri("/categories")
+            .set_json(category)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
+
+        let body = test::read_body(res).await;
+        let problem: ErrorResponse = serde_json::from_slice(&body).unwrap();
+
+        let errors = problem.errors.unwrap();
+        assert_eq!(errors.get("name").unwrap()[0].code, "length");
+        assert_eq!(problem.instance.as_deref(), Some("/categories"));
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_conflict_error_when_name_already_exists() {
+        let (_, app) = get_app(init_routes).await;
+        let category = dto::RequestCreateCategory::mock_default();
+
+        let req = test::TestRequest::post()
+            .uri("/categories")
+            .set_json(category.clone())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::CREATED);
+
+        let mut duplicated = dto::RequestCreateCategory::mock_default();
+        duplicated.name = category.name.to_uppercase();
+
+        let req = test::TestRequest::post()
+            .uri("/categories")
+            .set_json(duplicated)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::CONFLICT);
+
+        let body = test::read_body(res).await;
+        let problem: ErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.detail, "category name already exists");
+    }
+
+    #[actix_web::test]
+    async fn it_should_replay_category_created_with_same_idempotency_key() {
+        let (_, app) = get_app(init_routes).await;
+        let idempotency_key = uuid::Uuid::new_v4().to_string();
+        let category = dto::RequestCreateCategory::mock_default();
+
+        let req = test::TestRequest::post()
+            .uri("/categories")
+            .insert_header((IDEMPOTENCY_KEY_HEADER, idempotency_key.clone()))
+            .set_json(category.clone())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::CREATED);
+
+        let body = test::read_body(res).await;
+        let created: ApiResponse<dto::ResponseCategory> = serde_json::from_slice(&body).unwrap();
+
+        let req = test::TestRequest::post()
+            .uri("/categories")
+            .insert_header((IDEMPOTENCY_KEY_HEADER, idempotency_key))
+            .set_json(category)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::CREATED);
+        assert!(res.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER));
+
+        let body = test::read_body(res).await;
+        let replayed: ApiResponse<dto::ResponseCategory> = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            created.records.first().unwrap().id,
+            replayed.records.first().unwrap().id
+        );
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_unprocessable_entity_when_idempotency_key_reused_with_different_payload(
+    ) {
+        let (_, app) = get_app(init_routes).await;
+        let idempotency_key = uuid::Uuid::new_v4().to_string();
+
+        let req = test::TestRequest::post()
+            .uri("/categories")
+            .insert_header((IDEMPOTENCY_KEY_HEADER, idempotency_key.clone()))
+            .set_json(dto::RequestCreateCategory::mock_default())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::CREATED);
+
+        let mut category = dto::RequestCreateCategory::mock_default();
+        category.name = String::from("Pizzas");
+
+        let req = test::TestRequest::post()
+            .uri("/categories")
+            .insert_header((IDEMPOTENCY_KEY_HEADER, idempotency_key))
+            .set_json(category)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[actix_web::test]
+    async fn it_should_read_idempotent_payload_up_to_the_json_limit() {
+        let (_, app) = get_app(init_routes).await;
+
+        let mut category = dto::RequestCreateCategory::mock_default();
+        category.description = Some("a".repeat(512 * 1024));
+
+        let req = test::TestRequest::post()
+            .uri("/categories")
+            .insert_header((IDEMPOTENCY_KEY_HEADER, uuid::Uuid::new_v4().to_string()))
+            .set_json(category)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_unauthorized_when_bearer_token_is_missing_or_invalid() {
+        let (_, app) = get_app_with_auth(init_routes).await;
+
+        let req = test::TestRequest::post()
+            .uri("/categories")
+            .set_json(dto::RequestCreateCategory::mock_default())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::UNAUTHORIZED);
+        assert!(res.headers().contains_key(header::WWW_AUTHENTICATE));
+
+        let req = test::TestRequest::post()
+            .uri("/categories")
+            .insert_header((header::AUTHORIZATION, "Bearer invalid"))
+            .insert_header((header::ORIGIN, "http://localhost"))
+            .set_json(dto::RequestCreateCategory::mock_default())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status().as_u16(), StatusCode::UNAUTHORIZED);
+        assert!(res
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_forbidden_when_write_scope_is_missing() {
+        let (_, app) = get_app_with_auth(init_routes).await;
+
+        let req = test::TestRequest::post()
+            .uri("/categories")
+            .insert_header((header::AUTHORIZATION, bearer_token("categories:read")))
+            .set_json(dto::RequestCreateCategory::mock_default())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::FORBIDDEN);
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_category_created_with_write_scope() {
+        let (_, app) = get_app_with_auth(init_routes).await;
+
+        let req = test::TestRequest::post()
+            .uri("/categories")
+            .insert_header((header::AUTHORIZATION, bearer_token("categories:write")))
+            .set_json(dto::RequestCreateCategory::mock_default())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::CREATED);
+    }
 }
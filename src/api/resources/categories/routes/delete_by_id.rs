@@ -1,12 +1,16 @@
 use actix_web::{
     delete,
     web::{self, Data},
-    HttpResponse,
+    HttpRequest, HttpResponse,
 };
 use uuid::Uuid;
 
 use crate::{
-    api::lib::AppState,
+    api::{
+        lib::AppState,
+        middleware::auth::{Authorized, CategoriesWrite},
+        utils::etag,
+    },
     domain::{categories, error::DomainError},
 };
 
@@ -17,22 +21,33 @@ use crate::{
     tag = "categories",
     params(
         ("category_id" = Uuid, Path, description = "category uuid"),
+        ("If-Match" = Option<String>, Header, description = "Deletes only if the category ETag matches"),
     ),
+    security(("bearer_auth" = ["categories:write"])),
     responses(
          (status = 204, description = "category deleted"),
          (status = 400, description = "Invalid category id",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:write scope",  body = ErrorResponse),
          (status = 404, description = "category not found",  body = ErrorResponse),
          (status = 409, description = "category is in use",  body = ErrorResponse),
+         (status = 412, description = "category version does not match",  body = ErrorResponse),
     ),
  )]
 #[delete("/categories/{category_id}")]
+#[tracing::instrument(name = "api::categories::delete_by_id", skip_all)]
 async fn handler(
+    _: Authorized<CategoriesWrite>,
     state: Data<AppState>,
     param: web::Path<Uuid>,
+    req: HttpRequest,
 ) -> Result<HttpResponse, DomainError> {
+    let version = etag::if_match_version(&req)?;
+
     categories::resources::delete_by_id::execute(
         state.category_repository.clone(),
         param.to_owned(),
+        version,
     )
     .await?;
     Ok(HttpResponse::NoContent().finish())
@@ -40,11 +55,18 @@ async fn handler(
 
 #[cfg(test)]
 mod tests {
-    use actix_web::{http::StatusCode, test};
+    use actix_web::{
+        http::{header, StatusCode},
+        test,
+    };
     use uuid::Uuid;
 
     use crate::{
-        api::{resources::categories::routes::init_routes, tests::utils::get_app},
+        api::{
+            error::{ErrorResponse, PROBLEM_JSON_CONTENT_TYPE},
+            resources::categories::routes::init_routes,
+            tests::utils::get_app,
+        },
         domain::categories::{model::CategoryCreateModel, repository::CategoryRepository},
     };
 
@@ -66,17 +88,34 @@ mod tests {
         let res = test::call_service(&app, req).await;
 
         assert!(res.status().is_success());
+
+        let deleted = repositories
+            .category_repository
+            .find_by_id(&category_model.id)
+            .await
+            .unwrap();
+        assert!(deleted.is_none());
     }
 
     #[actix_web::test]
     async fn it_should_return_not_found_error_when_deleting() {
         let (_, app) = get_app(init_routes).await;
 
-        let req = test::TestRequest::delete()
-            .uri(&format!("/categories/{}", Uuid::new_v4()))
-            .to_request();
+        let uri = format!("/categories/{}", Uuid::new_v4());
+        let req = test::TestRequest::delete().uri(&uri).to_request();
         let res = test::call_service(&app, req).await;
 
         assert_eq!(res.status().as_u16(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            PROBLEM_JSON_CONTENT_TYPE
+        );
+
+        let body = test::read_body(res).await;
+        let problem: ErrorResponse = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(problem.status, StatusCode::NOT_FOUND.as_u16());
+        assert_eq!(problem.title, "Not Found");
+        assert_eq!(problem.instance, Some(uri));
     }
 }
@@ -1,8 +1,5 @@
-use actix_web::{
-    get,
-    web::{Data, Query},
-    HttpResponse,
-};
+use actix_web::{get, web::Data, HttpResponse};
+use serde_qs::actix::QsQuery;
 
 use validator::Validate;
 
@@ -10,10 +7,17 @@ use crate::{
     api::{
         config,
         lib::AppState,
+        middleware::auth::{Authorized, CategoriesRead},
         resources::categories::dto::{self, ResponseCategory},
-        utils::response::ApiResponse,
+        utils::response::{ApiResponse, Meta},
+    },
+    domain::{
+        categories::{
+            self,
+            model::{CategoryFindModel, CategoryPaginationModel},
+        },
+        error::DomainError,
     },
-    domain::{categories, error::DomainError},
 };
 
 #[utoipa::path(
@@ -24,40 +28,86 @@ use crate::{
     params(
         dto::RequestFindCategories
     ),
+    security(("bearer_auth" = ["categories:read"])),
     responses(
          (status = 200, description = "categories",  body = ApiResponseCategory),
          (status = 204, description = "no content categories"),
          (status = 400, description = "Invalid query parameters",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:read scope",  body = ErrorResponse),
     ),
  )]
 #[get("/categories")]
+#[tracing::instrument(name = "api::categories::find", skip_all)]
 async fn handler(
+    _: Authorized<CategoriesRead>,
     state: Data<AppState>,
-    query: Query<dto::RequestFindCategories>,
+    query: QsQuery<dto::RequestFindCategories>,
 ) -> Result<HttpResponse, DomainError> {
     query.validate()?;
 
-    let page = query.page.unwrap_or(1);
+    if query.page.is_some() && query.cursor.is_some() {
+        return Err(DomainError::BadRequest(String::from(
+            "page and cursor can not be combined",
+        )));
+    }
+
     let page_size = query
         .page_size
         .unwrap_or(config::get_config().page_size_default);
 
-    let name = query.name.to_owned();
+    let (pagination, page) = match &query.cursor {
+        Some(cursor) => (
+            CategoryPaginationModel::Cursor(dto::decode_cursor(cursor)?),
+            None,
+        ),
+        None => {
+            let page = query.page.unwrap_or(1);
+            (CategoryPaginationModel::Page(page), Some(page))
+        }
+    };
+
+    let sort = match &query.sort {
+        Some(sort) => dto::parse_sort(sort)?,
+        None => vec![],
+    };
+
+    let category_find_model = CategoryFindModel {
+        name: query.name.to_owned(),
+        include_inactive: query.include_inactive.unwrap_or(false),
+        is_active: query.is_active,
+        ids: query.ids.to_owned(),
+        created_at_from: query.created_at_from,
+        created_at_to: query.created_at_to,
+        updated_at_from: query.updated_at_from,
+        updated_at_to: query.updated_at_to,
+        sort,
+        pagination,
+        page_size,
+        with_count: query.with_count.unwrap_or(query.cursor.is_none()),
+    };
 
     let result = categories::resources::find::execute(
         state.category_repository.clone(),
-        name,
-        page,
-        page_size,
+        category_find_model,
     )
     .await?;
 
-    if let Some((categories, count)) = result {
-        let response = ApiResponse::<ResponseCategory>::new(
-            categories.into_iter().map(|i| i.into()).collect(),
-            Some(page),
-            Some(count),
-            Some(page_size),
+    if let Some(category_page) = result {
+        let meta = Meta::paginated(
+            page,
+            category_page.count,
+            page_size,
+            category_page.next_cursor.as_ref().map(dto::encode_cursor),
+            category_page.prev_cursor.as_ref().map(dto::encode_cursor),
+        );
+        let response = ApiResponse::<ResponseCategory>::with_meta(
+            category_page
+                .categories
+                .into_iter()
+                .map(|i| i.into())
+                .collect(),
+            meta,
         );
         return Ok(HttpResponse::Ok().json(response));
     }
@@ -66,6 +116,7 @@ async fn handler(
 }
 
 #[cfg(test)]
+#[allow(clippy::unnecessary_to_owned)]
 mod tests {
     use actix_web::{http::StatusCode, test};
 
@@ -154,5 +205,250 @@ mod tests {
         let res = test::call_service(&app, req).await;
 
         assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::get()
+            .uri("/categories?page=0&page_size=24")
+            .to_request();
+
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_deleted_categories_only_when_include_inactive() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let mut category_model = CategoryCreateModel::mock_default();
+        category_model.name = category_model.id.to_string();
+        repositories
+            .category_repository
+            .insert(&category_model.clone())
+            .await
+            .unwrap();
+        repositories
+            .category_repository
+            .delete_by_id(&category_model.id, &None)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/categories?name={}", category_model.name))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::NO_CONTENT);
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/categories?name={}&include_inactive=true",
+                category_model.name
+            ))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let response_categories_finded: ApiResponse<dto::ResponseCategory> =
+            serde_json::from_slice(&body).unwrap();
+
+        assert!(
+            !response_categories_finded
+                .records
+                .first()
+                .unwrap()
+                .is_active
+        );
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_categories_finded_by_cursor() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let name = uuid::Uuid::new_v4().to_string();
+        for index in 0..3 {
+            let mut category_model = CategoryCreateModel::mock_default();
+            category_model.name = format!("{name} {index}");
+            repositories
+                .category_repository
+                .insert(&category_model)
+                .await
+                .unwrap();
+        }
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/categories?name={name}&page_size=2"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let first_page: ApiResponse<dto::ResponseCategory> = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(first_page.records.len(), 2);
+        assert_eq!(first_page.meta.count, Some(3));
+        assert!(first_page.meta.prev_cursor.is_none());
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/categories?name={name}&page_size=2&cursor={}",
+                first_page.meta.next_cursor.as_ref().unwrap()
+            ))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let second_page: ApiResponse<dto::ResponseCategory> =
+            serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(second_page.records.len(), 1);
+        assert!(second_page.meta.count.is_none());
+        assert!(second_page.meta.next_cursor.is_none());
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/categories?name={name}&page_size=2&cursor={}",
+                second_page.meta.prev_cursor.unwrap()
+            ))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        let body = test::read_body(res).await;
+        let previous_page: ApiResponse<dto::ResponseCategory> =
+            serde_json::from_slice(&body).unwrap();
+
+        let ids = |page: &ApiResponse<dto::ResponseCategory>| -> Vec<uuid::Uuid> {
+            page.records.iter().map(|i| i.id).collect()
+        };
+        assert_eq!(ids(&previous_page), ids(&first_page));
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_bad_request_error_when_cursor_is_invalid() {
+        let (_, app) = get_app(init_routes).await;
+
+        let req = test::TestRequest::get()
+            .uri("/categories?cursor=invalid")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::get()
+            .uri("/categories?page=1&cursor=invalid")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_categories_finded_by_filters_and_sorted() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let prefix = uuid::Uuid::new_v4();
+        let mut ids = vec![];
+        for name in ["B", "A", "C"] {
+            let mut category_model = CategoryCreateModel::mock_default();
+            category_model.name = format!("{prefix} {name}");
+            repositories
+                .category_repository
+                .insert(&category_model)
+                .await
+                .unwrap();
+            ids.push(category_model.id);
+        }
+
+        let created_at_from = chrono::Utc::now() - chrono::Duration::minutes(1);
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/categories?sort=-name&is_active=true&created_at_from={}&ids[]={}&ids[]={}&ids[]={}",
+                created_at_from.format("%Y-%m-%dT%H:%M:%SZ"),
+                ids[0],
+                ids[1],
+                ids[2],
+            ))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let response_categories_finded: ApiResponse<dto::ResponseCategory> =
+            serde_json::from_slice(&body).unwrap();
+
+        let names: Vec<String> = response_categories_finded
+            .records
+            .into_iter()
+            .map(|i| i.name.replace(&format!("{prefix} "), ""))
+            .collect();
+        assert_eq!(names, vec!["C", "B", "A"]);
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_categories_finded_by_cursor_when_sorted() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let prefix = uuid::Uuid::new_v4();
+        let mut ids = vec![];
+        for name in ["B", "A", "C"] {
+            let mut category_model = CategoryCreateModel::mock_default();
+            category_model.name = format!("{prefix} {name}");
+            repositories
+                .category_repository
+                .insert(&category_model)
+                .await
+                .unwrap();
+            ids.push(category_model.id);
+        }
+        let ids = ids
+            .iter()
+            .map(|id| format!("ids[]={id}"))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/categories?sort=name&page_size=2&{ids}"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        let body = test::read_body(res).await;
+        let first_page: ApiResponse<dto::ResponseCategory> = serde_json::from_slice(&body).unwrap();
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/categories?sort=name&page_size=2&{ids}&cursor={}",
+                first_page.meta.next_cursor.unwrap()
+            ))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        let body = test::read_body(res).await;
+        let second_page: ApiResponse<dto::ResponseCategory> =
+            serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(first_page.records[1].name, format!("{prefix} B"));
+        assert_eq!(second_page.records.len(), 1);
+        assert_eq!(second_page.records[0].name, format!("{prefix} C"));
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_bad_request_error_when_sort_is_not_supported() {
+        let (_, app) = get_app(init_routes).await;
+
+        let req = test::TestRequest::get()
+            .uri("/categories?sort=description")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
     }
 }
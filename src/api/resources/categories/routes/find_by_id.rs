@@ -1,13 +1,17 @@
 use actix_web::{
     get,
+    http::header::ETag,
     web::{self, Data},
-    HttpResponse,
+    HttpRequest, HttpResponse,
 };
 use uuid::Uuid;
 
 use crate::{
     api::{
-        lib::AppState, resources::categories::dto::ResponseCategory, utils::response::ApiResponse,
+        lib::AppState,
+        middleware::auth::{Authorized, CategoriesRead},
+        resources::categories::dto::ResponseCategory,
+        utils::{etag, response::ApiResponse},
     },
     domain::{categories, error::DomainError},
 };
@@ -19,16 +23,24 @@ use crate::{
     tag = "categories",
     params(
         ("category_id" = Uuid, Path, description = "Category uuid"),
+        ("If-None-Match" = Option<String>, Header, description = "Category ETag already held by the client"),
     ),
+    security(("bearer_auth" = ["categories:read"])),
     responses(
          (status = 200, description = "Category finded",  body = ApiResponseCategory),
          (status = 204, description = "Category no content"),
+         (status = 304, description = "Category not modified"),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:read scope",  body = ErrorResponse),
     ),
  )]
 #[get("/categories/{category_id}")]
+#[tracing::instrument(name = "api::categories::find_by_id", skip_all)]
 async fn handler(
+    _: Authorized<CategoriesRead>,
     state: Data<AppState>,
     param: web::Path<Uuid>,
+    req: HttpRequest,
 ) -> Result<HttpResponse, DomainError> {
     let result = categories::resources::find_by_id::execute(
         state.category_repository.clone(),
@@ -37,10 +49,19 @@ async fn handler(
     .await?;
 
     if let Some(category) = result {
+        let entity_tag = etag::entity_tag(category.version);
+        if etag::is_not_modified(&req, category.version) {
+            return Ok(HttpResponse::NotModified()
+                .insert_header(ETag(entity_tag))
+                .finish());
+        }
+
         let response =
             ApiResponse::<ResponseCategory>::new(vec![category.into()], None, None, None);
 
-        return Ok(HttpResponse::Ok().json(response));
+        return Ok(HttpResponse::Ok()
+            .insert_header(ETag(entity_tag))
+            .json(response));
     }
 
     Ok(HttpResponse::NoContent().finish())
@@ -48,7 +69,10 @@ async fn handler(
 
 #[cfg(test)]
 mod tests {
-    use actix_web::{http::StatusCode, test};
+    use actix_web::{
+        http::{header, StatusCode},
+        test,
+    };
     use uuid::Uuid;
 
     use crate::{
@@ -87,4 +111,31 @@ mod tests {
 
         assert_eq!(res.status().as_u16(), StatusCode::NO_CONTENT);
     }
+
+    #[actix_web::test]
+    async fn it_should_return_not_modified_when_etag_matches() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&category_model.clone())
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/categories/{}", category_model.id))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        let entity_tag = res.headers().get(header::ETAG).unwrap().clone();
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/categories/{}", category_model.id))
+            .insert_header((header::IF_NONE_MATCH, entity_tag))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::NOT_MODIFIED);
+    }
 }
@@ -0,0 +1,144 @@
+use actix_web::{
+    get,
+    web::{self, Data},
+    HttpResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        lib::AppState,
+        middleware::auth::{Authorized, CategoriesRead},
+        resources::categories::dto::ResponseCategory,
+        utils::response::ApiResponse,
+    },
+    domain::{categories, error::DomainError},
+};
+
+#[utoipa::path(
+    get,
+    operation_id = "find_category_children",
+    path = "/categories/{category_id}/children",
+    tag = "categories",
+    params(
+        ("category_id" = Uuid, Path, description = "Category uuid"),
+    ),
+    security(("bearer_auth" = ["categories:read"])),
+    responses(
+         (status = 200, description = "Category children finded",  body = ApiResponseCategory),
+         (status = 204, description = "Category has no children"),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:read scope",  body = ErrorResponse),
+         (status = 404, description = "Category not found",  body = ErrorResponse),
+    ),
+ )]
+#[get("/categories/{category_id}/children")]
+#[tracing::instrument(name = "api::categories::find_children", skip_all)]
+async fn handler(
+    _: Authorized<CategoriesRead>,
+    state: Data<AppState>,
+    param: web::Path<Uuid>,
+) -> Result<HttpResponse, DomainError> {
+    let result = categories::resources::find_children::execute(
+        state.category_repository.clone(),
+        param.to_owned(),
+    )
+    .await?;
+
+    if let Some(children) = result {
+        let count = children.len() as u32;
+        let response = ApiResponse::<ResponseCategory>::new(
+            children.into_iter().map(|i| i.into()).collect(),
+            None,
+            Some(count),
+            Some(count),
+        );
+
+        return Ok(HttpResponse::Ok().json(response));
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test};
+    use uuid::Uuid;
+
+    use crate::{
+        api::{
+            resources::categories::{dto, routes::init_routes},
+            tests::utils::get_app,
+            utils::response::ApiResponse,
+        },
+        domain::categories::{model::CategoryCreateModel, repository::CategoryRepository},
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_category_children_finded() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let parent_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&parent_model.clone())
+            .await
+            .unwrap();
+        let child_model = CategoryCreateModel::mock_default().with_parent_id(parent_model.id);
+        repositories
+            .category_repository
+            .insert(&child_model.clone())
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/categories/{}/children", parent_model.id))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let response_children: ApiResponse<dto::ResponseCategory> =
+            serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(response_children.records.len(), 1);
+        assert_eq!(
+            response_children.records.first().unwrap().parent_id,
+            Some(parent_model.id)
+        );
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_no_content_when_category_has_no_children() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&category_model.clone())
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/categories/{}/children", category_model.id))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::NO_CONTENT);
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_not_found_error_when_category_does_not_exist() {
+        let (_, app) = get_app(init_routes).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/categories/{}/children", Uuid::new_v4()))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::NOT_FOUND);
+    }
+}
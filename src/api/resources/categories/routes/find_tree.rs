@@ -0,0 +1,113 @@
+use actix_web::{
+    get,
+    web::{self, Data},
+    HttpResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        lib::AppState,
+        middleware::auth::{Authorized, CategoriesRead},
+        resources::categories::dto::ResponseCategoryTree,
+        utils::response::ApiResponse,
+    },
+    domain::{categories, error::DomainError},
+};
+
+#[utoipa::path(
+    get,
+    operation_id = "find_category_tree",
+    path = "/categories/{category_id}/tree",
+    tag = "categories",
+    params(
+        ("category_id" = Uuid, Path, description = "Category uuid"),
+    ),
+    security(("bearer_auth" = ["categories:read"])),
+    responses(
+         (status = 200, description = "Category tree finded",  body = ApiResponseCategoryTree),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:read scope",  body = ErrorResponse),
+         (status = 404, description = "Category not found",  body = ErrorResponse),
+    ),
+ )]
+#[get("/categories/{category_id}/tree")]
+#[tracing::instrument(name = "api::categories::find_tree", skip_all)]
+async fn handler(
+    _: Authorized<CategoriesRead>,
+    state: Data<AppState>,
+    param: web::Path<Uuid>,
+) -> Result<HttpResponse, DomainError> {
+    let tree = categories::resources::find_tree::execute(
+        state.category_repository.clone(),
+        param.to_owned(),
+    )
+    .await?;
+
+    let response = ApiResponse::<ResponseCategoryTree>::new(vec![tree.into()], None, None, None);
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test};
+    use uuid::Uuid;
+
+    use crate::{
+        api::{
+            resources::categories::{dto, routes::init_routes},
+            tests::utils::get_app,
+            utils::response::ApiResponse,
+        },
+        domain::categories::{model::CategoryCreateModel, repository::CategoryRepository},
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_category_tree_finded() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let root_model = CategoryCreateModel::mock_default();
+        let child_model = CategoryCreateModel::mock_default().with_parent_id(root_model.id);
+        let grandchild_model = CategoryCreateModel::mock_default().with_parent_id(child_model.id);
+        for category_model in [&root_model, &child_model, &grandchild_model] {
+            repositories
+                .category_repository
+                .insert(category_model)
+                .await
+                .unwrap();
+        }
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/categories/{}/tree", root_model.id))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let response_tree: ApiResponse<dto::ResponseCategoryTree> =
+            serde_json::from_slice(&body).unwrap();
+
+        let root = response_tree.records.first().unwrap();
+        assert_eq!(root.id, root_model.id);
+        assert_eq!(root.children.first().unwrap().id, child_model.id);
+        assert_eq!(
+            root.children.first().unwrap().children.first().unwrap().id,
+            grandchild_model.id
+        );
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_not_found_error_when_category_does_not_exist() {
+        let (_, app) = get_app(init_routes).await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/categories/{}/tree", Uuid::new_v4()))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::NOT_FOUND);
+    }
+}
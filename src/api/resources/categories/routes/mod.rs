@@ -1,15 +1,35 @@
 use actix_web::web;
 
+pub mod bulk_create;
+pub mod bulk_delete;
+pub mod bulk_update;
 pub mod create;
 pub mod delete_by_id;
 pub mod find;
 pub mod find_by_id;
+pub mod find_children;
+pub mod find_tree;
+pub mod patch_by_id;
+pub mod purge_by_id;
+pub mod restore_by_id;
+pub mod search;
 pub mod update_by_id;
 
 pub fn init_routes(config: &mut web::ServiceConfig) {
+    // Registered before the `{category_id}` routes so `bulk` and `search` are
+    // not parsed as an id.
+    config.service(search::handler);
+    config.service(bulk_create::handler);
+    config.service(bulk_update::handler);
+    config.service(bulk_delete::handler);
     config.service(create::handler);
     config.service(update_by_id::handler);
+    config.service(patch_by_id::handler);
     config.service(find_by_id::handler);
+    config.service(find_children::handler);
+    config.service(find_tree::handler);
     config.service(find::handler);
     config.service(delete_by_id::handler);
+    config.service(restore_by_id::handler);
+    config.service(purge_by_id::handler);
 }
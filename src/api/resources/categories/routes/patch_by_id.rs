@@ -0,0 +1,153 @@
+use actix_web::{
+    http::header::ETag,
+    patch,
+    web::{self, Data},
+    HttpRequest, HttpResponse,
+};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    api::{
+        lib::AppState,
+        middleware::auth::{Authorized, CategoriesWrite},
+        resources::categories::dto::{self, ResponseCategory},
+        utils::{etag, response::ApiResponse},
+    },
+    domain::{categories, error::DomainError},
+};
+
+#[utoipa::path(
+    patch,
+    operation_id = "patch_categories",
+    path = "/categories/{category_id}",
+    tag = "categories",
+    params(
+        ("category_id" = Uuid, Path, description = "Category uuid"),
+        ("If-Match" = Option<String>, Header, description = "Patches only if the category ETag matches"),
+    ),
+    request_body(content = RequestPatchCategory, content_type = "application/merge-patch+json"),
+    security(("bearer_auth" = ["categories:write"])),
+    responses(
+         (status = 200, description = "Category patched",  body = ApiResponseCategory),
+         (status = 400, description = "Invalid payload",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:write scope",  body = ErrorResponse),
+         (status = 404, description = "Category not found",  body = ErrorResponse),
+         (status = 409, description = "Category name already exists",  body = ErrorResponse),
+         (status = 412, description = "Category version does not match",  body = ErrorResponse),
+    ),
+ )]
+#[patch("/categories/{category_id}")]
+#[tracing::instrument(name = "api::categories::patch_by_id", skip_all)]
+async fn handler(
+    _: Authorized<CategoriesWrite>,
+    state: Data<AppState>,
+    param: web::Path<Uuid>,
+    body: web::Json<dto::RequestPatchCategory>,
+    req: HttpRequest,
+) -> Result<HttpResponse, DomainError> {
+    body.validate()?;
+    let version = etag::if_match_version(&req)?;
+
+    let category = categories::resources::patch_by_id::execute(
+        state.category_repository.clone(),
+        param.to_owned(),
+        body.0.into(),
+        version,
+    )
+    .await?;
+
+    let entity_tag = etag::entity_tag(category.version);
+    let response = ApiResponse::<ResponseCategory>::new(vec![category.into()], None, None, None);
+
+    Ok(HttpResponse::Ok()
+        .insert_header(ETag(entity_tag))
+        .json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        http::{header, StatusCode},
+        test,
+    };
+    use uuid::Uuid;
+
+    use crate::{
+        api::{
+            resources::categories::{dto, routes::init_routes},
+            tests::utils::get_app,
+            utils::response::ApiResponse,
+        },
+        domain::categories::{model::CategoryCreateModel, repository::CategoryRepository},
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_category_patched_keeping_absent_fields() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&category_model.clone())
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::patch()
+            .uri(&format!("/categories/{}", category_model.id))
+            .insert_header((header::CONTENT_TYPE, "application/merge-patch+json"))
+            .set_payload(r#"{"description":null}"#)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let response_category: ApiResponse<dto::ResponseCategory> =
+            serde_json::from_slice(&body).unwrap();
+        let category = response_category.records.first().unwrap();
+
+        assert_eq!(category.name, category_model.name);
+        assert!(category.description.is_none());
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_bad_request_error_when_name_is_null() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&category_model.clone())
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::patch()
+            .uri(&format!("/categories/{}", category_model.id))
+            .insert_header((header::CONTENT_TYPE, "application/merge-patch+json"))
+            .set_payload(r#"{"name":null}"#)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_not_found_error_when_patched_because_invalid_id() {
+        let (_, app) = get_app(init_routes).await;
+
+        let req = test::TestRequest::patch()
+            .uri(&format!("/categories/{}", Uuid::new_v4()))
+            .set_json(dto::RequestPatchCategory {
+                name: Some(String::from("Pizzas")),
+                ..Default::default()
+            })
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,154 @@
+use actix_web::{
+    delete,
+    web::{self, Data},
+    HttpResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        lib::AppState,
+        middleware::auth::{Authorized, CategoriesAdmin},
+    },
+    domain::{categories, error::DomainError},
+};
+
+#[utoipa::path(
+    delete,
+    operation_id = "purge_categories",
+    path = "/admin/categories/{category_id}",
+    tag = "admin",
+    params(
+        ("category_id" = Uuid, Path, description = "category uuid"),
+    ),
+    security(("bearer_auth" = ["categories:admin"])),
+    responses(
+         (status = 204, description = "category permanently deleted"),
+         (status = 400, description = "Invalid category id",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:admin scope",  body = ErrorResponse),
+         (status = 404, description = "category not found",  body = ErrorResponse),
+         (status = 409, description = "category is in use",  body = ErrorResponse),
+    ),
+ )]
+#[delete("/admin/categories/{category_id}")]
+#[tracing::instrument(name = "api::categories::purge_by_id", skip_all)]
+async fn handler(
+    _: Authorized<CategoriesAdmin>,
+    state: Data<AppState>,
+    param: web::Path<Uuid>,
+) -> Result<HttpResponse, DomainError> {
+    categories::resources::purge_by_id::execute(
+        state.category_repository.clone(),
+        param.to_owned(),
+    )
+    .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        http::{header, StatusCode},
+        test,
+    };
+    use uuid::Uuid;
+
+    use crate::{
+        api::{
+            error::ErrorResponse,
+            resources::categories::routes::init_routes,
+            tests::utils::{bearer_token, get_app, get_app_with_auth},
+        },
+        domain::categories::{model::CategoryCreateModel, repository::CategoryRepository},
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_void_category_purged() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&category_model.clone())
+            .await
+            .unwrap();
+        repositories
+            .category_repository
+            .delete_by_id(&category_model.id, &None)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/admin/categories/{}", category_model.id))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let restored = repositories
+            .category_repository
+            .restore_by_id(&category_model.id)
+            .await
+            .unwrap();
+        assert!(restored.is_none());
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_conflict_error_when_category_is_in_use() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        let child_model = CategoryCreateModel::mock_default().with_parent_id(category_model.id);
+        for model in [&category_model, &child_model] {
+            repositories
+                .category_repository
+                .insert(model)
+                .await
+                .unwrap();
+        }
+        repositories
+            .category_repository
+            .delete_by_id(&category_model.id, &None)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/admin/categories/{}", category_model.id))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::CONFLICT);
+
+        let body = test::read_body(res).await;
+        let problem: ErrorResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.detail, "category is referenced by another category");
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_not_found_error_when_purging() {
+        let (_, app) = get_app(init_routes).await;
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/admin/categories/{}", Uuid::new_v4()))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_forbidden_when_purging_without_admin_scope() {
+        let (_, app) = get_app_with_auth(init_routes).await;
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/admin/categories/{}", Uuid::new_v4()))
+            .insert_header((header::AUTHORIZATION, bearer_token("categories:write")))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::FORBIDDEN);
+    }
+}
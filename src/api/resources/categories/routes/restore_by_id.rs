@@ -0,0 +1,119 @@
+use actix_web::{
+    post,
+    web::{self, Data},
+    HttpResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        lib::AppState,
+        middleware::auth::{Authorized, CategoriesWrite},
+        resources::categories::dto::ResponseCategory,
+        utils::response::ApiResponse,
+    },
+    domain::{categories, error::DomainError},
+};
+
+#[utoipa::path(
+    post,
+    operation_id = "restore_categories",
+    path = "/categories/{category_id}/restore",
+    tag = "categories",
+    params(
+        ("category_id" = Uuid, Path, description = "Category uuid"),
+    ),
+    security(("bearer_auth" = ["categories:write"])),
+    responses(
+         (status = 200, description = "Category restored",  body = ApiResponseCategory),
+         (status = 400, description = "Invalid category id",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:write scope",  body = ErrorResponse),
+         (status = 404, description = "Deleted category not found",  body = ErrorResponse),
+         (status = 409, description = "Category name already exists",  body = ErrorResponse),
+    ),
+ )]
+#[post("/categories/{category_id}/restore")]
+#[tracing::instrument(name = "api::categories::restore_by_id", skip_all)]
+async fn handler(
+    _: Authorized<CategoriesWrite>,
+    state: Data<AppState>,
+    param: web::Path<Uuid>,
+) -> Result<HttpResponse, DomainError> {
+    let category = categories::resources::restore_by_id::execute(
+        state.category_repository.clone(),
+        param.to_owned(),
+    )
+    .await?;
+
+    let response = ApiResponse::<ResponseCategory>::new(vec![category.into()], None, None, None);
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test};
+
+    use crate::{
+        api::{
+            resources::categories::{dto, routes::init_routes},
+            tests::utils::get_app,
+            utils::response::ApiResponse,
+        },
+        domain::categories::{model::CategoryCreateModel, repository::CategoryRepository},
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_category_restored() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&category_model.clone())
+            .await
+            .unwrap();
+        repositories
+            .category_repository
+            .delete_by_id(&category_model.id, &None)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/categories/{}/restore", category_model.id))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let response_category_restored: ApiResponse<dto::ResponseCategory> =
+            serde_json::from_slice(&body).unwrap();
+
+        let category = response_category_restored.records.first().unwrap();
+        assert!(category.is_active);
+        assert!(category.deleted_at.is_none());
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_not_found_error_when_category_is_not_deleted() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&category_model.clone())
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/categories/{}/restore", category_model.id))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,217 @@
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+
+use validator::Validate;
+
+use crate::{
+    api::{
+        config,
+        lib::AppState,
+        middleware::auth::{Authorized, CategoriesRead},
+        resources::categories::dto::{self, ResponseCategorySearch},
+        utils::response::ApiResponse,
+    },
+    domain::{categories, error::DomainError},
+};
+
+#[utoipa::path(
+    get,
+    operation_id = "search_categories",
+    path = "/categories/search",
+    tag = "categories",
+    params(
+        dto::RequestSearchCategories
+    ),
+    security(("bearer_auth" = ["categories:read"])),
+    responses(
+         (status = 200, description = "categories ordered by relevance",  body = ApiResponseCategorySearch),
+         (status = 204, description = "no content categories"),
+         (status = 400, description = "Invalid query parameters",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:read scope",  body = ErrorResponse),
+    ),
+ )]
+#[get("/categories/search")]
+#[tracing::instrument(name = "api::categories::search", skip_all)]
+async fn handler(
+    _: Authorized<CategoriesRead>,
+    state: Data<AppState>,
+    query: Query<dto::RequestSearchCategories>,
+) -> Result<HttpResponse, DomainError> {
+    query.validate()?;
+
+    let page = query.page.unwrap_or(1);
+    let page_size = query
+        .page_size
+        .unwrap_or(config::get_config().page_size_default);
+
+    let result = categories::resources::search::execute(
+        state.category_repository.clone(),
+        query.q.to_owned(),
+        page,
+        page_size,
+    )
+    .await?;
+
+    if let Some((categories, count)) = result {
+        let response = ApiResponse::<ResponseCategorySearch>::new(
+            categories.into_iter().map(|i| i.into()).collect(),
+            Some(page),
+            Some(count),
+            Some(page_size),
+        );
+        return Ok(HttpResponse::Ok().json(response));
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test};
+
+    use crate::{
+        api::{
+            resources::categories::{dto, routes::init_routes},
+            tests::utils::get_app,
+            utils::response::ApiResponse,
+        },
+        domain::categories::{model::CategoryCreateModel, repository::CategoryRepository},
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_categories_searched_with_highlight() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&category_model)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::get()
+            .uri("/categories/search?q=big%20burgers")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let response: ApiResponse<dto::ResponseCategorySearch> =
+            serde_json::from_slice(&body).unwrap();
+
+        let record = response
+            .records
+            .iter()
+            .find(|i| i.category.id == category_model.id)
+            .unwrap();
+        assert!(record.highlight.name.starts_with("<mark>Burgers</mark> "));
+        assert_eq!(
+            record.highlight.description.as_deref(),
+            Some("The <mark>Big</mark> <mark>Burgers</mark>")
+        );
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_categories_searched_with_escaped_highlight() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let mut category_model = CategoryCreateModel::mock_default();
+        category_model.description = Some(String::from("<script>Big</script> Burgers"));
+        repositories
+            .category_repository
+            .insert(&category_model)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::get()
+            .uri("/categories/search?q=big%20burgers")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let response: ApiResponse<dto::ResponseCategorySearch> =
+            serde_json::from_slice(&body).unwrap();
+
+        let record = response
+            .records
+            .iter()
+            .find(|i| i.category.id == category_model.id)
+            .unwrap();
+        let description = record.highlight.description.as_deref().unwrap();
+        assert!(!description.contains("<script>"));
+        assert!(description.contains("&lt;script&gt;"));
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_categories_searched_with_typo() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let mut category_model = CategoryCreateModel::mock_default();
+        category_model.name = category_model
+            .id
+            .simple()
+            .to_string()
+            .chars()
+            .map(|c| (b'a' + c.to_digit(16).unwrap() as u8) as char)
+            .collect();
+        repositories
+            .category_repository
+            .insert(&category_model)
+            .await
+            .unwrap();
+
+        let mut typo: Vec<char> = category_model.name.chars().collect();
+        typo.swap(4, 5);
+        let typo: String = typo.into_iter().collect();
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/categories/search?q={typo}"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let response: ApiResponse<dto::ResponseCategorySearch> =
+            serde_json::from_slice(&body).unwrap();
+
+        assert!(response
+            .records
+            .iter()
+            .any(|i| i.category.id == category_model.id));
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_bad_request_error_when_query_is_missing() {
+        let (_, app) = get_app(init_routes).await;
+
+        let req = test::TestRequest::get()
+            .uri("/categories/search")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_bad_request_error_when_page_is_zero() {
+        let (_, app) = get_app(init_routes).await;
+
+        let req = test::TestRequest::get()
+            .uri("/categories/search?q=burgers&page=0")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
+    }
+}
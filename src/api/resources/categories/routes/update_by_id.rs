@@ -1,7 +1,8 @@
 use actix_web::{
+    http::header::ETag,
     put,
     web::{self, Data},
-    HttpResponse,
+    HttpRequest, HttpResponse,
 };
 use uuid::Uuid;
 use validator::Validate;
@@ -9,8 +10,9 @@ use validator::Validate;
 use crate::{
     api::{
         lib::AppState,
+        middleware::auth::{Authorized, CategoriesWrite},
         resources::categories::dto::{self, ResponseCategory},
-        utils::response::ApiResponse,
+        utils::{etag, response::ApiResponse},
     },
     domain::{categories, error::DomainError},
 };
@@ -22,37 +24,55 @@ use crate::{
     tag = "categories",
     params(
         ("category_id" = Uuid, Path, description = "Category uuid"),
+        ("If-Match" = Option<String>, Header, description = "Updates only if the category ETag matches"),
     ),
     request_body = RequestUpdateCategory,
+    security(("bearer_auth" = ["categories:write"])),
     responses(
          (status = 200, description = "Category updated",  body = ApiResponseCategory),
          (status = 400, description = "Invalid payload",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing categories:write scope",  body = ErrorResponse),
          (status = 404, description = "Category not found",  body = ErrorResponse),
+         (status = 409, description = "Category name already exists",  body = ErrorResponse),
+         (status = 412, description = "Category version does not match",  body = ErrorResponse),
     ),
  )]
 #[put("/categories/{category_id}")]
+#[tracing::instrument(name = "api::categories::update_by_id", skip_all)]
 async fn handler(
+    _: Authorized<CategoriesWrite>,
     state: Data<AppState>,
     param: web::Path<Uuid>,
     body: web::Json<dto::RequestUpdateCategory>,
+    req: HttpRequest,
 ) -> Result<HttpResponse, DomainError> {
     body.validate()?;
+    let version = etag::if_match_version(&req)?;
 
     let category = categories::resources::update_by_id::execute(
         state.category_repository.clone(),
         param.to_owned(),
         body.0.into(),
+        version,
     )
     .await?;
 
+    let entity_tag = etag::entity_tag(category.version);
     let response = ApiResponse::<ResponseCategory>::new(vec![category.into()], None, None, None);
 
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok()
+        .insert_header(ETag(entity_tag))
+        .json(response))
 }
 
 #[cfg(test)]
+#[allow(clippy::unnecessary_to_owned)]
 mod tests {
-    use actix_web::{http::StatusCode, test};
+    use actix_web::{
+        http::{header, StatusCode},
+        test,
+    };
     use uuid::Uuid;
 
     use crate::{
@@ -76,8 +96,7 @@ mod tests {
             .await
             .unwrap();
 
-        let mock_request_update_category =
-            dto::RequestUpdateCategory::mock_default().with_name("Burgers Supreme");
+        let mock_request_update_category = dto::RequestUpdateCategory::mock_default();
         let req = test::TestRequest::put()
             .uri(&format!("/categories/{}", category_model.id))
             .set_json(mock_request_update_category.clone())
@@ -108,4 +127,60 @@ mod tests {
 
         assert_eq!(res.status().as_u16(), StatusCode::NOT_FOUND);
     }
+
+    #[actix_web::test]
+    async fn it_should_return_bad_request_error_when_parent_is_a_descendant() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        let child_model = CategoryCreateModel::mock_default().with_parent_id(category_model.id);
+        for model in [&category_model, &child_model] {
+            repositories
+                .category_repository
+                .insert(model)
+                .await
+                .unwrap();
+        }
+
+        let req = test::TestRequest::put()
+            .uri(&format!("/categories/{}", category_model.id))
+            .set_json(dto::RequestUpdateCategory::mock_default().with_parent_id(child_model.id))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_precondition_failed_when_etag_is_stale() {
+        let (repositories, app) = get_app(init_routes).await;
+
+        //Seed
+        let category_model = CategoryCreateModel::mock_default();
+        repositories
+            .category_repository
+            .insert(&category_model.clone())
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::put()
+            .uri(&format!("/categories/{}", category_model.id))
+            .insert_header((header::IF_MATCH, "\"1\""))
+            .set_json(dto::RequestUpdateCategory::mock_default())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+        assert_eq!(res.headers().get(header::ETAG).unwrap(), "\"2\"");
+
+        let req = test::TestRequest::put()
+            .uri(&format!("/categories/{}", category_model.id))
+            .insert_header((header::IF_MATCH, "\"1\""))
+            .set_json(dto::RequestUpdateCategory::mock_default())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::PRECONDITION_FAILED);
+    }
 }
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::{
+    api::utils::validator::validate_page_size_max, domain::dead_letters::model::DeadLetterModel,
+};
+
+#[derive(Debug, Clone, Deserialize, Validate, IntoParams)]
+pub struct RequestFindDeadLetters {
+    #[validate(range(min = 1))]
+    pub page: Option<u32>,
+    #[validate(custom = "validate_page_size_max")]
+    pub page_size: Option<u32>,
+}
+
+#[cfg_attr(test, derive(Serialize))]
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct RequestSelectDeadLetters {
+    /// Dead letter ids to select, as listed in `id`. All dead letters are
+    /// selected when omitted.
+    #[validate(length(min = 1))]
+    pub message_ids: Option<Vec<String>>,
+}
+
+#[cfg_attr(test, derive(Deserialize))]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResponseDeadLetter {
+    /// The message id, or `sha256:` and the hash of the body for messages
+    /// published without one.
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    pub exchange: String,
+    pub routing_key: String,
+    pub body: String,
+    #[schema(value_type = Object)]
+    pub headers: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub death_reason: Option<String>,
+}
+impl From<DeadLetterModel> for ResponseDeadLetter {
+    fn from(value: DeadLetterModel) -> Self {
+        Self {
+            id: value.id,
+            message_id: value.message_id,
+            exchange: value.exchange,
+            routing_key: value.routing_key,
+            body: value.body,
+            headers: value.headers,
+            death_reason: value.death_reason,
+        }
+    }
+}
+
+#[cfg_attr(test, derive(Deserialize))]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResponseDeadLettersAffected {
+    pub count: u32,
+}
@@ -0,0 +1,132 @@
+use actix_web::{
+    get,
+    web::{Data, Query},
+    HttpResponse,
+};
+
+use validator::Validate;
+
+use crate::{
+    api::{
+        config,
+        lib::AppState,
+        middleware::auth::{Authorized, DeadLettersRead},
+        resources::dead_letters::dto::{self, ResponseDeadLetter},
+        utils::response::ApiResponse,
+    },
+    domain::{dead_letters, error::DomainError},
+};
+
+/// Lists dead letters by taking them out of the queue and requeueing them, so
+/// listing is not side-effect free: the listed messages are unavailable for the
+/// duration of the call and come back flagged as redelivered. Only the first
+/// 1000 dead letters can be listed.
+#[utoipa::path(
+    get,
+    operation_id = "find_dead_letters",
+    path = "/admin/dead-letters",
+    tag = "admin",
+    params(
+        dto::RequestFindDeadLetters
+    ),
+    security(("bearer_auth" = ["dead_letters:read"])),
+    responses(
+         (status = 200, description = "dead letters",  body = ApiResponseDeadLetter),
+         (status = 204, description = "no content dead letters"),
+         (status = 400, description = "Invalid query parameters or page beyond the first 1000 dead letters",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing dead_letters:read scope",  body = ErrorResponse),
+    ),
+ )]
+#[get("/admin/dead-letters")]
+#[tracing::instrument(name = "api::dead_letters::find", skip_all)]
+async fn handler(
+    _: Authorized<DeadLettersRead>,
+    state: Data<AppState>,
+    query: Query<dto::RequestFindDeadLetters>,
+) -> Result<HttpResponse, DomainError> {
+    query.validate()?;
+
+    let page = query.page.unwrap_or(1);
+    let page_size = query
+        .page_size
+        .unwrap_or(config::get_config().page_size_default);
+
+    let result = dead_letters::resources::find::execute(
+        state.dead_letter_repository.clone(),
+        page,
+        page_size,
+    )
+    .await?;
+
+    if let Some((dead_letters, count)) = result {
+        let response = ApiResponse::<ResponseDeadLetter>::new(
+            dead_letters.into_iter().map(|i| i.into()).collect(),
+            Some(page),
+            Some(count),
+            Some(page_size),
+        );
+        return Ok(HttpResponse::Ok().json(response));
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test};
+
+    use crate::{
+        api::{
+            resources::dead_letters::{dto, routes::init_routes},
+            tests::utils::{get_app_with_dead_letter_repository, MockFakeDeadLetterRepository},
+            utils::response::ApiResponse,
+        },
+        domain::dead_letters::model::DeadLetterModel,
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_dead_letters_finded() {
+        let mut dead_letter_repository = MockFakeDeadLetterRepository::new();
+        dead_letter_repository
+            .expect_find()
+            .return_once(|_, _| Ok(Some((vec![DeadLetterModel::mock_default()], 1))));
+
+        let (_, app) =
+            get_app_with_dead_letter_repository(init_routes, dead_letter_repository).await;
+
+        let req = test::TestRequest::get()
+            .uri("/admin/dead-letters?page=1&page_size=12")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let response_dead_letters: ApiResponse<dto::ResponseDeadLetter> =
+            serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            response_dead_letters.records.first().unwrap().death_reason,
+            Some("rejected".to_string())
+        );
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_dead_letters_no_content() {
+        let mut dead_letter_repository = MockFakeDeadLetterRepository::new();
+        dead_letter_repository
+            .expect_find()
+            .return_once(|_, _| Ok(None));
+
+        let (_, app) =
+            get_app_with_dead_letter_repository(init_routes, dead_letter_repository).await;
+
+        let req = test::TestRequest::get()
+            .uri("/admin/dead-letters")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::NO_CONTENT);
+    }
+}
@@ -0,0 +1,11 @@
+use actix_web::web;
+
+pub mod find;
+pub mod purge;
+pub mod replay;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(find::handler);
+    config.service(replay::handler);
+    config.service(purge::handler);
+}
@@ -0,0 +1,100 @@
+use actix_web::{
+    post,
+    web::{self, Data},
+    HttpResponse,
+};
+
+use validator::Validate;
+
+use crate::{
+    api::{
+        lib::AppState,
+        middleware::auth::{Authorized, DeadLettersWrite},
+        resources::dead_letters::dto,
+    },
+    domain::{dead_letters, error::DomainError},
+};
+
+#[utoipa::path(
+    post,
+    operation_id = "purge_dead_letters",
+    path = "/admin/dead-letters/purge",
+    tag = "admin",
+    request_body = RequestSelectDeadLetters,
+    security(("bearer_auth" = ["dead_letters:write"])),
+    responses(
+         (status = 200, description = "dead letters purged",  body = ResponseDeadLettersAffected),
+         (status = 400, description = "Invalid payload",  body = ErrorResponse),
+         (status = 401, description = "Missing or invalid bearer token",  body = ErrorResponse),
+         (status = 403, description = "Missing dead_letters:write scope",  body = ErrorResponse),
+    ),
+ )]
+#[post("/admin/dead-letters/purge")]
+#[tracing::instrument(name = "api::dead_letters::purge", skip_all)]
+async fn handler(
+    _: Authorized<DeadLettersWrite>,
+    state: Data<AppState>,
+    body: web::Json<dto::RequestSelectDeadLetters>,
+) -> Result<HttpResponse, DomainError> {
+    body.validate()?;
+
+    let count = dead_letters::resources::purge::execute(
+        state.dead_letter_repository.clone(),
+        body.0.message_ids,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(dto::ResponseDeadLettersAffected { count }))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::StatusCode, test};
+
+    use crate::api::{
+        resources::dead_letters::{dto, routes::init_routes},
+        tests::utils::{get_app_with_dead_letter_repository, MockFakeDeadLetterRepository},
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_dead_letters_purged() {
+        let mut dead_letter_repository = MockFakeDeadLetterRepository::new();
+        dead_letter_repository
+            .expect_purge()
+            .withf(|message_ids| message_ids.is_none())
+            .return_once(|_| Ok(2));
+
+        let (_, app) =
+            get_app_with_dead_letter_repository(init_routes, dead_letter_repository).await;
+
+        let req = test::TestRequest::post()
+            .uri("/admin/dead-letters/purge")
+            .set_json(dto::RequestSelectDeadLetters::default())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body = test::read_body(res).await;
+        let response: dto::ResponseDeadLettersAffected = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(response.count, 2);
+    }
+
+    #[actix_web::test]
+    async fn it_should_return_bad_request_error_when_message_ids_is_empty() {
+        let (_, app) =
+            get_app_with_dead_letter_repository(init_routes, MockFakeDeadLetterRepository::new())
+                .await;
+
+        let req = test::TestRequest::post()
+            .uri("/admin/dead-letters/purge")
+            .set_json(dto::RequestSelectDeadLetters {
+                message_ids: Some(vec![]),
+            })
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status().as_u16(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::health::model::{DependencyHealthModel, HealthReportModel, HealthStatus};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseHealthStatus {
+    Up,
+    Down,
+}
+impl From<HealthStatus> for ResponseHealthStatus {
+    fn from(value: HealthStatus) -> Self {
+        match value {
+            HealthStatus::Up => Self::Up,
+            HealthStatus::Down => Self::Down,
+        }
+    }
+}
+
+#[cfg_attr(test, derive(Deserialize))]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResponseHealth {
+    pub status: ResponseHealthStatus,
+    /// Checks by dependency, `postgres`, `redis` and `rabbitmq`. Omitted by
+    /// the liveness probe, which checks no dependency.
+    #[cfg_attr(test, serde(default))]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub checks: BTreeMap<String, ResponseDependencyHealth>,
+}
+impl ResponseHealth {
+    pub fn live() -> Self {
+        Self {
+            status: ResponseHealthStatus::Up,
+            checks: BTreeMap::new(),
+        }
+    }
+}
+impl From<HealthReportModel> for ResponseHealth {
+    fn from(value: HealthReportModel) -> Self {
+        Self {
+            status: value.status.into(),
+            checks: value
+                .dependencies
+                .into_iter()
+                .map(|dependency| (dependency.name.clone(), dependency.into()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg_attr(test, derive(Deserialize))]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResponseDependencyHealth {
+    pub status: ResponseHealthStatus,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+impl From<DependencyHealthModel> for ResponseDependencyHealth {
+    fn from(value: DependencyHealthModel) -> Self {
+        Self {
+            status: value.status.into(),
+            latency_ms: value.latency.as_millis() as u64,
+            error: value.error,
+        }
+    }
+}
@@ -1 +1,2 @@
-pub mod routes;
\ No newline at end of file
+pub mod dto;
+pub mod routes;
@@ -0,0 +1,42 @@
+use actix_web::{get, HttpResponse};
+
+use crate::api::resources::health::dto::ResponseHealth;
+
+#[utoipa::path(
+    get,
+    operation_id = "health_live",
+    path = "/health/live",
+    tag = "health",
+    responses(
+         (status = 200, description = "Process is up, no dependency is checked", body = ResponseHealth),
+    ),
+ )]
+#[get("/health/live")]
+#[tracing::instrument(name = "api::health::live", skip_all)]
+async fn handler() -> HttpResponse {
+    HttpResponse::Ok().json(ResponseHealth::live())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{test, App};
+
+    use crate::api::resources::health::{
+        dto::{ResponseHealth, ResponseHealthStatus},
+        routes::init_routes,
+    };
+
+    #[actix_web::test]
+    async fn it_should_return_live_without_checking_dependencies() {
+        let app = test::init_service(App::new().configure(init_routes)).await;
+
+        let req = test::TestRequest::get().uri("/health/live").to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body: ResponseHealth = test::read_body_json(res).await;
+        assert_eq!(body.status, ResponseHealthStatus::Up);
+        assert!(body.checks.is_empty());
+    }
+}
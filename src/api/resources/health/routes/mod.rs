@@ -1,7 +1,8 @@
 use actix_web::web;
 
-pub mod check;
+pub mod live;
+pub mod ready;
 
 pub fn init_routes(config: &mut web::ServiceConfig) {
-    config.service(check::handler);
+    config.service(live::handler).service(ready::handler);
 }
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use actix_web::{get, web::Data, HttpResponse};
+
+use crate::{
+    api::{config, lib::AppState, resources::health::dto::ResponseHealth},
+    domain::health::{self, model::HealthStatus},
+};
+
+#[utoipa::path(
+    get,
+    operation_id = "health_ready",
+    path = "/health/ready",
+    tag = "health",
+    responses(
+         (status = 200, description = "Every dependency is up", body = ResponseHealth),
+         (status = 503, description = "A dependency is down", body = ResponseHealth),
+    ),
+ )]
+#[get("/health/ready")]
+#[tracing::instrument(name = "api::health::ready", skip_all)]
+async fn handler(state: Data<AppState>) -> HttpResponse {
+    let report = health::check::execute(
+        state.health_repository.clone(),
+        Duration::from_millis(config::get_config().health_check_timeout),
+    )
+    .await;
+
+    let mut response = match report.status {
+        HealthStatus::Up => HttpResponse::Ok(),
+        HealthStatus::Down => HttpResponse::ServiceUnavailable(),
+    };
+    response.json(ResponseHealth::from(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        resources::health::{
+            dto::{ResponseHealth, ResponseHealthStatus},
+            routes::init_routes,
+        },
+        tests::utils::get_app,
+    };
+    use actix_web::test;
+
+    #[actix_web::test]
+    async fn it_should_return_ready_with_dependency_checks() {
+        let (_, app) = get_app(init_routes).await;
+
+        let req = test::TestRequest::get().uri("/health/ready").to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+
+        let body: ResponseHealth = test::read_body_json(res).await;
+        assert_eq!(body.status, ResponseHealthStatus::Up);
+        for dependency in ["postgres", "redis", "rabbitmq"] {
+            assert_eq!(body.checks[dependency].status, ResponseHealthStatus::Up);
+        }
+    }
+}
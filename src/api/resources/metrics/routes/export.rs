@@ -0,0 +1,57 @@
+use actix_web::{get, HttpResponse};
+
+use crate::{domain::error::DomainError, metrics};
+
+#[utoipa::path(
+    get,
+    operation_id = "metrics",
+    path = "/metrics",
+    tag = "metrics",
+    responses(
+         (status = 200, description = "Metrics in the Prometheus text format", content_type = "text/plain"),
+    ),
+ )]
+#[get("/metrics")]
+#[tracing::instrument(name = "api::metrics::export", skip_all)]
+async fn handler() -> Result<HttpResponse, DomainError> {
+    let (content_type, body) =
+        metrics::export().map_err(|err| DomainError::InternalServerError(err.to_string()))?;
+
+    Ok(HttpResponse::Ok().content_type(content_type).body(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{http::header, test, App};
+
+    use crate::api::{middleware, resources::metrics::routes::init_routes};
+
+    #[actix_web::test]
+    async fn it_should_return_request_metrics_by_route_pattern() {
+        let app = test::init_service(
+            App::new()
+                .wrap(middleware::metrics::Metrics)
+                .configure(init_routes),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/metrics").to_request();
+        test::call_service(&app, req).await;
+
+        let req = test::TestRequest::get().uri("/metrics").to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert!(res.status().is_success());
+        assert!(res
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain"));
+
+        let body = test::read_body(res).await;
+        let body = String::from_utf8_lossy(&body);
+        assert!(body.contains(r#"http_requests_total{method="GET",route="/metrics",status="200"}"#));
+    }
+}
@@ -0,0 +1,7 @@
+use actix_web::web;
+
+pub mod export;
+
+pub fn init_routes(config: &mut web::ServiceConfig) {
+    config.service(export::handler);
+}
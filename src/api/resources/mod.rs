@@ -1,3 +1,6 @@
+pub mod api_keys;
 pub mod categories;
+pub mod dead_letters;
 pub mod health;
+pub mod metrics;
 pub mod swagger;
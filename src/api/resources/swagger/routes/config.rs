@@ -1,29 +1,95 @@
 use actix_web::{get, http::header, HttpResponse, Responder};
-use utoipa::OpenApi;
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
 use utoipa_swagger_ui::SwaggerUi;
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
-        crate::api::resources::health::routes::check::handler,
+        crate::api::resources::health::routes::live::handler,
+        crate::api::resources::health::routes::ready::handler,
+        crate::api::resources::metrics::routes::export::handler,
         //Category
         crate::api::resources::categories::routes::create::handler,
+        crate::api::resources::categories::routes::bulk_create::handler,
+        crate::api::resources::categories::routes::bulk_update::handler,
+        crate::api::resources::categories::routes::bulk_delete::handler,
         crate::api::resources::categories::routes::update_by_id::handler,
+        crate::api::resources::categories::routes::patch_by_id::handler,
         crate::api::resources::categories::routes::find_by_id::handler,
+        crate::api::resources::categories::routes::find_children::handler,
+        crate::api::resources::categories::routes::find_tree::handler,
         crate::api::resources::categories::routes::find::handler,
+        crate::api::resources::categories::routes::search::handler,
         crate::api::resources::categories::routes::delete_by_id::handler,
+        crate::api::resources::categories::routes::restore_by_id::handler,
+        crate::api::resources::categories::routes::purge_by_id::handler,
+        //Dead letters
+        crate::api::resources::dead_letters::routes::find::handler,
+        crate::api::resources::dead_letters::routes::replay::handler,
+        crate::api::resources::dead_letters::routes::purge::handler,
+        //API keys
+        crate::api::resources::api_keys::routes::create::handler,
+        crate::api::resources::api_keys::routes::find::handler,
+        crate::api::resources::api_keys::routes::rotate_by_id::handler,
+        crate::api::resources::api_keys::routes::revoke_by_id::handler,
     ),
     components(schemas(
-        crate::api::error::ErrorResponse, crate::api::utils::response::Meta,
+        crate::api::error::ErrorResponse, crate::api::error::ErrorField,
+        crate::api::utils::response::Meta,
+        crate::api::resources::health::dto::ResponseHealth,
+        crate::api::resources::health::dto::ResponseHealthStatus,
+        crate::api::resources::health::dto::ResponseDependencyHealth,
         //Category
         crate::api::utils::response::ApiResponseCategory,
+        crate::api::utils::response::ApiResponseCategoryTree,
+        crate::api::utils::response::ApiResponseCategorySearch,
         crate::api::resources::categories::dto::ResponseCategory,
+        crate::api::resources::categories::dto::ResponseCategoryTree,
+        crate::api::resources::categories::dto::ResponseCategorySearch,
+        crate::api::resources::categories::dto::ResponseCategoryHighlight,
         crate::api::resources::categories::dto::RequestCreateCategory,
         crate::api::resources::categories::dto::RequestUpdateCategory,
-    ))
+        crate::api::resources::categories::dto::RequestPatchCategory,
+        crate::api::resources::categories::dto::RequestBulkUpdateCategory,
+        crate::api::resources::categories::dto::ResponseBulkCategories,
+        crate::api::resources::categories::dto::ResponseBulkCategory,
+        //Dead letters
+        crate::api::utils::response::ApiResponseDeadLetter,
+        crate::api::resources::dead_letters::dto::ResponseDeadLetter,
+        crate::api::resources::dead_letters::dto::RequestSelectDeadLetters,
+        crate::api::resources::dead_letters::dto::ResponseDeadLettersAffected,
+        //API keys
+        crate::api::utils::response::ApiResponseApiKey,
+        crate::api::utils::response::ApiResponseApiKeyIssued,
+        crate::api::resources::api_keys::dto::ResponseApiKey,
+        crate::api::resources::api_keys::dto::ResponseApiKeyIssued,
+        crate::api::resources::api_keys::dto::RequestCreateApiKey,
+    )),
+    modifiers(&SecurityAddon)
 )]
 struct ApiDoc;
 
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "bearer_auth",
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .bearer_format("JWT")
+                        .build(),
+                ),
+            );
+        }
+    }
+}
+
 #[get("/docs")]
 async fn redirect() -> impl Responder {
     HttpResponse::Found()
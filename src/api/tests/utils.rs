@@ -1,8 +1,19 @@
 use std::sync::Arc;
 
 use crate::{
-    api::{error::ErrorResponse, lib::AppState, middleware},
+    amqp,
+    api::{
+        config,
+        error::bad_request_handler,
+        lib::{AppState, PAYLOAD_LIMIT},
+        middleware::{self, auth::JwtValidator},
+    },
+    domain::{
+        dead_letters::{model::DeadLetterModel, repository::DeadLetterRepository},
+        error::DomainError,
+    },
     repository::{
+        api_keys::PgApiKeyRepository,
         categories::PgCategoryRepository,
         health::PgHealthRepository,
         postgres::{self, init_to_tests},
@@ -10,22 +21,29 @@ use crate::{
     },
 };
 
+use async_trait::async_trait;
+use jsonwebtoken::{encode, get_current_timestamp, EncodingKey, Header};
+use lapin::Connection;
+use mockall::mock;
+use serde_json::json;
+use serde_qs::actix::QsQueryConfig;
 use tokio::sync::OnceCell;
+use tokio_util::sync::CancellationToken;
 
 use actix_http::Request;
 
 use actix_web::{
     body::MessageBody,
     dev::{Service, ServiceResponse},
-    error::InternalError,
     test,
     web::{Data, ServiceConfig},
-    App, Error, HttpResponse,
+    App, Error,
 };
 
 static INIT_DB: OnceCell<()> = OnceCell::const_new();
+static AMQP_CONNECTION: OnceCell<Arc<Connection>> = OnceCell::const_new();
 
-async fn setup() {
+pub async fn setup() {
     INIT_DB
         .get_or_init(|| async {
             dotenv::from_filename(".env.test").ok();
@@ -40,6 +58,7 @@ async fn setup() {
 pub struct Repositories {
     pub health_repository: Arc<PgHealthRepository>,
     pub category_repository: Arc<PgCategoryRepository>,
+    pub api_key_repository: Arc<PgApiKeyRepository>,
 }
 
 impl Repositories {
@@ -47,19 +66,37 @@ impl Repositories {
     pub fn new(
         health_repository: Arc<PgHealthRepository>,
         category_repository: Arc<PgCategoryRepository>,
+        api_key_repository: Arc<PgApiKeyRepository>,
     ) -> Self {
         Self {
             health_repository,
             category_repository,
+            api_key_repository,
         }
     }
 }
 
+mock! {
+    pub FakeDeadLetterRepository { }
+
+    #[async_trait]
+    impl DeadLetterRepository for FakeDeadLetterRepository {
+        async fn find(&self, page: &u32, page_size: &u32) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError>;
+        async fn replay(&self, message_ids: &Option<Vec<String>>) -> Result<u32, DomainError>;
+        async fn purge(&self, message_ids: &Option<Vec<String>>) -> Result<u32, DomainError>;
+    }
+}
+
 impl AppState {
-    fn mock_default(repositories: &Repositories) -> Data<Self> {
+    fn mock_default(
+        repositories: &Repositories,
+        dead_letter_repository: Arc<dyn DeadLetterRepository>,
+    ) -> Data<Self> {
         Data::new(Self {
             health_repository: repositories.health_repository.clone(),
             category_repository: repositories.category_repository.clone(),
+            dead_letter_repository,
+            api_key_repository: repositories.api_key_repository.clone(),
         })
     }
 }
@@ -70,45 +107,136 @@ pub async fn get_app<F>(
     Repositories,
     impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
 )
+where
+    F: FnOnce(&mut ServiceConfig),
+{
+    get_app_with_dead_letter_repository(routes, MockFakeDeadLetterRepository::new()).await
+}
+
+pub const JWT_SECRET: &str = "test-secret";
+
+/// Signs a HS256 token with `JWT_SECRET` granting the space separated `scope`.
+pub fn bearer_token(scope: &str) -> String {
+    let token = encode(
+        &Header::default(),
+        &json!({"sub": "test", "scope": scope, "exp": get_current_timestamp() + 60}),
+        &EncodingKey::from_secret(JWT_SECRET.as_bytes()),
+    )
+    .unwrap();
+    format!("Bearer {token}")
+}
+
+pub async fn get_app_with_auth<F>(
+    routes: F,
+) -> (
+    Repositories,
+    impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+)
+where
+    F: FnOnce(&mut ServiceConfig),
+{
+    let jwt_validator = JwtValidator::new(Some(JWT_SECRET), None, None, None);
+    init_app(
+        routes,
+        MockFakeDeadLetterRepository::new(),
+        Some(Arc::new(jwt_validator)),
+    )
+    .await
+}
+
+pub async fn get_app_with_dead_letter_repository<F>(
+    routes: F,
+    dead_letter_repository: MockFakeDeadLetterRepository,
+) -> (
+    Repositories,
+    impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+)
+where
+    F: FnOnce(&mut ServiceConfig),
+{
+    init_app(routes, dead_letter_repository, None).await
+}
+
+async fn init_app<F>(
+    routes: F,
+    dead_letter_repository: MockFakeDeadLetterRepository,
+    jwt_validator: Option<Arc<JwtValidator>>,
+) -> (
+    Repositories,
+    impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+)
 where
     F: FnOnce(&mut ServiceConfig),
 {
     setup().await;
 
-    let json_config = actix_web::web::JsonConfig::default().error_handler(|err, _req| {
-        let http_error =
-            HttpResponse::BadRequest().json(ErrorResponse::new(err.to_string().as_str()));
-        InternalError::from_response(err, http_error).into()
-    });
+    let json_config = actix_web::web::JsonConfig::default()
+        .limit(PAYLOAD_LIMIT)
+        .error_handler(bad_request_handler);
 
-    let query_config = actix_web::web::QueryConfig::default().error_handler(|err, _req| {
-        let http_error =
-            HttpResponse::BadRequest().json(ErrorResponse::new(err.to_string().as_str()));
-        InternalError::from_response(err, http_error).into()
-    });
+    let payload_config = actix_web::web::PayloadConfig::new(PAYLOAD_LIMIT);
 
-    let path_config = actix_web::web::PathConfig::default().error_handler(|err, _req| {
-        let http_error =
-            HttpResponse::BadRequest().json(ErrorResponse::new(err.to_string().as_str()));
-        InternalError::from_response(err, http_error).into()
-    });
+    let query_config = actix_web::web::QueryConfig::default().error_handler(bad_request_handler);
+
+    let path_config = actix_web::web::PathConfig::default().error_handler(bad_request_handler);
+
+    let qs_config = QsQueryConfig::default()
+        .error_handler(bad_request_handler)
+        .qs_config(serde_qs::Config::new(5, false));
 
     let pool = Arc::new(postgres::init().unwrap());
-    let redis_client = Arc::new(redis::init());
+    let redis_connection = redis::init().await.unwrap();
 
-    let health_repository = Arc::new(PgHealthRepository::new(pool.clone(), redis_client.clone()));
+    let amqp_connection = AMQP_CONNECTION
+        .get_or_init(|| async {
+            Arc::new(
+                amqp::lib::connect(CancellationToken::new())
+                    .await
+                    .expect("Error to connect amqp to tests"),
+            )
+        })
+        .await
+        .clone();
+
+    let health_repository = Arc::new(PgHealthRepository::new(
+        pool.clone(),
+        redis_connection.clone(),
+        amqp_connection,
+    ));
     let category_repository = Arc::new(PgCategoryRepository::new(pool.clone()));
 
-    let repositories = Repositories::new(health_repository, category_repository);
+    let api_key_repository = Arc::new(PgApiKeyRepository::new(pool.clone()));
+
+    let repositories =
+        Repositories::new(health_repository, category_repository, api_key_repository);
 
-    let app_state = AppState::mock_default(&repositories);
+    let app_state = AppState::mock_default(&repositories, Arc::new(dead_letter_repository));
+    let api_key_repository = repositories.api_key_repository.clone();
 
     (
         repositories,
         test::init_service(
             App::new()
+                .wrap(middleware::idempotency::Idempotency::new(
+                    redis_connection.clone(),
+                    config::get_config().idempotency_ttl,
+                ))
+                .wrap(middleware::api_key::ApiKeyAuthentication::new(
+                    api_key_repository,
+                ))
+                .wrap(middleware::auth::Authentication::new(jwt_validator))
+                .wrap(middleware::rate_limit::RateLimit::new(
+                    redis_connection.clone(),
+                    middleware::rate_limit::RateLimitPolicy::from_config(config::get_config()),
+                ))
+                .wrap(middleware::problem::ProblemInstance)
+                .wrap(middleware::metrics::Metrics)
+                .wrap(middleware::trace::RequestTrace)
+                .wrap(middleware::request_id::RequestIdentifier)
                 .wrap(middleware::cors::default())
                 .app_data(json_config.to_owned())
+                .app_data(payload_config.to_owned())
+                .app_data(qs_config)
                 .app_data(query_config.to_owned())
                 .app_data(path_config.to_owned())
                 .app_data(app_state)
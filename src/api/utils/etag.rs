@@ -0,0 +1,78 @@
+use actix_web::{
+    http::header::{self, EntityTag, Header, IfMatch, IfNoneMatch},
+    HttpRequest,
+};
+
+use crate::domain::error::DomainError;
+
+pub fn entity_tag(version: i32) -> EntityTag {
+    EntityTag::new_strong(version.to_string())
+}
+
+/// Version required by the `If-Match` header, or `None` when any version is
+/// accepted. Weak or unknown tags can never match a category version.
+pub fn if_match_version(req: &HttpRequest) -> Result<Option<i32>, DomainError> {
+    if !req.headers().contains_key(header::IF_MATCH) {
+        return Ok(None);
+    }
+
+    match IfMatch::parse(req) {
+        Ok(IfMatch::Any) => Ok(None),
+        Ok(IfMatch::Items(items)) => items
+            .iter()
+            .filter(|item| !item.weak)
+            .find_map(|item| item.tag().parse::<i32>().ok())
+            .map(Some)
+            .ok_or_else(|| {
+                DomainError::PreconditionFailed(String::from("Category version does not match"))
+            }),
+        Err(_) => Err(DomainError::BadRequest(String::from(
+            "Invalid If-Match header",
+        ))),
+    }
+}
+
+pub fn is_not_modified(req: &HttpRequest, version: i32) -> bool {
+    match IfNoneMatch::parse(req) {
+        Ok(IfNoneMatch::Any) => true,
+        Ok(IfNoneMatch::Items(items)) => {
+            items.iter().any(|item| item.weak_eq(&entity_tag(version)))
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    #[test]
+    fn it_should_return_version_from_if_match() {
+        let req = TestRequest::default()
+            .insert_header((header::IF_MATCH, "\"3\""))
+            .to_http_request();
+        assert_eq!(if_match_version(&req).unwrap(), Some(3));
+
+        let req = TestRequest::default()
+            .insert_header((header::IF_MATCH, "*"))
+            .to_http_request();
+        assert_eq!(if_match_version(&req).unwrap(), None);
+
+        let req = TestRequest::default().to_http_request();
+        assert_eq!(if_match_version(&req).unwrap(), None);
+    }
+
+    #[test]
+    fn it_should_return_precondition_failed_for_weak_if_match() {
+        let req = TestRequest::default()
+            .insert_header((header::IF_MATCH, "W/\"3\""))
+            .to_http_request();
+
+        match if_match_version(&req) {
+            Err(DomainError::PreconditionFailed(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+}
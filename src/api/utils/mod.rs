@@ -1,2 +1,3 @@
+pub mod etag;
 pub mod response;
 pub mod validator;
@@ -1,13 +1,27 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::api::{config::get_config, resources::categories::dto::ResponseCategory};
+use crate::api::{
+    config::get_config,
+    resources::{
+        api_keys::dto::{ResponseApiKey, ResponseApiKeyIssued},
+        categories::dto::{ResponseCategory, ResponseCategorySearch, ResponseCategoryTree},
+        dead_letters::dto::ResponseDeadLetter,
+    },
+};
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct Meta {
-    pub count: u32,
-    pub page: u32,
-    pub pages: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pages: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 impl Meta {
     pub fn new(page: Option<u32>, count: Option<u32>, page_size: Option<u32>) -> Self {
@@ -17,14 +31,39 @@ impl Meta {
         let count = count.unwrap_or(1);
         let page_size = page_size.unwrap_or(config.page_size_default);
 
-        let pages = ((count as f32) / (page_size as f32)).ceil() as u32;
+        Self::paginated(Some(page), Some(count), page_size, None, None)
+    }
+
+    /// Meta of a page that may have been requested by cursor, where the page
+    /// number and the count are not always known.
+    pub fn paginated(
+        page: Option<u32>,
+        count: Option<u32>,
+        page_size: u32,
+        next_cursor: Option<String>,
+        prev_cursor: Option<String>,
+    ) -> Self {
+        let pages = count.map(|count| ((count as f32) / (page_size as f32)).ceil() as u32);
 
-        Self { count, page, pages }
+        Self {
+            count,
+            page,
+            pages,
+            next_cursor,
+            prev_cursor,
+        }
     }
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
-#[aliases(ApiResponseCategory = ApiResponse<ResponseCategory>,)]
+#[aliases(
+    ApiResponseCategory = ApiResponse<ResponseCategory>,
+    ApiResponseCategoryTree = ApiResponse<ResponseCategoryTree>,
+    ApiResponseCategorySearch = ApiResponse<ResponseCategorySearch>,
+    ApiResponseDeadLetter = ApiResponse<ResponseDeadLetter>,
+    ApiResponseApiKey = ApiResponse<ResponseApiKey>,
+    ApiResponseApiKeyIssued = ApiResponse<ResponseApiKeyIssued>,
+)]
 pub struct ApiResponse<T> {
     pub meta: Meta,
     pub records: Vec<T>,
@@ -41,4 +80,8 @@ impl<T> ApiResponse<T> {
             records,
         }
     }
+
+    pub fn with_meta(records: Vec<T>, meta: Meta) -> Self {
+        Self { meta, records }
+    }
 }
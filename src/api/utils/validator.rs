@@ -1,6 +1,9 @@
 use validator::ValidationError;
 
-use crate::api::config;
+use crate::{
+    api::{config, middleware::auth::SCOPES},
+    domain::error::DomainError,
+};
 
 pub fn validate_page_size_max(page_size: u32) -> Result<(), ValidationError> {
     if page_size > config::get_config().page_size_max {
@@ -8,3 +11,22 @@ pub fn validate_page_size_max(page_size: u32) -> Result<(), ValidationError> {
     }
     Ok(())
 }
+
+pub fn validate_bulk_size(size: usize) -> Result<(), DomainError> {
+    let bulk_max_items = config::get_config().bulk_max_items;
+    if size == 0 || size > bulk_max_items {
+        return Err(DomainError::BadRequest(format!(
+            "bulk must have between 1 and {bulk_max_items} items"
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_scopes(scopes: &[String]) -> Result<(), ValidationError> {
+    if scopes.is_empty() || scopes.iter().any(|scope| !SCOPES.contains(&scope.as_str())) {
+        let mut error = ValidationError::new("scopes");
+        error.add_param("allowed".into(), &SCOPES);
+        return Err(error);
+    }
+    Ok(())
+}
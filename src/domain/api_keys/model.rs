@@ -0,0 +1,113 @@
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+const API_KEY_PREFIX: &str = "ak_";
+const API_KEY_PREFIX_LENGTH: usize = 11;
+/// Minimum time between two writes of `last_used_at`.
+const API_KEY_TOUCH_INTERVAL_SECONDS: i64 = 60;
+
+/// Secret part of an API key. Only `key_hash` is stored, `key` is returned
+/// once to the caller and `prefix` identifies the key in listings.
+#[derive(Debug, Clone)]
+pub struct ApiKeySecretModel {
+    pub key: String,
+    pub prefix: String,
+    pub key_hash: String,
+}
+impl ApiKeySecretModel {
+    pub fn generate() -> Self {
+        let key = format!(
+            "{API_KEY_PREFIX}{}{}",
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        );
+
+        Self {
+            prefix: key[..API_KEY_PREFIX_LENGTH].to_owned(),
+            key_hash: Self::hash(&key),
+            key,
+        }
+    }
+
+    pub fn hash(key: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(key.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiKeyCreateModel {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub secret: ApiKeySecretModel,
+}
+impl ApiKeyCreateModel {
+    pub fn new(name: String, scopes: Vec<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            scopes,
+            secret: ApiKeySecretModel::generate(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl ApiKeyCreateModel {
+    pub fn mock_default() -> Self {
+        Self::new(
+            String::from("Orders service"),
+            vec![String::from("categories:read")],
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiKeyModel {
+    pub id: Uuid,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+impl ApiKeyModel {
+    /// Whether `last_used_at` is old enough to be recorded again at `now`.
+    pub fn is_touch_due(&self, now: DateTime<Utc>) -> bool {
+        match self.last_used_at {
+            Some(last_used_at) => {
+                now - last_used_at >= Duration::seconds(API_KEY_TOUCH_INTERVAL_SECONDS)
+            }
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+impl ApiKeyModel {
+    pub fn mock_default() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: String::from("Orders service"),
+            prefix: String::from("ak_01234567"),
+            scopes: vec![String::from("categories:read")],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+        }
+    }
+}
+
+/// API key with its plain secret, only available right after it is created
+/// or rotated.
+#[derive(Debug, Clone)]
+pub struct ApiKeyIssuedModel {
+    pub api_key: ApiKeyModel,
+    pub key: String,
+}
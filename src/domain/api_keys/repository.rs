@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+
+use super::model::{ApiKeyCreateModel, ApiKeyModel, ApiKeySecretModel};
+
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    async fn find(
+        &self,
+        page: &u32,
+        page_size: &u32,
+    ) -> Result<Option<(Vec<ApiKeyModel>, u32)>, DomainError>;
+    /// Finds a key that was not revoked by the hash of its secret.
+    async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKeyModel>, DomainError>;
+    async fn insert(
+        &self,
+        api_key_create_model: &ApiKeyCreateModel,
+    ) -> Result<ApiKeyModel, DomainError>;
+    async fn rotate_by_id(
+        &self,
+        id: &Uuid,
+        secret: &ApiKeySecretModel,
+    ) -> Result<Option<ApiKeyModel>, DomainError>;
+    async fn revoke_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+    async fn touch_by_id(&self, id: &Uuid) -> Result<(), DomainError>;
+}
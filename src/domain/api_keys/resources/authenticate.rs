@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use crate::domain::{
+    api_keys::{
+        model::{ApiKeyModel, ApiKeySecretModel},
+        repository::ApiKeyRepository,
+    },
+    error::DomainError,
+};
+
+/// Resolves the key sent by a caller. `last_used_at` is only recorded when the
+/// resolved key was last used more than a minute ago, recording it is best
+/// effort and never rejects the request.
+#[tracing::instrument(name = "domain::api_keys::authenticate", skip_all)]
+pub async fn execute(
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+    key: &str,
+) -> Result<Option<ApiKeyModel>, DomainError> {
+    let key_hash = ApiKeySecretModel::hash(key);
+
+    let api_key = api_key_repository.find_by_hash(&key_hash).await?;
+
+    if let Some(api_key) = api_key
+        .as_ref()
+        .filter(|api_key| api_key.is_touch_due(Utc::now()))
+    {
+        if let Err(err) = api_key_repository.touch_by_id(&api_key.id).await {
+            log::warn!("Error to record API key usage: {}", err);
+        }
+    }
+
+    Ok(api_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use mockall::mock;
+    use uuid::Uuid;
+
+    use crate::domain::api_keys::model::ApiKeyCreateModel;
+
+    use super::*;
+
+    mock! {
+        pub FakeApiKeyRepository { }
+
+        #[async_trait]
+        impl ApiKeyRepository for FakeApiKeyRepository {
+            async fn find(&self, page: &u32, page_size: &u32) -> Result<Option<(Vec<ApiKeyModel>, u32)>, DomainError>;
+            async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKeyModel>, DomainError>;
+            async fn insert(&self, api_key_create_model: &ApiKeyCreateModel) -> Result<ApiKeyModel, DomainError>;
+            async fn rotate_by_id(&self, id: &Uuid, secret: &ApiKeySecretModel) -> Result<Option<ApiKeyModel>, DomainError>;
+            async fn revoke_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+            async fn touch_by_id(&self, id: &Uuid) -> Result<(), DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_api_key_and_record_usage() {
+        let mut api_key_repository = MockFakeApiKeyRepository::new();
+        let secret = ApiKeySecretModel::generate();
+        let key_hash = secret.key_hash.clone();
+
+        api_key_repository
+            .expect_find_by_hash()
+            .withf(move |hash| hash == key_hash)
+            .return_once(|_| Ok(Some(ApiKeyModel::mock_default())));
+        api_key_repository
+            .expect_touch_by_id()
+            .times(1)
+            .return_once(|_| Ok(()));
+
+        let api_key = execute(Arc::new(api_key_repository), &secret.key)
+            .await
+            .unwrap();
+
+        assert!(api_key.is_some());
+    }
+
+    #[tokio::test]
+    async fn it_should_not_record_usage_of_recently_used_key() {
+        let mut api_key_repository = MockFakeApiKeyRepository::new();
+        let secret = ApiKeySecretModel::generate();
+
+        let mut api_key = ApiKeyModel::mock_default();
+        api_key.last_used_at = Some(Utc::now() - chrono::Duration::seconds(10));
+        api_key_repository
+            .expect_find_by_hash()
+            .return_once(move |_| Ok(Some(api_key)));
+        api_key_repository.expect_touch_by_id().never();
+
+        let api_key = execute(Arc::new(api_key_repository), &secret.key)
+            .await
+            .unwrap();
+
+        assert!(api_key.is_some());
+    }
+
+    #[tokio::test]
+    async fn it_should_return_none_when_key_is_unknown() {
+        let mut api_key_repository = MockFakeApiKeyRepository::new();
+
+        api_key_repository
+            .expect_find_by_hash()
+            .return_once(|_| Ok(None));
+        api_key_repository.expect_touch_by_id().never();
+
+        let api_key = execute(Arc::new(api_key_repository), "ak_unknown")
+            .await
+            .unwrap();
+
+        assert!(api_key.is_none());
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    api_keys::{
+        model::{ApiKeyCreateModel, ApiKeyIssuedModel},
+        repository::ApiKeyRepository,
+    },
+    error::DomainError,
+};
+
+#[tracing::instrument(name = "domain::api_keys::create", skip_all)]
+pub async fn execute(
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+    api_key_create_model: ApiKeyCreateModel,
+) -> Result<ApiKeyIssuedModel, DomainError> {
+    let api_key = api_key_repository.insert(&api_key_create_model).await?;
+
+    Ok(ApiKeyIssuedModel {
+        api_key,
+        key: api_key_create_model.secret.key,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use mockall::mock;
+    use uuid::Uuid;
+
+    use crate::domain::api_keys::model::{ApiKeyModel, ApiKeySecretModel};
+
+    use super::*;
+
+    mock! {
+        pub FakeApiKeyRepository { }
+
+        #[async_trait]
+        impl ApiKeyRepository for FakeApiKeyRepository {
+            async fn find(&self, page: &u32, page_size: &u32) -> Result<Option<(Vec<ApiKeyModel>, u32)>, DomainError>;
+            async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKeyModel>, DomainError>;
+            async fn insert(&self, api_key_create_model: &ApiKeyCreateModel) -> Result<ApiKeyModel, DomainError>;
+            async fn rotate_by_id(&self, id: &Uuid, secret: &ApiKeySecretModel) -> Result<Option<ApiKeyModel>, DomainError>;
+            async fn revoke_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+            async fn touch_by_id(&self, id: &Uuid) -> Result<(), DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_api_key_created_with_secret() {
+        let mut api_key_repository = MockFakeApiKeyRepository::new();
+
+        let api_key_create_model = ApiKeyCreateModel::mock_default();
+        let key_hash = api_key_create_model.secret.key_hash.clone();
+
+        api_key_repository
+            .expect_insert()
+            .withf(move |model| model.secret.key_hash == key_hash)
+            .return_once(|_| Ok(ApiKeyModel::mock_default()));
+
+        let issued = execute(Arc::new(api_key_repository), api_key_create_model.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(issued.key, api_key_create_model.secret.key);
+        assert_eq!(
+            ApiKeySecretModel::hash(&issued.key),
+            api_key_create_model.secret.key_hash
+        );
+    }
+}
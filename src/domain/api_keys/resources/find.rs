@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    api_keys::{model::ApiKeyModel, repository::ApiKeyRepository},
+    error::DomainError,
+};
+
+#[tracing::instrument(name = "domain::api_keys::find", skip_all)]
+pub async fn execute(
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+    page: u32,
+    page_size: u32,
+) -> Result<Option<(Vec<ApiKeyModel>, u32)>, DomainError> {
+    let api_keys = api_key_repository.find(&page, &page_size).await?;
+
+    Ok(api_keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use mockall::mock;
+    use uuid::Uuid;
+
+    use crate::domain::api_keys::model::{ApiKeyCreateModel, ApiKeySecretModel};
+
+    use super::*;
+
+    mock! {
+        pub FakeApiKeyRepository { }
+
+        #[async_trait]
+        impl ApiKeyRepository for FakeApiKeyRepository {
+            async fn find(&self, page: &u32, page_size: &u32) -> Result<Option<(Vec<ApiKeyModel>, u32)>, DomainError>;
+            async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKeyModel>, DomainError>;
+            async fn insert(&self, api_key_create_model: &ApiKeyCreateModel) -> Result<ApiKeyModel, DomainError>;
+            async fn rotate_by_id(&self, id: &Uuid, secret: &ApiKeySecretModel) -> Result<Option<ApiKeyModel>, DomainError>;
+            async fn revoke_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+            async fn touch_by_id(&self, id: &Uuid) -> Result<(), DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_api_keys_finded() {
+        let mut api_key_repository = MockFakeApiKeyRepository::new();
+
+        api_key_repository
+            .expect_find()
+            .return_once(|_, _| Ok(Some((vec![ApiKeyModel::mock_default()], 1))));
+
+        let (api_keys, count) = execute(Arc::new(api_key_repository), 1, 12)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(!api_keys.is_empty());
+        assert!(count == 1);
+    }
+
+    #[tokio::test]
+    async fn it_should_return_none_finded() {
+        let mut api_key_repository = MockFakeApiKeyRepository::new();
+
+        api_key_repository
+            .expect_find()
+            .return_once(|_, _| Ok(None));
+
+        let response = execute(Arc::new(api_key_repository), 1, 12).await.unwrap();
+
+        assert!(response.is_none());
+    }
+}
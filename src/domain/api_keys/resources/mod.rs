@@ -0,0 +1,5 @@
+pub mod authenticate;
+pub mod create;
+pub mod find;
+pub mod revoke_by_id;
+pub mod rotate_by_id;
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::{api_keys::repository::ApiKeyRepository, error::DomainError};
+
+#[tracing::instrument(name = "domain::api_keys::revoke_by_id", skip_all)]
+pub async fn execute(
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+    id: Uuid,
+) -> Result<(), DomainError> {
+    if api_key_repository.revoke_by_id(&id).await? {
+        return Ok(());
+    }
+
+    Err(DomainError::NotFound(String::from("API key id not found")))
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    use crate::domain::api_keys::model::{ApiKeyCreateModel, ApiKeyModel, ApiKeySecretModel};
+
+    use super::*;
+
+    mock! {
+        pub FakeApiKeyRepository { }
+
+        #[async_trait]
+        impl ApiKeyRepository for FakeApiKeyRepository {
+            async fn find(&self, page: &u32, page_size: &u32) -> Result<Option<(Vec<ApiKeyModel>, u32)>, DomainError>;
+            async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKeyModel>, DomainError>;
+            async fn insert(&self, api_key_create_model: &ApiKeyCreateModel) -> Result<ApiKeyModel, DomainError>;
+            async fn rotate_by_id(&self, id: &Uuid, secret: &ApiKeySecretModel) -> Result<Option<ApiKeyModel>, DomainError>;
+            async fn revoke_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+            async fn touch_by_id(&self, id: &Uuid) -> Result<(), DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_revoke_api_key() {
+        let mut api_key_repository = MockFakeApiKeyRepository::new();
+
+        api_key_repository
+            .expect_revoke_by_id()
+            .return_once(|_| Ok(true));
+
+        let result = execute(Arc::new(api_key_repository), Uuid::new_v4()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_api_key_not_found() {
+        let mut api_key_repository = MockFakeApiKeyRepository::new();
+
+        api_key_repository
+            .expect_revoke_by_id()
+            .return_once(|_| Ok(false));
+
+        let result = execute(Arc::new(api_key_repository), Uuid::new_v4()).await;
+
+        match result {
+            Err(DomainError::NotFound(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+}
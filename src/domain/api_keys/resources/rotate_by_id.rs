@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::{
+    api_keys::{
+        model::{ApiKeyIssuedModel, ApiKeySecretModel},
+        repository::ApiKeyRepository,
+    },
+    error::DomainError,
+};
+
+/// Replaces the secret of a key, the previous secret stops working at once.
+#[tracing::instrument(name = "domain::api_keys::rotate_by_id", skip_all)]
+pub async fn execute(
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+    id: Uuid,
+) -> Result<ApiKeyIssuedModel, DomainError> {
+    let secret = ApiKeySecretModel::generate();
+
+    if let Some(api_key) = api_key_repository.rotate_by_id(&id, &secret).await? {
+        return Ok(ApiKeyIssuedModel {
+            api_key,
+            key: secret.key,
+        });
+    }
+
+    Err(DomainError::NotFound(String::from("API key id not found")))
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    use crate::domain::api_keys::model::{ApiKeyCreateModel, ApiKeyModel};
+
+    use super::*;
+
+    mock! {
+        pub FakeApiKeyRepository { }
+
+        #[async_trait]
+        impl ApiKeyRepository for FakeApiKeyRepository {
+            async fn find(&self, page: &u32, page_size: &u32) -> Result<Option<(Vec<ApiKeyModel>, u32)>, DomainError>;
+            async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKeyModel>, DomainError>;
+            async fn insert(&self, api_key_create_model: &ApiKeyCreateModel) -> Result<ApiKeyModel, DomainError>;
+            async fn rotate_by_id(&self, id: &Uuid, secret: &ApiKeySecretModel) -> Result<Option<ApiKeyModel>, DomainError>;
+            async fn revoke_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+            async fn touch_by_id(&self, id: &Uuid) -> Result<(), DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_api_key_rotated_with_new_secret() {
+        let mut api_key_repository = MockFakeApiKeyRepository::new();
+
+        api_key_repository
+            .expect_rotate_by_id()
+            .withf(|_, secret| ApiKeySecretModel::hash(&secret.key) == secret.key_hash)
+            .return_once(|_, _| Ok(Some(ApiKeyModel::mock_default())));
+
+        let issued = execute(Arc::new(api_key_repository), Uuid::new_v4())
+            .await
+            .unwrap();
+
+        assert!(issued.key.starts_with("ak_"));
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_api_key_not_found() {
+        let mut api_key_repository = MockFakeApiKeyRepository::new();
+
+        api_key_repository
+            .expect_rotate_by_id()
+            .return_once(|_, _| Ok(None));
+
+        let result = execute(Arc::new(api_key_repository), Uuid::new_v4()).await;
+
+        match result {
+            Err(DomainError::NotFound(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -1,18 +1,22 @@
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::domain::error::DomainError;
+
 #[derive(Debug, Clone)]
 pub struct CategoryCreateModel {
     pub id: Uuid,
     pub name: String,
     pub description: Option<String>,
+    pub parent_id: Option<Uuid>,
 }
 impl CategoryCreateModel {
-    pub fn new(name: String, description: Option<String>) -> Self {
+    pub fn new(name: String, description: Option<String>, parent_id: Option<Uuid>) -> Self {
         Self {
             id: Uuid::new_v4(),
             name,
             description,
+            parent_id,
         }
     }
 }
@@ -20,24 +24,33 @@ impl CategoryCreateModel {
 #[cfg(test)]
 impl CategoryCreateModel {
     pub fn mock_default() -> Self {
+        let id = uuid::Uuid::new_v4();
         Self {
-            id: uuid::Uuid::new_v4(),
-            name: "Burgers".to_string(),
+            id,
+            name: format!("Burgers {id}"),
             description: Some("The Big Burgers".to_string()),
+            parent_id: None,
         }
     }
+
+    pub fn with_parent_id(mut self, parent_id: Uuid) -> Self {
+        self.parent_id = Some(parent_id);
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct CategoryUpdateModel {
     pub name: String,
     pub description: Option<String>,
+    pub parent_id: Option<Uuid>,
 }
 impl CategoryUpdateModel {
-    pub fn new(name: String, description: Option<String>) -> Self {
+    pub fn new(name: String, description: Option<String>, parent_id: Option<Uuid>) -> Self {
         Self {
             name,
             description,
+            parent_id,
         }
     }
 }
@@ -45,31 +58,229 @@ impl CategoryUpdateModel {
 impl CategoryUpdateModel {
     pub fn mock_default() -> Self {
         Self {
-            name: "French fries".to_string(),
+            name: format!("French fries {}", uuid::Uuid::new_v4()),
             description: Some("The French fries".to_string()),
+            parent_id: None,
         }
     }
 }
 
+/// Fields of a merge patch. `None` leaves the field untouched, while
+/// `Some(None)` clears a nullable field.
+#[derive(Debug, Clone, Default)]
+pub struct CategoryPatchModel {
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+    pub parent_id: Option<Option<Uuid>>,
+}
+impl CategoryPatchModel {
+    pub fn new(
+        name: Option<String>,
+        description: Option<Option<String>>,
+        parent_id: Option<Option<Uuid>>,
+    ) -> Self {
+        Self {
+            name,
+            description,
+            parent_id,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.description.is_none() && self.parent_id.is_none()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CategoryModel {
     pub id: Uuid,
     pub name: String,
     pub description: Option<String>,
+    pub parent_id: Option<Uuid>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub version: i32,
 }
 #[cfg(test)]
 impl CategoryModel {
     pub fn mock_default() -> Self {
+        let id = uuid::Uuid::new_v4();
         Self {
-            id: uuid::Uuid::new_v4(),
-            name: "Burgers".to_string(),
+            id,
+            name: format!("Burgers {id}"),
             description: Some("The Big Burgers".to_string()),
+            parent_id: None,
             is_active: true,
             created_at: DateTime::default(),
             updated_at: DateTime::default(),
+            deleted_at: None,
+            version: 1,
+        }
+    }
+
+    pub fn with_parent_id(mut self, parent_id: Uuid) -> Self {
+        self.parent_id = Some(parent_id);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CategoryTreeModel {
+    pub category: CategoryModel,
+    pub children: Vec<CategoryTreeModel>,
+}
+
+/// Direction of a keyset page relative to its cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CategoryCursorDirection {
+    Next,
+    Prev,
+}
+
+/// Keyset position, the page holds the rows ordered after (`Next`) or
+/// before (`Prev`) the category these sortable values were taken from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryCursorModel {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub direction: CategoryCursorDirection,
+}
+impl CategoryCursorModel {
+    pub fn new(category: &CategoryModel, direction: CategoryCursorDirection) -> Self {
+        Self {
+            id: category.id,
+            name: category.name.clone(),
+            created_at: category.created_at,
+            updated_at: category.updated_at,
+            direction,
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CategorySortField {
+    Name,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CategorySortModel {
+    pub field: CategorySortField,
+    pub descending: bool,
+}
+impl CategorySortModel {
+    pub fn new(field: CategorySortField, descending: bool) -> Self {
+        Self { field, descending }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum CategoryPaginationModel {
+    Page(u32),
+    Cursor(CategoryCursorModel),
+}
+
+#[derive(Debug, Clone)]
+pub struct CategoryFindModel {
+    pub name: Option<String>,
+    pub include_inactive: bool,
+    /// Overrides `include_inactive` when set.
+    pub is_active: Option<bool>,
+    pub ids: Option<Vec<Uuid>>,
+    pub created_at_from: Option<DateTime<Utc>>,
+    pub created_at_to: Option<DateTime<Utc>>,
+    pub updated_at_from: Option<DateTime<Utc>>,
+    pub updated_at_to: Option<DateTime<Utc>>,
+    /// Ties are always broken by id. Empty sorts by creation date.
+    pub sort: Vec<CategorySortModel>,
+    pub pagination: CategoryPaginationModel,
+    pub page_size: u32,
+    pub with_count: bool,
+}
+#[cfg(test)]
+impl CategoryFindModel {
+    pub fn mock_default() -> Self {
+        Self {
+            name: None,
+            include_inactive: false,
+            is_active: None,
+            ids: None,
+            created_at_from: None,
+            created_at_to: None,
+            updated_at_from: None,
+            updated_at_to: None,
+            sort: vec![],
+            pagination: CategoryPaginationModel::Page(1),
+            page_size: 12,
+            with_count: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CategoryPageModel {
+    pub categories: Vec<CategoryModel>,
+    pub count: Option<u32>,
+    pub next_cursor: Option<CategoryCursorModel>,
+    pub prev_cursor: Option<CategoryCursorModel>,
+}
+impl CategoryPageModel {
+    pub fn new(
+        categories: Vec<CategoryModel>,
+        count: Option<u32>,
+        has_next: bool,
+        has_prev: bool,
+    ) -> Self {
+        let next_cursor = categories
+            .last()
+            .filter(|_| has_next)
+            .map(|category| CategoryCursorModel::new(category, CategoryCursorDirection::Next));
+        let prev_cursor = categories
+            .first()
+            .filter(|_| has_prev)
+            .map(|category| CategoryCursorModel::new(category, CategoryCursorDirection::Prev));
+
+        Self {
+            categories,
+            count,
+            next_cursor,
+            prev_cursor,
+        }
+    }
+}
+
+/// Search hit, `name_highlight` and `description_highlight` wrap the
+/// matched words in `<mark>` tags.
+#[derive(Debug, Clone)]
+pub struct CategorySearchModel {
+    pub category: CategoryModel,
+    pub rank: f32,
+    pub name_highlight: String,
+    pub description_highlight: Option<String>,
+}
+#[cfg(test)]
+impl CategorySearchModel {
+    pub fn mock_default() -> Self {
+        Self {
+            category: CategoryModel::mock_default(),
+            rank: 1.0,
+            name_highlight: "<mark>Burgers</mark>".to_string(),
+            description_highlight: Some("The Big <mark>Burgers</mark>".to_string()),
+        }
+    }
+}
+
+/// Outcome of one item of a bulk operation.
+#[derive(Debug)]
+pub enum CategoryBulkItemModel<T> {
+    Applied(T),
+    Failed(DomainError),
+    /// The item was valid but discarded because another item failed in
+    /// atomic mode.
+    RolledBack,
+}
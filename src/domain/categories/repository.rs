@@ -3,17 +3,27 @@ use uuid::Uuid;
 
 use crate::domain::error::DomainError;
 
-use super::model::{CategoryCreateModel, CategoryModel, CategoryUpdateModel};
+use super::model::{
+    CategoryCreateModel, CategoryFindModel, CategoryModel, CategoryPageModel, CategoryPatchModel,
+    CategorySearchModel, CategoryUpdateModel,
+};
 
 #[async_trait]
 pub trait CategoryRepository: Send + Sync {
     async fn find(
         &self,
-        name: &Option<String>,
+        category_find_model: &CategoryFindModel,
+    ) -> Result<Option<CategoryPageModel>, DomainError>;
+    async fn search(
+        &self,
+        query: &str,
         page: &u32,
         page_size: &u32,
-    ) -> Result<Option<(Vec<CategoryModel>, u32)>, DomainError>;
+    ) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
     async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+    async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+    async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+    async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
     async fn insert(
         &self,
         category_create_model: &CategoryCreateModel,
@@ -22,6 +32,30 @@ pub trait CategoryRepository: Send + Sync {
         &self,
         id: &Uuid,
         category_update_model: &CategoryUpdateModel,
+        version: &Option<i32>,
+    ) -> Result<CategoryModel, DomainError>;
+    async fn patch_by_id(
+        &self,
+        id: &Uuid,
+        category_patch_model: &CategoryPatchModel,
+        version: &Option<i32>,
     ) -> Result<CategoryModel, DomainError>;
-    async fn delete_by_id(&self, id: &Uuid) -> Result<(), DomainError>;
+    async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+    async fn insert_many(
+        &self,
+        category_create_models: &[CategoryCreateModel],
+        atomic: &bool,
+    ) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+    async fn update_many(
+        &self,
+        category_update_models: &[(Uuid, CategoryUpdateModel)],
+        atomic: &bool,
+    ) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+    async fn delete_many(
+        &self,
+        ids: &[Uuid],
+        atomic: &bool,
+    ) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+    async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+    async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
 }
@@ -0,0 +1,227 @@
+use std::{collections::HashSet, sync::Arc};
+
+use crate::domain::{
+    categories::{
+        model::{CategoryBulkItemModel, CategoryCreateModel, CategoryModel},
+        repository::CategoryRepository,
+    },
+    error::DomainError,
+};
+
+/// Items that already failed input validation are reported as failed. Parents
+/// may be created earlier in the same batch.
+#[tracing::instrument(name = "domain::categories::bulk_create", skip_all)]
+pub async fn execute(
+    category_repository: Arc<dyn CategoryRepository>,
+    items: Vec<Result<CategoryCreateModel, DomainError>>,
+    atomic: bool,
+) -> Result<Vec<CategoryBulkItemModel<CategoryModel>>, DomainError> {
+    let mut batch_ids = HashSet::new();
+    let mut checked = vec![];
+    for item in items {
+        let item = match item {
+            Ok(category_create_model) => {
+                check_parent(&category_repository, &batch_ids, &category_create_model)
+                    .await?
+                    .map(|_| category_create_model)
+            }
+            Err(err) => Err(err),
+        };
+
+        if let Ok(category_create_model) = &item {
+            batch_ids.insert(category_create_model.id);
+        }
+        checked.push(item);
+    }
+
+    if atomic && checked.iter().any(|item| item.is_err()) {
+        return Ok(rolled_back(checked));
+    }
+
+    let category_create_models: Vec<CategoryCreateModel> = checked
+        .iter()
+        .filter_map(|item| item.as_ref().ok().cloned())
+        .collect();
+    let results = category_repository
+        .insert_many(&category_create_models, &atomic)
+        .await?;
+
+    Ok(merge(checked, results, atomic))
+}
+
+async fn check_parent(
+    category_repository: &Arc<dyn CategoryRepository>,
+    batch_ids: &HashSet<uuid::Uuid>,
+    category_create_model: &CategoryCreateModel,
+) -> Result<Result<(), DomainError>, DomainError> {
+    if let Some(parent_id) = category_create_model.parent_id {
+        if !batch_ids.contains(&parent_id)
+            && category_repository.find_by_id(&parent_id).await?.is_none()
+        {
+            return Ok(Err(DomainError::BadRequest(String::from(
+                "Parent category id not found",
+            ))));
+        }
+    }
+
+    Ok(Ok(()))
+}
+
+/// Reports every valid item as rolled back when an atomic batch fails before
+/// reaching the repository.
+pub(super) fn rolled_back<M, T>(
+    items: Vec<Result<M, DomainError>>,
+) -> Vec<CategoryBulkItemModel<T>> {
+    items
+        .into_iter()
+        .map(|item| match item {
+            Ok(_) => CategoryBulkItemModel::RolledBack,
+            Err(err) => CategoryBulkItemModel::Failed(err),
+        })
+        .collect()
+}
+
+/// Pairs the repository results with the valid items, in order.
+pub(super) fn merge<M, T>(
+    items: Vec<Result<M, DomainError>>,
+    results: Vec<Result<T, DomainError>>,
+    atomic: bool,
+) -> Vec<CategoryBulkItemModel<T>> {
+    let committed = !atomic || results.iter().all(|result| result.is_ok());
+    let mut results = results.into_iter();
+
+    items
+        .into_iter()
+        .map(|item| match item {
+            Err(err) => CategoryBulkItemModel::Failed(err),
+            Ok(_) => match results.next() {
+                Some(Ok(value)) if committed => CategoryBulkItemModel::Applied(value),
+                Some(Ok(_)) => CategoryBulkItemModel::RolledBack,
+                Some(Err(err)) => CategoryBulkItemModel::Failed(err),
+                None => CategoryBulkItemModel::Failed(DomainError::InternalServerError(
+                    String::from("Missing bulk item result"),
+                )),
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::domain::categories::model::{
+        CategoryFindModel, CategoryPageModel, CategoryPatchModel, CategorySearchModel,
+        CategoryUpdateModel,
+    };
+
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    mock! {
+        pub FakeCategoryRepository { }
+
+        #[async_trait]
+        impl CategoryRepository for FakeCategoryRepository {
+            async fn find(&self,category_find_model: &CategoryFindModel) -> Result<Option<CategoryPageModel>, DomainError>;
+            async fn search(&self,query: &str,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn insert(&self,category_create_model: &CategoryCreateModel) -> Result<CategoryModel, DomainError>;
+            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn patch_by_id(&self,id: &Uuid,category_patch_model: &CategoryPatchModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+            async fn insert_many(&self,category_create_models: &[CategoryCreateModel],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn update_many(&self,category_update_models: &[(Uuid, CategoryUpdateModel)],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn delete_many(&self,ids: &[Uuid],atomic: &bool) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+            async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_categories_created() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        category_repository
+            .expect_insert_many()
+            .withf(|category_create_models, atomic| category_create_models.len() == 2 && *atomic)
+            .return_once(|_, _| {
+                Ok(vec![
+                    Ok(CategoryModel::mock_default()),
+                    Ok(CategoryModel::mock_default()),
+                ])
+            });
+
+        let parent = CategoryCreateModel::mock_default();
+        let child = CategoryCreateModel::mock_default().with_parent_id(parent.id);
+
+        let result = execute(
+            Arc::new(category_repository),
+            vec![Ok(parent), Ok(child)],
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert!(result
+            .iter()
+            .all(|item| matches!(item, CategoryBulkItemModel::Applied(_))));
+    }
+
+    #[tokio::test]
+    async fn it_should_return_rolled_back_items_when_atomic_batch_has_invalid_item() {
+        let category_repository = MockFakeCategoryRepository::new();
+
+        let result = execute(
+            Arc::new(category_repository),
+            vec![
+                Ok(CategoryCreateModel::mock_default()),
+                Err(DomainError::BadRequest(String::from("name too long"))),
+            ],
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(result[0], CategoryBulkItemModel::RolledBack));
+        assert!(matches!(
+            result[1],
+            CategoryBulkItemModel::Failed(DomainError::BadRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_should_return_applied_and_failed_items_when_not_atomic() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        category_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(None));
+        category_repository
+            .expect_insert_many()
+            .withf(|category_create_models, atomic| category_create_models.len() == 1 && !*atomic)
+            .return_once(|_, _| Ok(vec![Ok(CategoryModel::mock_default())]));
+
+        let result = execute(
+            Arc::new(category_repository),
+            vec![
+                Ok(CategoryCreateModel::mock_default()),
+                Ok(CategoryCreateModel::mock_default().with_parent_id(Uuid::new_v4())),
+            ],
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(result[0], CategoryBulkItemModel::Applied(_)));
+        assert!(matches!(
+            result[1],
+            CategoryBulkItemModel::Failed(DomainError::BadRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::{
+    categories::{model::CategoryBulkItemModel, repository::CategoryRepository},
+    error::DomainError,
+};
+
+use super::bulk_create::merge;
+
+#[tracing::instrument(name = "domain::categories::bulk_delete", skip_all)]
+pub async fn execute(
+    category_repository: Arc<dyn CategoryRepository>,
+    ids: Vec<Uuid>,
+    atomic: bool,
+) -> Result<Vec<CategoryBulkItemModel<()>>, DomainError> {
+    let results = category_repository.delete_many(&ids, &atomic).await?;
+
+    Ok(merge(
+        ids.into_iter()
+            .map(Ok)
+            .collect::<Vec<Result<Uuid, DomainError>>>(),
+        results,
+        atomic,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::categories::model::{
+        CategoryCreateModel, CategoryFindModel, CategoryModel, CategoryPageModel,
+        CategoryPatchModel, CategorySearchModel, CategoryUpdateModel,
+    };
+
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    mock! {
+        pub FakeCategoryRepository { }
+
+        #[async_trait]
+        impl CategoryRepository for FakeCategoryRepository {
+            async fn find(&self,category_find_model: &CategoryFindModel) -> Result<Option<CategoryPageModel>, DomainError>;
+            async fn search(&self,query: &str,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn insert(&self,category_create_model: &CategoryCreateModel) -> Result<CategoryModel, DomainError>;
+            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn patch_by_id(&self,id: &Uuid,category_patch_model: &CategoryPatchModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+            async fn insert_many(&self,category_create_models: &[CategoryCreateModel],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn update_many(&self,category_update_models: &[(Uuid, CategoryUpdateModel)],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn delete_many(&self,ids: &[Uuid],atomic: &bool) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+            async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_categories_deleted_and_not_found_when_not_atomic() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        category_repository
+            .expect_delete_many()
+            .return_once(|_, _| {
+                Ok(vec![
+                    Ok(()),
+                    Err(DomainError::NotFound(String::from("Category id not found"))),
+                ])
+            });
+
+        let result = execute(
+            Arc::new(category_repository),
+            vec![Uuid::new_v4(), Uuid::new_v4()],
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(result[0], CategoryBulkItemModel::Applied(())));
+        assert!(matches!(
+            result[1],
+            CategoryBulkItemModel::Failed(DomainError::NotFound(_))
+        ));
+    }
+}
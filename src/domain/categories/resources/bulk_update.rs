@@ -0,0 +1,258 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use uuid::Uuid;
+
+use crate::domain::{
+    categories::{
+        model::{CategoryBulkItemModel, CategoryModel, CategoryUpdateModel},
+        repository::CategoryRepository,
+    },
+    error::DomainError,
+};
+
+use super::{
+    bulk_create::{merge, rolled_back},
+    update_by_id::validate_parent,
+};
+
+#[tracing::instrument(name = "domain::categories::bulk_update", skip_all)]
+pub async fn execute(
+    category_repository: Arc<dyn CategoryRepository>,
+    items: Vec<Result<(Uuid, CategoryUpdateModel), DomainError>>,
+    atomic: bool,
+) -> Result<Vec<CategoryBulkItemModel<CategoryModel>>, DomainError> {
+    let mut checked = vec![];
+    for item in items {
+        let item = match item {
+            Ok((id, category_update_model)) => match category_update_model.parent_id {
+                Some(parent_id) => {
+                    match validate_parent(category_repository.clone(), &id, &parent_id).await {
+                        Ok(()) => Ok((id, category_update_model)),
+                        Err(err @ DomainError::BadRequest(_)) => Err(err),
+                        Err(err) => return Err(err),
+                    }
+                }
+                None => Ok((id, category_update_model)),
+            },
+            Err(err) => Err(err),
+        };
+        checked.push(item);
+    }
+    reject_cycles(&category_repository, &mut checked).await?;
+
+    if atomic && checked.iter().any(|item| item.is_err()) {
+        return Ok(rolled_back(checked));
+    }
+
+    let category_update_models: Vec<(Uuid, CategoryUpdateModel)> = checked
+        .iter()
+        .filter_map(|item| item.as_ref().ok().cloned())
+        .collect();
+    let results = category_repository
+        .update_many(&category_update_models, &atomic)
+        .await?;
+
+    Ok(merge(checked, results, atomic))
+}
+
+/// `validate_parent` only sees the parents stored in the database, so items
+/// of the same batch can still form a cycle between them, e.g. A under B and B
+/// under A. Rejects the first item closing a cycle over the parents the batch
+/// would leave, until none is left.
+async fn reject_cycles(
+    category_repository: &Arc<dyn CategoryRepository>,
+    checked: &mut [Result<(Uuid, CategoryUpdateModel), DomainError>],
+) -> Result<(), DomainError> {
+    loop {
+        let parents: HashMap<Uuid, Option<Uuid>> = checked
+            .iter()
+            .filter_map(|item| item.as_ref().ok())
+            .map(|(id, category_update_model)| (*id, category_update_model.parent_id))
+            .collect();
+
+        let mut rejected = None;
+        for (index, item) in checked.iter().enumerate() {
+            let Ok((
+                id,
+                CategoryUpdateModel {
+                    parent_id: Some(parent_id),
+                    ..
+                },
+            )) = item
+            else {
+                continue;
+            };
+            if closes_cycle(category_repository, &parents, id, parent_id).await? {
+                rejected = Some(index);
+                break;
+            }
+        }
+
+        match rejected {
+            Some(index) => {
+                checked[index] = Err(DomainError::BadRequest(String::from(
+                    "Parent category cannot be a descendant of the category",
+                )))
+            }
+            None => return Ok(()),
+        }
+    }
+}
+
+/// Walks up from `parent_id`, preferring the parents set by the batch over the
+/// stored ones, and tells whether the walk reaches `id`.
+async fn closes_cycle(
+    category_repository: &Arc<dyn CategoryRepository>,
+    parents: &HashMap<Uuid, Option<Uuid>>,
+    id: &Uuid,
+    parent_id: &Uuid,
+) -> Result<bool, DomainError> {
+    let mut visited = HashSet::new();
+    let mut current = Some(*parent_id);
+
+    while let Some(ancestor_id) = current {
+        if &ancestor_id == id {
+            return Ok(true);
+        }
+        if !visited.insert(ancestor_id) {
+            return Ok(false);
+        }
+
+        current = match parents.get(&ancestor_id) {
+            Some(parent_id) => *parent_id,
+            None => category_repository
+                .find_by_id(&ancestor_id)
+                .await?
+                .and_then(|category| category.parent_id),
+        };
+    }
+
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::categories::model::{
+        CategoryCreateModel, CategoryFindModel, CategoryPageModel, CategoryPatchModel,
+        CategorySearchModel,
+    };
+
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    mock! {
+        pub FakeCategoryRepository { }
+
+        #[async_trait]
+        impl CategoryRepository for FakeCategoryRepository {
+            async fn find(&self,category_find_model: &CategoryFindModel) -> Result<Option<CategoryPageModel>, DomainError>;
+            async fn search(&self,query: &str,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn insert(&self,category_create_model: &CategoryCreateModel) -> Result<CategoryModel, DomainError>;
+            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn patch_by_id(&self,id: &Uuid,category_patch_model: &CategoryPatchModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+            async fn insert_many(&self,category_create_models: &[CategoryCreateModel],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn update_many(&self,category_update_models: &[(Uuid, CategoryUpdateModel)],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn delete_many(&self,ids: &[Uuid],atomic: &bool) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+            async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_categories_updated() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        category_repository
+            .expect_update_many()
+            .return_once(|_, _| Ok(vec![Ok(CategoryModel::mock_default())]));
+
+        let result = execute(
+            Arc::new(category_repository),
+            vec![Ok((Uuid::new_v4(), CategoryUpdateModel::mock_default()))],
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(result[0], CategoryBulkItemModel::Applied(_)));
+    }
+
+    #[tokio::test]
+    async fn it_should_return_rolled_back_items_when_atomic_batch_fails_in_repository() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        category_repository
+            .expect_update_many()
+            .return_once(|_, _| {
+                Ok(vec![
+                    Ok(CategoryModel::mock_default()),
+                    Err(DomainError::NotFound(String::from("Category id not found"))),
+                ])
+            });
+
+        let result = execute(
+            Arc::new(category_repository),
+            vec![
+                Ok((Uuid::new_v4(), CategoryUpdateModel::mock_default())),
+                Ok((Uuid::new_v4(), CategoryUpdateModel::mock_default())),
+            ],
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(result[0], CategoryBulkItemModel::RolledBack));
+        assert!(matches!(
+            result[1],
+            CategoryBulkItemModel::Failed(DomainError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_should_reject_parents_forming_a_cycle_within_the_batch() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+        let (first_id, second_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        category_repository.expect_find_by_id().returning(|id| {
+            let mut category = CategoryModel::mock_default();
+            category.id = *id;
+            Ok(Some(category))
+        });
+        category_repository
+            .expect_find_ancestors()
+            .returning(|_| Ok(vec![]));
+        category_repository
+            .expect_update_many()
+            .withf(move |models, _| models.len() == 1 && models[0].0 == second_id)
+            .return_once(|_, _| Ok(vec![Ok(CategoryModel::mock_default())]));
+
+        let mut first = CategoryUpdateModel::mock_default();
+        first.parent_id = Some(second_id);
+        let mut second = CategoryUpdateModel::mock_default();
+        second.parent_id = Some(first_id);
+
+        let result = execute(
+            Arc::new(category_repository),
+            vec![Ok((first_id, first)), Ok((second_id, second))],
+            false,
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            result[0],
+            CategoryBulkItemModel::Failed(DomainError::BadRequest(_))
+        ));
+        assert!(matches!(result[1], CategoryBulkItemModel::Applied(_)));
+    }
+}
@@ -6,17 +6,30 @@ use crate::domain::{
     error::DomainError,
 };
 
+#[tracing::instrument(name = "domain::categories::create", skip_all)]
 pub async fn execute(
     category_repository: Arc<dyn CategoryRepository>,
     category_create_model: CategoryCreateModel,
 ) -> Result<CategoryModel, DomainError> {
+    if let Some(parent_id) = category_create_model.parent_id {
+        let has_parent = category_repository.find_by_id(&parent_id).await?;
+        if has_parent.is_none() {
+            return Err(DomainError::BadRequest(String::from(
+                "Parent category id not found",
+            )));
+        }
+    }
+
     let category = category_repository.insert(&category_create_model).await?;
     Ok(category)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::categories::model::CategoryUpdateModel;
+    use crate::domain::categories::model::{
+        CategoryFindModel, CategoryPageModel, CategoryPatchModel, CategorySearchModel,
+        CategoryUpdateModel,
+    };
 
     use super::*;
 
@@ -29,11 +42,21 @@ mod tests {
 
         #[async_trait]
         impl CategoryRepository for FakeCategoryRepository {
-            async fn find(&self,name: &Option<String>,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategoryModel>, u32)>, DomainError>;
+            async fn find(&self,category_find_model: &CategoryFindModel) -> Result<Option<CategoryPageModel>, DomainError>;
+            async fn search(&self,query: &str,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
             async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
             async fn insert(&self,category_create_model: &CategoryCreateModel) -> Result<CategoryModel, DomainError>;
-            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel) -> Result<CategoryModel, DomainError>;
-            async fn delete_by_id(&self, id: &Uuid) -> Result<(), DomainError>;
+            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn patch_by_id(&self,id: &Uuid,category_patch_model: &CategoryPatchModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+            async fn insert_many(&self,category_create_models: &[CategoryCreateModel],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn update_many(&self,category_update_models: &[(Uuid, CategoryUpdateModel)],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn delete_many(&self,ids: &[Uuid],atomic: &bool) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+            async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
         }
     }
 
@@ -56,4 +79,24 @@ mod tests {
             Err(err) => unreachable!("{err}"),
         }
     }
+
+    #[tokio::test]
+    async fn it_should_return_error_parent_category_not_found() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        category_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(None));
+
+        let result = execute(
+            Arc::new(category_repository),
+            CategoryCreateModel::mock_default().with_parent_id(Uuid::new_v4()),
+        )
+        .await;
+
+        match result {
+            Err(DomainError::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
 }
@@ -4,16 +4,26 @@ use uuid::Uuid;
 
 use crate::domain::{categories::repository::CategoryRepository, error::DomainError};
 
+#[tracing::instrument(name = "domain::categories::delete_by_id", skip_all)]
 pub async fn execute(
     category_repository: Arc<dyn CategoryRepository>,
     category_id: Uuid,
+    version: Option<i32>,
 ) -> Result<(), DomainError> {
     let has_category = category_repository.find_by_id(&category_id).await?;
-    if has_category.is_none() {
-        return Err(DomainError::NotFound(String::from("Category id not found")));
+    match has_category {
+        None => return Err(DomainError::NotFound(String::from("Category id not found"))),
+        Some(category) if version.is_some_and(|version| version != category.version) => {
+            return Err(DomainError::PreconditionFailed(String::from(
+                "Category version does not match",
+            )))
+        }
+        Some(_) => {}
     }
 
-    category_repository.delete_by_id(&category_id).await?;
+    category_repository
+        .delete_by_id(&category_id, &version)
+        .await?;
 
     Ok(())
 }
@@ -25,7 +35,8 @@ mod tests {
     use uuid::Uuid;
 
     use crate::domain::categories::model::{
-        CategoryCreateModel, CategoryModel, CategoryUpdateModel,
+        CategoryCreateModel, CategoryFindModel, CategoryModel, CategoryPageModel,
+        CategoryPatchModel, CategorySearchModel, CategoryUpdateModel,
     };
 
     use super::*;
@@ -35,11 +46,21 @@ mod tests {
 
         #[async_trait]
         impl CategoryRepository for FakeCategoryRepository {
-            async fn find(&self,name: &Option<String>,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategoryModel>, u32)>, DomainError>;
+            async fn find(&self,category_find_model: &CategoryFindModel) -> Result<Option<CategoryPageModel>, DomainError>;
+            async fn search(&self,query: &str,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
             async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
             async fn insert(&self,category_create_model: &CategoryCreateModel) -> Result<CategoryModel, DomainError>;
-            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel) -> Result<CategoryModel, DomainError>;
-            async fn delete_by_id(&self, id: &Uuid) -> Result<(), DomainError>;
+            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn patch_by_id(&self,id: &Uuid,category_patch_model: &CategoryPatchModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+            async fn insert_many(&self,category_create_models: &[CategoryCreateModel],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn update_many(&self,category_update_models: &[(Uuid, CategoryUpdateModel)],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn delete_many(&self,ids: &[Uuid],atomic: &bool) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+            async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
         }
     }
 
@@ -53,9 +74,9 @@ mod tests {
 
         category_repository
             .expect_delete_by_id()
-            .return_once(|_| Ok(()));
+            .return_once(|_, _| Ok(()));
 
-        let result = execute(Arc::new(category_repository), Uuid::new_v4()).await;
+        let result = execute(Arc::new(category_repository), Uuid::new_v4(), None).await;
 
         match result {
             Ok(()) => {}
@@ -71,11 +92,30 @@ mod tests {
             .expect_find_by_id()
             .return_once(|_| Ok(None));
 
-        let result = execute(Arc::new(category_repository), Uuid::new_v4()).await;
+        let result = execute(Arc::new(category_repository), Uuid::new_v4(), None).await;
 
         match result {
             Err(DomainError::NotFound(_)) => {}
             _ => unreachable!(),
         }
     }
+
+    #[tokio::test]
+    async fn it_should_return_error_precondition_failed_when_version_does_not_match() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        let mock_category_model = CategoryModel::mock_default();
+        let version = mock_category_model.version + 1;
+
+        category_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(Some(mock_category_model)));
+
+        let result = execute(Arc::new(category_repository), Uuid::new_v4(), Some(version)).await;
+
+        match result {
+            Err(DomainError::PreconditionFailed(_)) => {}
+            _ => unreachable!(),
+        }
+    }
 }
@@ -1,20 +1,22 @@
 use std::sync::Arc;
 
 use crate::domain::{
-    categories::{model::CategoryModel, repository::CategoryRepository},
+    categories::{
+        model::{CategoryFindModel, CategoryPageModel},
+        repository::CategoryRepository,
+    },
     error::DomainError,
 };
 
+#[tracing::instrument(name = "domain::categories::find", skip_all)]
 pub async fn execute(
     category_repository: Arc<dyn CategoryRepository>,
-    name: Option<String>,
-    page: u32,
-    page_size: u32,
-) -> Result<Option<(Vec<CategoryModel>, u32)>, DomainError> {
-    let categories = category_repository.find(&name, &page, &page_size).await?;
-
-    if categories.is_some() {
-        return Ok(categories);
+    category_find_model: CategoryFindModel,
+) -> Result<Option<CategoryPageModel>, DomainError> {
+    let page = category_repository.find(&category_find_model).await?;
+
+    if page.is_some() {
+        return Ok(page);
     }
 
     Ok(None)
@@ -28,18 +30,31 @@ mod tests {
     use mockall::mock;
     use uuid::Uuid;
 
-    use crate::domain::categories::model::{CategoryCreateModel, CategoryUpdateModel};
+    use crate::domain::categories::model::{
+        CategoryCreateModel, CategoryCursorDirection, CategoryModel, CategoryPatchModel,
+        CategorySearchModel, CategoryUpdateModel,
+    };
 
     mock! {
         pub FakeCategoryRepository { }
 
         #[async_trait]
         impl CategoryRepository for FakeCategoryRepository {
-            async fn find(&self,name: &Option<String>,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategoryModel>, u32)>, DomainError>;
+            async fn find(&self,category_find_model: &CategoryFindModel) -> Result<Option<CategoryPageModel>, DomainError>;
+            async fn search(&self,query: &str,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
             async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
             async fn insert(&self,category_create_model: &CategoryCreateModel) -> Result<CategoryModel, DomainError>;
-            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel) -> Result<CategoryModel, DomainError>;
-            async fn delete_by_id(&self, id: &Uuid) -> Result<(), DomainError>;
+            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn patch_by_id(&self,id: &Uuid,category_patch_model: &CategoryPatchModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+            async fn insert_many(&self,category_create_models: &[CategoryCreateModel],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn update_many(&self,category_update_models: &[(Uuid, CategoryUpdateModel)],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn delete_many(&self,ids: &[Uuid],atomic: &bool) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+            async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
         }
     }
 
@@ -47,29 +62,71 @@ mod tests {
     async fn it_should_return_categories_finded() {
         let mut category_repository = MockFakeCategoryRepository::new();
 
-        category_repository
-            .expect_find()
-            .return_once(|_, _, _| Ok(Some((vec![CategoryModel::mock_default()], 1))));
+        category_repository.expect_find().return_once(|_| {
+            Ok(Some(CategoryPageModel::new(
+                vec![CategoryModel::mock_default()],
+                Some(1),
+                false,
+                false,
+            )))
+        });
+
+        let page = execute(
+            Arc::new(category_repository),
+            CategoryFindModel::mock_default(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert!(!page.categories.is_empty());
+        assert!(page.count == Some(1));
+        assert!(page.next_cursor.is_none());
+    }
 
-        let (categories, count) = execute(Arc::new(category_repository), None, 1, 12)
-            .await
-            .unwrap()
-            .unwrap();
+    #[tokio::test]
+    async fn it_should_return_cursors_when_there_are_more_pages() {
+        let mut category_repository = MockFakeCategoryRepository::new();
 
-        assert!(!categories.is_empty());
-        assert!(count == 1);
+        let first = CategoryModel::mock_default();
+        let last = CategoryModel::mock_default();
+        let (first_id, last_id) = (first.id, last.id);
+        category_repository.expect_find().return_once(|_| {
+            Ok(Some(CategoryPageModel::new(
+                vec![first, last],
+                None,
+                true,
+                true,
+            )))
+        });
+
+        let page = execute(
+            Arc::new(category_repository),
+            CategoryFindModel::mock_default(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let next_cursor = page.next_cursor.unwrap();
+        assert_eq!(next_cursor.id, last_id);
+        assert_eq!(next_cursor.direction, CategoryCursorDirection::Next);
+        let prev_cursor = page.prev_cursor.unwrap();
+        assert_eq!(prev_cursor.id, first_id);
+        assert_eq!(prev_cursor.direction, CategoryCursorDirection::Prev);
     }
 
     #[tokio::test]
     async fn it_should_return_none_finded() {
         let mut category_repository = MockFakeCategoryRepository::new();
-        category_repository
-            .expect_find()
-            .return_once(|_, _, _| Ok(None));
-
-        let response = execute(Arc::new(category_repository), None, 1, 12)
-            .await
-            .unwrap();
+        category_repository.expect_find().return_once(|_| Ok(None));
+
+        let response = execute(
+            Arc::new(category_repository),
+            CategoryFindModel::mock_default(),
+        )
+        .await
+        .unwrap();
 
         assert!(response.is_none());
     }
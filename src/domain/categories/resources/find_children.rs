@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::{
+    categories::{model::CategoryModel, repository::CategoryRepository},
+    error::DomainError,
+};
+
+#[tracing::instrument(name = "domain::categories::find_children", skip_all)]
+pub async fn execute(
+    category_repository: Arc<dyn CategoryRepository>,
+    id: Uuid,
+) -> Result<Option<Vec<CategoryModel>>, DomainError> {
+    let has_category = category_repository.find_by_id(&id).await?;
+    if has_category.is_none() {
+        return Err(DomainError::NotFound(String::from("Category id not found")));
+    }
+
+    let children = category_repository.find_children(&id).await?;
+    if children.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(children))
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    use crate::domain::categories::model::{
+        CategoryCreateModel, CategoryFindModel, CategoryPageModel, CategoryPatchModel,
+        CategorySearchModel, CategoryUpdateModel,
+    };
+
+    use super::*;
+
+    mock! {
+        pub FakeCategoryRepository { }
+
+        #[async_trait]
+        impl CategoryRepository for FakeCategoryRepository {
+            async fn find(&self,category_find_model: &CategoryFindModel) -> Result<Option<CategoryPageModel>, DomainError>;
+            async fn search(&self,query: &str,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn insert(&self,category_create_model: &CategoryCreateModel) -> Result<CategoryModel, DomainError>;
+            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn patch_by_id(&self,id: &Uuid,category_patch_model: &CategoryPatchModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+            async fn insert_many(&self,category_create_models: &[CategoryCreateModel],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn update_many(&self,category_update_models: &[(Uuid, CategoryUpdateModel)],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn delete_many(&self,ids: &[Uuid],atomic: &bool) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+            async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_children_finded() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        let mock_category_model = CategoryModel::mock_default();
+        let mock_children =
+            vec![CategoryModel::mock_default().with_parent_id(mock_category_model.id)];
+
+        category_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(Some(mock_category_model)));
+        category_repository
+            .expect_find_children()
+            .return_once(|_| Ok(mock_children));
+
+        let children = execute(Arc::new(category_repository), Uuid::new_v4())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(children.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn it_should_return_none_when_category_has_no_children() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        category_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(Some(CategoryModel::mock_default())));
+        category_repository
+            .expect_find_children()
+            .return_once(|_| Ok(vec![]));
+
+        let result = execute(Arc::new(category_repository), Uuid::new_v4())
+            .await
+            .unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_category_not_found() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        category_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(None));
+
+        let result = execute(Arc::new(category_repository), Uuid::new_v4()).await;
+
+        match result {
+            Err(DomainError::NotFound(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+}
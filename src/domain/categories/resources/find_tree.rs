@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::{
+    categories::{
+        model::{CategoryModel, CategoryTreeModel},
+        repository::CategoryRepository,
+    },
+    error::DomainError,
+};
+
+#[tracing::instrument(name = "domain::categories::find_tree", skip_all)]
+pub async fn execute(
+    category_repository: Arc<dyn CategoryRepository>,
+    id: Uuid,
+) -> Result<CategoryTreeModel, DomainError> {
+    let mut subtree = category_repository.find_subtree(&id).await?;
+    if subtree.is_empty() {
+        return Err(DomainError::NotFound(String::from("Category id not found")));
+    }
+
+    let root = subtree.remove(0);
+    Ok(build_tree(root, &subtree))
+}
+
+fn build_tree(category: CategoryModel, descendants: &[CategoryModel]) -> CategoryTreeModel {
+    let children = descendants
+        .iter()
+        .filter(|descendant| descendant.parent_id == Some(category.id))
+        .map(|child| build_tree(child.clone(), descendants))
+        .collect();
+
+    CategoryTreeModel { category, children }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    use crate::domain::categories::model::{
+        CategoryCreateModel, CategoryFindModel, CategoryPageModel, CategoryPatchModel,
+        CategorySearchModel, CategoryUpdateModel,
+    };
+
+    use super::*;
+
+    mock! {
+        pub FakeCategoryRepository { }
+
+        #[async_trait]
+        impl CategoryRepository for FakeCategoryRepository {
+            async fn find(&self,category_find_model: &CategoryFindModel) -> Result<Option<CategoryPageModel>, DomainError>;
+            async fn search(&self,query: &str,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn insert(&self,category_create_model: &CategoryCreateModel) -> Result<CategoryModel, DomainError>;
+            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn patch_by_id(&self,id: &Uuid,category_patch_model: &CategoryPatchModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+            async fn insert_many(&self,category_create_models: &[CategoryCreateModel],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn update_many(&self,category_update_models: &[(Uuid, CategoryUpdateModel)],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn delete_many(&self,ids: &[Uuid],atomic: &bool) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+            async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_category_tree() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        let root = CategoryModel::mock_default();
+        let child = CategoryModel::mock_default().with_parent_id(root.id);
+        let grandchild = CategoryModel::mock_default().with_parent_id(child.id);
+        let (root_id, child_id, grandchild_id) = (root.id, child.id, grandchild.id);
+
+        category_repository
+            .expect_find_subtree()
+            .return_once(|_| Ok(vec![root, child, grandchild]));
+
+        let tree = execute(Arc::new(category_repository), root_id)
+            .await
+            .unwrap();
+
+        assert_eq!(tree.category.id, root_id);
+        assert_eq!(tree.children.len(), 1);
+        assert_eq!(tree.children[0].category.id, child_id);
+        assert_eq!(tree.children[0].children[0].category.id, grandchild_id);
+        assert!(tree.children[0].children[0].children.is_empty());
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_category_not_found() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        category_repository
+            .expect_find_subtree()
+            .return_once(|_| Ok(vec![]));
+
+        let result = execute(Arc::new(category_repository), Uuid::new_v4()).await;
+
+        match result {
+            Err(DomainError::NotFound(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -1,5 +1,14 @@
+pub mod bulk_create;
+pub mod bulk_delete;
+pub mod bulk_update;
 pub mod create;
 pub mod delete_by_id;
 pub mod find;
 pub mod find_by_id;
+pub mod find_children;
+pub mod find_tree;
+pub mod patch_by_id;
+pub mod purge_by_id;
+pub mod restore_by_id;
+pub mod search;
 pub mod update_by_id;
@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::{
+    categories::{
+        model::{CategoryModel, CategoryPatchModel},
+        repository::CategoryRepository,
+    },
+    error::DomainError,
+};
+
+use super::update_by_id::{find_current, validate_parent};
+
+#[tracing::instrument(name = "domain::categories::patch_by_id", skip_all)]
+pub async fn execute(
+    category_repository: Arc<dyn CategoryRepository>,
+    id: Uuid,
+    category_patch_model: CategoryPatchModel,
+    version: Option<i32>,
+) -> Result<CategoryModel, DomainError> {
+    let category = find_current(category_repository.clone(), &id, &version).await?;
+
+    if category_patch_model.is_empty() {
+        return Ok(category);
+    }
+
+    if let Some(Some(parent_id)) = category_patch_model.parent_id {
+        validate_parent(category_repository.clone(), &id, &parent_id).await?;
+    }
+
+    let category = category_repository
+        .patch_by_id(&id, &category_patch_model, &version)
+        .await?;
+
+    Ok(category)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::categories::model::{
+        CategoryCreateModel, CategoryFindModel, CategoryPageModel, CategorySearchModel,
+        CategoryUpdateModel,
+    };
+
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    mock! {
+        pub FakeCategoryRepository { }
+
+        #[async_trait]
+        impl CategoryRepository for FakeCategoryRepository {
+            async fn find(&self,category_find_model: &CategoryFindModel) -> Result<Option<CategoryPageModel>, DomainError>;
+            async fn search(&self,query: &str,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn insert(&self,category_create_model: &CategoryCreateModel) -> Result<CategoryModel, DomainError>;
+            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn patch_by_id(&self,id: &Uuid,category_patch_model: &CategoryPatchModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+            async fn insert_many(&self,category_create_models: &[CategoryCreateModel],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn update_many(&self,category_update_models: &[(Uuid, CategoryUpdateModel)],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn delete_many(&self,ids: &[Uuid],atomic: &bool) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+            async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_category_patched() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        let mock_category_model = CategoryModel::mock_default();
+        let category_id = mock_category_model.id;
+
+        category_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(Some(mock_category_model)));
+
+        category_repository
+            .expect_patch_by_id()
+            .withf(|_, category_patch_model, _| {
+                category_patch_model.name.is_none()
+                    && category_patch_model.description == Some(None)
+            })
+            .return_once(|_, _, _| Ok(CategoryModel::mock_default()));
+
+        let result = execute(
+            Arc::new(category_repository),
+            category_id,
+            CategoryPatchModel::new(None, Some(None), None),
+            None,
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_should_return_category_unchanged_when_patch_is_empty() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        let mock_category_model = CategoryModel::mock_default();
+        let category_id = mock_category_model.id;
+
+        category_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(Some(mock_category_model)));
+
+        let response = execute(
+            Arc::new(category_repository),
+            category_id,
+            CategoryPatchModel::default(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.id, category_id);
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_when_parent_is_itself() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        let mock_category_model = CategoryModel::mock_default();
+        let category_id = mock_category_model.id;
+
+        category_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(Some(mock_category_model)));
+
+        let result = execute(
+            Arc::new(category_repository),
+            category_id,
+            CategoryPatchModel::new(None, None, Some(Some(category_id))),
+            None,
+        )
+        .await;
+
+        match result {
+            Err(DomainError::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_not_found_category() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+        category_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(None));
+
+        let result = execute(
+            Arc::new(category_repository),
+            Uuid::new_v4(),
+            CategoryPatchModel::new(Some(String::from("Pizzas")), None, None),
+            None,
+        )
+        .await;
+
+        match result {
+            Err(DomainError::NotFound(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+}
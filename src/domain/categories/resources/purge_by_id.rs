@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::domain::{categories::repository::CategoryRepository, error::DomainError};
+
+#[tracing::instrument(name = "domain::categories::purge_by_id", skip_all)]
+pub async fn execute(
+    category_repository: Arc<dyn CategoryRepository>,
+    category_id: Uuid,
+) -> Result<(), DomainError> {
+    let purged = category_repository.purge_by_id(&category_id).await?;
+    if !purged {
+        return Err(DomainError::NotFound(String::from("Category id not found")));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    use crate::domain::categories::model::{
+        CategoryCreateModel, CategoryFindModel, CategoryModel, CategoryPageModel,
+        CategoryPatchModel, CategorySearchModel, CategoryUpdateModel,
+    };
+
+    use super::*;
+
+    mock! {
+        pub FakeCategoryRepository { }
+
+        #[async_trait]
+        impl CategoryRepository for FakeCategoryRepository {
+            async fn find(&self,category_find_model: &CategoryFindModel) -> Result<Option<CategoryPageModel>, DomainError>;
+            async fn search(&self,query: &str,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn insert(&self,category_create_model: &CategoryCreateModel) -> Result<CategoryModel, DomainError>;
+            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn patch_by_id(&self,id: &Uuid,category_patch_model: &CategoryPatchModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+            async fn insert_many(&self,category_create_models: &[CategoryCreateModel],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn update_many(&self,category_update_models: &[(Uuid, CategoryUpdateModel)],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn delete_many(&self,ids: &[Uuid],atomic: &bool) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+            async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_void_category_purged() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        category_repository
+            .expect_purge_by_id()
+            .return_once(|_| Ok(true));
+
+        let result = execute(Arc::new(category_repository), Uuid::new_v4()).await;
+
+        match result {
+            Ok(()) => {}
+            Err(err) => unreachable!("{err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_category_not_found() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        category_repository
+            .expect_purge_by_id()
+            .return_once(|_| Ok(false));
+
+        let result = execute(Arc::new(category_repository), Uuid::new_v4()).await;
+
+        match result {
+            Err(DomainError::NotFound(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+}
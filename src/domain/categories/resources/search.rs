@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    categories::{model::CategorySearchModel, repository::CategoryRepository},
+    error::DomainError,
+};
+
+#[tracing::instrument(name = "domain::categories::search", skip_all)]
+pub async fn execute(
+    category_repository: Arc<dyn CategoryRepository>,
+    query: String,
+    page: u32,
+    page_size: u32,
+) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(DomainError::BadRequest(String::from(
+            "search query must not be empty",
+        )));
+    }
+
+    let categories = category_repository.search(query, &page, &page_size).await?;
+
+    if categories.is_some() {
+        return Ok(categories);
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::{mock, predicate};
+    use uuid::Uuid;
+
+    use crate::domain::categories::model::{
+        CategoryCreateModel, CategoryFindModel, CategoryModel, CategoryPageModel,
+        CategoryPatchModel, CategoryUpdateModel,
+    };
+
+    mock! {
+        pub FakeCategoryRepository { }
+
+        #[async_trait]
+        impl CategoryRepository for FakeCategoryRepository {
+            async fn find(&self,category_find_model: &CategoryFindModel) -> Result<Option<CategoryPageModel>, DomainError>;
+            async fn search(&self,query: &str,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn insert(&self,category_create_model: &CategoryCreateModel) -> Result<CategoryModel, DomainError>;
+            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn patch_by_id(&self,id: &Uuid,category_patch_model: &CategoryPatchModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+            async fn insert_many(&self,category_create_models: &[CategoryCreateModel],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn update_many(&self,category_update_models: &[(Uuid, CategoryUpdateModel)],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn delete_many(&self,ids: &[Uuid],atomic: &bool) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+            async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_categories_searched() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        category_repository
+            .expect_search()
+            .with(
+                predicate::eq("burgers"),
+                predicate::always(),
+                predicate::always(),
+            )
+            .return_once(|_, _, _| Ok(Some((vec![CategorySearchModel::mock_default()], 1))));
+
+        let (categories, count) = execute(
+            Arc::new(category_repository),
+            String::from(" burgers "),
+            1,
+            12,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert!(!categories.is_empty());
+        assert!(count == 1);
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_when_query_is_empty() {
+        let category_repository = MockFakeCategoryRepository::new();
+
+        let response = execute(Arc::new(category_repository), String::from("  "), 1, 12).await;
+
+        assert!(matches!(response, Err(DomainError::BadRequest(_))));
+    }
+}
@@ -10,26 +10,76 @@ use crate::domain::{
     error::DomainError,
 };
 
+#[tracing::instrument(name = "domain::categories::update_by_id", skip_all)]
 pub async fn execute(
     category_repository: Arc<dyn CategoryRepository>,
     id: Uuid,
     category_update_model: CategoryUpdateModel,
+    version: Option<i32>,
 ) -> Result<CategoryModel, DomainError> {
-    let has_category = category_repository.find_by_id(&id).await?;
-    if has_category.is_none() {
-        return Err(DomainError::NotFound(String::from("Category id not found")));
+    find_current(category_repository.clone(), &id, &version).await?;
+
+    if let Some(parent_id) = category_update_model.parent_id {
+        validate_parent(category_repository.clone(), &id, &parent_id).await?;
     }
 
     let category = category_repository
-        .update_by_id(&id, &category_update_model)
+        .update_by_id(&id, &category_update_model, &version)
         .await?;
 
     Ok(category)
 }
 
+/// Returns the category being changed, checking the version the client read
+/// when one is given.
+pub(super) async fn find_current(
+    category_repository: Arc<dyn CategoryRepository>,
+    id: &Uuid,
+    version: &Option<i32>,
+) -> Result<CategoryModel, DomainError> {
+    match category_repository.find_by_id(id).await? {
+        None => Err(DomainError::NotFound(String::from("Category id not found"))),
+        Some(category) if version.is_some_and(|version| version != category.version) => Err(
+            DomainError::PreconditionFailed(String::from("Category version does not match")),
+        ),
+        Some(category) => Ok(category),
+    }
+}
+
+pub(super) async fn validate_parent(
+    category_repository: Arc<dyn CategoryRepository>,
+    id: &Uuid,
+    parent_id: &Uuid,
+) -> Result<(), DomainError> {
+    if parent_id == id {
+        return Err(DomainError::BadRequest(String::from(
+            "Category cannot be its own parent",
+        )));
+    }
+
+    let has_parent = category_repository.find_by_id(parent_id).await?;
+    if has_parent.is_none() {
+        return Err(DomainError::BadRequest(String::from(
+            "Parent category id not found",
+        )));
+    }
+
+    let ancestors = category_repository.find_ancestors(parent_id).await?;
+    if ancestors.iter().any(|ancestor| &ancestor.id == id) {
+        return Err(DomainError::BadRequest(String::from(
+            "Parent category cannot be a descendant of the category",
+        )));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::domain::categories::model::CategoryCreateModel;
+    use crate::domain::categories::model::{
+        CategoryCreateModel, CategoryFindModel, CategoryPageModel, CategoryPatchModel,
+        CategorySearchModel,
+    };
 
     use super::*;
 
@@ -41,11 +91,21 @@ mod tests {
 
         #[async_trait]
         impl CategoryRepository for FakeCategoryRepository {
-            async fn find(&self,name: &Option<String>,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategoryModel>, u32)>, DomainError>;
+            async fn find(&self,category_find_model: &CategoryFindModel) -> Result<Option<CategoryPageModel>, DomainError>;
+            async fn search(&self,query: &str,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
             async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
             async fn insert(&self,category_create_model: &CategoryCreateModel) -> Result<CategoryModel, DomainError>;
-            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel) -> Result<CategoryModel, DomainError>;
-            async fn delete_by_id(&self, id: &Uuid) -> Result<(), DomainError>;
+            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn patch_by_id(&self,id: &Uuid,category_patch_model: &CategoryPatchModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+            async fn insert_many(&self,category_create_models: &[CategoryCreateModel],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn update_many(&self,category_update_models: &[(Uuid, CategoryUpdateModel)],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn delete_many(&self,ids: &[Uuid],atomic: &bool) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+            async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
         }
     }
 
@@ -63,12 +123,13 @@ mod tests {
 
         category_repository
             .expect_update_by_id()
-            .return_once(|_, _| Ok(CategoryModel::mock_default()));
+            .return_once(|_, _, _| Ok(CategoryModel::mock_default()));
 
         let response = execute(
             Arc::new(category_repository),
             Uuid::new_v4(),
             mock_request_category_update,
+            None,
         )
         .await
         .unwrap();
@@ -87,6 +148,7 @@ mod tests {
             Arc::new(category_repository),
             Uuid::new_v4(),
             CategoryUpdateModel::mock_default(),
+            None,
         )
         .await;
 
@@ -95,4 +157,88 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[tokio::test]
+    async fn it_should_return_error_when_parent_is_itself() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        let mock_category_model = CategoryModel::mock_default();
+        let category_id = mock_category_model.id;
+        let mut mock_request_category_update = CategoryUpdateModel::mock_default();
+        mock_request_category_update.parent_id = Some(category_id);
+
+        category_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(Some(mock_category_model)));
+
+        let result = execute(
+            Arc::new(category_repository),
+            category_id,
+            mock_request_category_update,
+            None,
+        )
+        .await;
+
+        match result {
+            Err(DomainError::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_when_parent_is_a_descendant() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        let mock_category_model = CategoryModel::mock_default();
+        let category_id = mock_category_model.id;
+        let mock_ancestors = vec![mock_category_model.clone()];
+        let mut mock_request_category_update = CategoryUpdateModel::mock_default();
+        mock_request_category_update.parent_id = Some(Uuid::new_v4());
+
+        category_repository
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(mock_category_model.clone())));
+
+        category_repository
+            .expect_find_ancestors()
+            .return_once(|_| Ok(mock_ancestors));
+
+        let result = execute(
+            Arc::new(category_repository),
+            category_id,
+            mock_request_category_update,
+            None,
+        )
+        .await;
+
+        match result {
+            Err(DomainError::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_precondition_failed_when_version_does_not_match() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        let mock_category_model = CategoryModel::mock_default();
+        let version = mock_category_model.version + 1;
+
+        category_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(Some(mock_category_model)));
+
+        let result = execute(
+            Arc::new(category_repository),
+            Uuid::new_v4(),
+            CategoryUpdateModel::mock_default(),
+            Some(version),
+        )
+        .await;
+
+        match result {
+            Err(DomainError::PreconditionFailed(_)) => {}
+            _ => unreachable!(),
+        }
+    }
 }
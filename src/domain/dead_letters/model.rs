@@ -0,0 +1,32 @@
+/// Dead letters are read by taking them out of the queue, so only the first
+/// messages of the queue, up to this limit, are listed, replayed or purged by
+/// message id in a single call.
+pub const DEAD_LETTERS_READ_MAX: u32 = 1000;
+
+#[derive(Debug, Clone)]
+pub struct DeadLetterModel {
+    /// The message id, or `sha256:` and the hash of the body for messages
+    /// published without one. Replay and purge select dead letters by it.
+    pub id: String,
+    pub message_id: Option<String>,
+    pub exchange: String,
+    pub routing_key: String,
+    pub body: String,
+    pub headers: serde_json::Value,
+    pub death_reason: Option<String>,
+}
+#[cfg(test)]
+impl DeadLetterModel {
+    pub fn mock_default() -> Self {
+        let message_id = uuid::Uuid::new_v4().to_string();
+        Self {
+            id: message_id.clone(),
+            message_id: Some(message_id),
+            exchange: "categories-dead-letter.exchange".to_string(),
+            routing_key: "".to_string(),
+            body: r#"{"type":"create","name":"Burgers"}"#.to_string(),
+            headers: serde_json::json!({ "x-retry-count": 5 }),
+            death_reason: Some("rejected".to_string()),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::domain::error::DomainError;
+
+use super::model::DeadLetterModel;
+
+#[async_trait]
+pub trait DeadLetterRepository: Send + Sync {
+    async fn find(
+        &self,
+        page: &u32,
+        page_size: &u32,
+    ) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError>;
+    async fn replay(&self, message_ids: &Option<Vec<String>>) -> Result<u32, DomainError>;
+    async fn purge(&self, message_ids: &Option<Vec<String>>) -> Result<u32, DomainError>;
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    dead_letters::{
+        model::{DeadLetterModel, DEAD_LETTERS_READ_MAX},
+        repository::DeadLetterRepository,
+    },
+    error::DomainError,
+};
+
+#[tracing::instrument(name = "domain::dead_letters::find", skip_all)]
+pub async fn execute(
+    dead_letter_repository: Arc<dyn DeadLetterRepository>,
+    page: u32,
+    page_size: u32,
+) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError> {
+    if page as u64 * page_size as u64 > DEAD_LETTERS_READ_MAX as u64 {
+        return Err(DomainError::BadRequest(format!(
+            "Only the first {DEAD_LETTERS_READ_MAX} dead letters can be listed"
+        )));
+    }
+
+    let dead_letters = dead_letter_repository.find(&page, &page_size).await?;
+
+    if dead_letters.is_some() {
+        return Ok(dead_letters);
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    mock! {
+        pub FakeDeadLetterRepository { }
+
+        #[async_trait]
+        impl DeadLetterRepository for FakeDeadLetterRepository {
+            async fn find(&self,page: &u32,page_size: &u32) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError>;
+            async fn replay(&self, message_ids: &Option<Vec<String>>) -> Result<u32, DomainError>;
+            async fn purge(&self, message_ids: &Option<Vec<String>>) -> Result<u32, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_dead_letters_finded() {
+        let mut dead_letter_repository = MockFakeDeadLetterRepository::new();
+
+        dead_letter_repository
+            .expect_find()
+            .return_once(|_, _| Ok(Some((vec![DeadLetterModel::mock_default()], 1))));
+
+        let (dead_letters, count) = execute(Arc::new(dead_letter_repository), 1, 12)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(!dead_letters.is_empty());
+        assert!(count == 1);
+    }
+
+    #[tokio::test]
+    async fn it_should_return_bad_request_error_when_page_is_beyond_read_max() {
+        let dead_letter_repository = MockFakeDeadLetterRepository::new();
+
+        let response = execute(Arc::new(dead_letter_repository), 11, 100).await;
+
+        match response {
+            Err(DomainError::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_none_finded() {
+        let mut dead_letter_repository = MockFakeDeadLetterRepository::new();
+
+        dead_letter_repository
+            .expect_find()
+            .return_once(|_, _| Ok(None));
+
+        let response = execute(Arc::new(dead_letter_repository), 1, 12)
+            .await
+            .unwrap();
+
+        assert!(response.is_none());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod find;
+pub mod purge;
+pub mod replay;
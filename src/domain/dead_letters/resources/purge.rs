@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::domain::{dead_letters::repository::DeadLetterRepository, error::DomainError};
+
+#[tracing::instrument(name = "domain::dead_letters::purge", skip_all)]
+pub async fn execute(
+    dead_letter_repository: Arc<dyn DeadLetterRepository>,
+    message_ids: Option<Vec<String>>,
+) -> Result<u32, DomainError> {
+    if matches!(&message_ids, Some(message_ids) if message_ids.is_empty()) {
+        return Err(DomainError::BadRequest(String::from(
+            "message_ids must not be empty",
+        )));
+    }
+
+    let purged = dead_letter_repository.purge(&message_ids).await?;
+
+    Ok(purged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    use crate::domain::dead_letters::model::DeadLetterModel;
+
+    mock! {
+        pub FakeDeadLetterRepository { }
+
+        #[async_trait]
+        impl DeadLetterRepository for FakeDeadLetterRepository {
+            async fn find(&self,page: &u32,page_size: &u32) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError>;
+            async fn replay(&self, message_ids: &Option<Vec<String>>) -> Result<u32, DomainError>;
+            async fn purge(&self, message_ids: &Option<Vec<String>>) -> Result<u32, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_purged_count() {
+        let mut dead_letter_repository = MockFakeDeadLetterRepository::new();
+
+        dead_letter_repository.expect_purge().return_once(|_| Ok(3));
+
+        let purged = execute(
+            Arc::new(dead_letter_repository),
+            Some(vec![uuid::Uuid::new_v4().to_string()]),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(purged, 3);
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_when_message_ids_is_empty() {
+        let dead_letter_repository = MockFakeDeadLetterRepository::new();
+
+        let result = execute(Arc::new(dead_letter_repository), Some(vec![])).await;
+
+        match result {
+            Err(DomainError::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use crate::domain::{dead_letters::repository::DeadLetterRepository, error::DomainError};
+
+#[tracing::instrument(name = "domain::dead_letters::replay", skip_all)]
+pub async fn execute(
+    dead_letter_repository: Arc<dyn DeadLetterRepository>,
+    message_ids: Option<Vec<String>>,
+) -> Result<u32, DomainError> {
+    if matches!(&message_ids, Some(message_ids) if message_ids.is_empty()) {
+        return Err(DomainError::BadRequest(String::from(
+            "message_ids must not be empty",
+        )));
+    }
+
+    let replayed = dead_letter_repository.replay(&message_ids).await?;
+
+    Ok(replayed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    use crate::domain::dead_letters::model::DeadLetterModel;
+
+    mock! {
+        pub FakeDeadLetterRepository { }
+
+        #[async_trait]
+        impl DeadLetterRepository for FakeDeadLetterRepository {
+            async fn find(&self,page: &u32,page_size: &u32) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError>;
+            async fn replay(&self, message_ids: &Option<Vec<String>>) -> Result<u32, DomainError>;
+            async fn purge(&self, message_ids: &Option<Vec<String>>) -> Result<u32, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_replayed_count() {
+        let mut dead_letter_repository = MockFakeDeadLetterRepository::new();
+
+        dead_letter_repository
+            .expect_replay()
+            .return_once(|_| Ok(2));
+
+        let replayed = execute(Arc::new(dead_letter_repository), None)
+            .await
+            .unwrap();
+
+        assert_eq!(replayed, 2);
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_when_message_ids_is_empty() {
+        let dead_letter_repository = MockFakeDeadLetterRepository::new();
+
+        let result = execute(Arc::new(dead_letter_repository), Some(vec![])).await;
+
+        match result {
+            Err(DomainError::BadRequest(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+}
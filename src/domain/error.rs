@@ -1,5 +1,5 @@
-use log::error;
 use thiserror::Error;
+use tokio_postgres::error::SqlState;
 
 #[derive(Debug, Error)]
 pub enum DomainError {
@@ -9,16 +9,73 @@ pub enum DomainError {
     #[error("{}", _0)]
     BadRequest(String),
 
+    #[error("{}", _0)]
+    Validation(validator::ValidationErrors),
+
+    #[error("{}", _0)]
+    PreconditionFailed(String),
+
+    #[error("{}", _0)]
+    Conflict(String),
+
     #[error("{}", _0)]
     InternalServerError(String),
+
+    /// The consumed message is already recorded in the processed message
+    /// ledger, its side effects were rolled back.
+    #[error("Message {} already processed", _0)]
+    AlreadyProcessed(String),
+}
+
+/// Statement the repository was running when a constraint was violated. A
+/// foreign key is violated by the referencing row on writes and by the
+/// referenced row on deletes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbOperation {
+    Write,
+    Delete,
 }
 
 impl From<tokio_postgres::Error> for DomainError {
     fn from(err: tokio_postgres::Error) -> Self {
+        DomainError::from_db(err, DbOperation::Write)
+    }
+}
+
+impl DomainError {
+    pub fn from_db(err: tokio_postgres::Error, operation: DbOperation) -> Self {
+        if let Some(db_error) = err.as_db_error() {
+            let code = db_error.code();
+            if code == &SqlState::UNIQUE_VIOLATION || code == &SqlState::FOREIGN_KEY_VIOLATION {
+                log::warn!(
+                    "Constraint {} violated {}",
+                    db_error.constraint().unwrap_or_default(),
+                    db_error.detail().unwrap_or(db_error.message())
+                );
+                return DomainError::Conflict(conflict_message(db_error.constraint(), operation));
+            }
+        }
+
         DomainError::InternalServerError(err.to_string())
     }
 }
 
+/// Message returned to the caller for a violated constraint. The database
+/// detail quotes the offending values, so it is only logged.
+fn conflict_message(constraint: Option<&str>, operation: DbOperation) -> String {
+    let conflict = match (constraint, operation) {
+        (Some("category_name_unique_idx"), _) => "category name already exists",
+        (Some("category_parent_id_fkey"), DbOperation::Write) => "parent category does not exist",
+        (Some("category_parent_id_fkey"), DbOperation::Delete) => {
+            "category is referenced by another category"
+        }
+        (Some("api_key_key_hash_key"), _) => "api key already exists",
+        _ => "resource conflicts with the current state",
+    };
+
+    conflict.to_owned()
+}
+
 impl From<deadpool_postgres::PoolError> for DomainError {
     fn from(err: deadpool_postgres::PoolError) -> Self {
         DomainError::InternalServerError(err.to_string())
@@ -45,7 +102,7 @@ impl From<redis::RedisError> for DomainError {
 
 impl From<validator::ValidationErrors> for DomainError {
     fn from(value: validator::ValidationErrors) -> Self {
-        DomainError::BadRequest(value.to_string())
+        DomainError::Validation(value)
     }
 }
 
@@ -53,4 +110,29 @@ impl From<lapin::Error> for DomainError {
     fn from(value: lapin::Error) -> Self {
         DomainError::InternalServerError(value.to_string())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_map_constraints_to_fixed_messages() {
+        assert_eq!(
+            conflict_message(Some("category_name_unique_idx"), DbOperation::Write),
+            "category name already exists"
+        );
+        assert_eq!(
+            conflict_message(Some("category_parent_id_fkey"), DbOperation::Delete),
+            "category is referenced by another category"
+        );
+        assert_eq!(
+            conflict_message(Some("category_parent_id_fkey"), DbOperation::Write),
+            "parent category does not exist"
+        );
+        assert_eq!(
+            conflict_message(None, DbOperation::Write),
+            "resource conflicts with the current state"
+        );
+    }
+}
@@ -1,13 +1,57 @@
-use std::sync::Arc;
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::domain::error::DomainError;
 
-use super::repository::HealthRepository;
+use super::{
+    model::{DependencyHealthModel, HealthReportModel, HealthStatus},
+    repository::HealthRepository,
+};
 
-pub async fn execute(health_repository: Arc<dyn HealthRepository>) -> Result<String, DomainError> {
-    let date_now = health_repository.get_now().await?;
-    let redis_pong = health_repository.ping().await?;
-    Ok(format!("POSTGRES: {date_now} REDIS: {redis_pong}"))
+/// Checks every dependency concurrently, a check that takes longer than
+/// `timeout` reports its dependency as down instead of holding the probe.
+#[tracing::instrument(name = "domain::health::check", skip_all)]
+pub async fn execute(
+    health_repository: Arc<dyn HealthRepository>,
+    timeout: Duration,
+) -> HealthReportModel {
+    let (postgres, redis, rabbitmq) = tokio::join!(
+        check("postgres", timeout, health_repository.get_now()),
+        check("redis", timeout, health_repository.ping()),
+        check("rabbitmq", timeout, health_repository.get_amqp_status()),
+    );
+
+    HealthReportModel::new(vec![postgres, redis, rabbitmq])
+}
+
+async fn check<F>(name: &str, timeout: Duration, future: F) -> DependencyHealthModel
+where
+    F: Future<Output = Result<String, DomainError>>,
+{
+    let started_at = Instant::now();
+    let result = tokio::time::timeout(timeout, future).await;
+
+    let (status, error) = match result {
+        Ok(Ok(_)) => (HealthStatus::Up, None),
+        Ok(Err(err)) => (HealthStatus::Down, Some(err.to_string())),
+        Err(_) => (
+            HealthStatus::Down,
+            Some(format!("Timed out after {}ms", timeout.as_millis())),
+        ),
+    };
+    if let Some(error) = &error {
+        log::error!("Health check of {} failed {}", name, error);
+    }
+
+    DependencyHealthModel {
+        name: name.to_owned(),
+        status,
+        latency: started_at.elapsed(),
+        error,
+    }
 }
 
 #[cfg(test)]
@@ -24,11 +68,30 @@ mod tests {
         impl HealthRepository for FakeRepository {
             async fn get_now(&self) -> Result<String, DomainError>;
             async fn ping(&self) -> Result<String, DomainError>;
+            async fn get_amqp_status(&self) -> Result<String, DomainError>;
+        }
+    }
+
+    struct HungRedisRepository;
+
+    #[async_trait]
+    impl HealthRepository for HungRedisRepository {
+        async fn get_now(&self) -> Result<String, DomainError> {
+            Ok(String::from("2023-01-15 13:05:27.205253+00"))
+        }
+
+        async fn ping(&self) -> Result<String, DomainError> {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(String::from("PONG"))
+        }
+
+        async fn get_amqp_status(&self) -> Result<String, DomainError> {
+            Ok(String::from("connected"))
         }
     }
 
     #[tokio::test]
-    async fn it_should_return_date_now_of_db() {
+    async fn it_should_return_every_dependency_up() {
         let mut repository = MockFakeRepository::new();
         repository
             .expect_get_now()
@@ -36,26 +99,62 @@ mod tests {
             .returning(|| Ok(String::from("2023-01-15 13:05:27.205253+00")));
         repository
             .expect_ping()
-            .return_once(|| Ok(String::from("pong")));
+            .return_once(|| Ok(String::from("PONG")));
+        repository
+            .expect_get_amqp_status()
+            .return_once(|| Ok(String::from("connected")));
 
-        let check = execute(Arc::new(repository)).await.unwrap();
+        let report = execute(Arc::new(repository), Duration::from_secs(1)).await;
 
-        assert_eq!(check, "POSTGRES: 2023-01-15 13:05:27.205253+00 REDIS: pong");
+        assert_eq!(report.status, HealthStatus::Up);
+        let names: Vec<&str> = report
+            .dependencies
+            .iter()
+            .map(|dependency| dependency.name.as_str())
+            .collect();
+        assert_eq!(names, ["postgres", "redis", "rabbitmq"]);
+        assert!(report
+            .dependencies
+            .iter()
+            .all(|dependency| dependency.error.is_none()));
     }
 
     #[tokio::test]
-    async fn it_should_return_db_error() {
+    async fn it_should_return_db_down() {
         let mut repository = MockFakeRepository::new();
         repository
             .expect_get_now()
             .once()
             .returning(|| Err(DomainError::InternalServerError("Connect DB".to_string())));
+        repository
+            .expect_ping()
+            .return_once(|| Ok(String::from("PONG")));
+        repository
+            .expect_get_amqp_status()
+            .return_once(|| Ok(String::from("connected")));
 
-        let res = execute(Arc::new(repository)).await;
+        let report = execute(Arc::new(repository), Duration::from_secs(1)).await;
 
-        match res {
-            Err(DomainError::InternalServerError(_)) => {}
-            _ => unreachable!(),
-        }
+        assert_eq!(report.status, HealthStatus::Down);
+        assert_eq!(report.dependencies[0].status, HealthStatus::Down);
+        assert_eq!(report.dependencies[0].error.as_deref(), Some("Connect DB"));
+        assert_eq!(report.dependencies[1].status, HealthStatus::Up);
+    }
+
+    #[tokio::test]
+    async fn it_should_time_out_hung_dependency() {
+        let started_at = Instant::now();
+
+        let report = execute(Arc::new(HungRedisRepository), Duration::from_millis(50)).await;
+
+        assert!(started_at.elapsed() < Duration::from_secs(1));
+        assert_eq!(report.status, HealthStatus::Down);
+        assert_eq!(report.dependencies[0].status, HealthStatus::Up);
+        assert_eq!(report.dependencies[1].status, HealthStatus::Down);
+        assert_eq!(
+            report.dependencies[1].error.as_deref(),
+            Some("Timed out after 50ms")
+        );
+        assert_eq!(report.dependencies[2].status, HealthStatus::Up);
     }
 }
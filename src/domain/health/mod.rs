@@ -1,2 +1,3 @@
 pub mod check;
-pub mod repository;
\ No newline at end of file
+pub mod model;
+pub mod repository;
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone)]
+pub struct DependencyHealthModel {
+    pub name: String,
+    pub status: HealthStatus,
+    pub latency: Duration,
+    pub error: Option<String>,
+}
+
+/// Readiness of the service, `Down` when any dependency is down.
+#[derive(Debug, Clone)]
+pub struct HealthReportModel {
+    pub status: HealthStatus,
+    pub dependencies: Vec<DependencyHealthModel>,
+}
+impl HealthReportModel {
+    pub fn new(dependencies: Vec<DependencyHealthModel>) -> Self {
+        let status = if dependencies
+            .iter()
+            .all(|dependency| dependency.status == HealthStatus::Up)
+        {
+            HealthStatus::Up
+        } else {
+            HealthStatus::Down
+        };
+
+        Self {
+            status,
+            dependencies,
+        }
+    }
+}
@@ -6,4 +6,5 @@ use crate::domain::error::DomainError;
 pub trait HealthRepository: Send + Sync {
     async fn get_now(&self) -> Result<String, DomainError>;
     async fn ping(&self) -> Result<String, DomainError>;
+    async fn get_amqp_status(&self) -> Result<String, DomainError>;
 }
@@ -1,3 +1,5 @@
+pub mod api_keys;
 pub mod categories;
+pub mod dead_letters;
 pub mod error;
 pub mod health;
@@ -1,17 +1,29 @@
 use api::lib;
 use dotenv::dotenv;
-use std::{io, sync::Arc};
+use std::{io, sync::Arc, time::Duration};
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
 
 use repository::{postgres, redis};
 mod amqp;
 mod api;
 mod domain;
+mod metrics;
 mod repository;
+mod shutdown;
+mod telemetry;
 
 #[actix_web::main]
 async fn main() -> io::Result<()> {
     dotenv().ok();
-    env_logger::init();
+
+    let tracer_provider = match telemetry::init() {
+        Ok(tracer_provider) => tracer_provider,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1)
+        }
+    };
 
     let pg_pool_result = postgres::init();
     if pg_pool_result.is_err() {
@@ -20,20 +32,78 @@ async fn main() -> io::Result<()> {
     }
 
     let pg_pool = Arc::new(pg_pool_result.unwrap());
-    let redis_client = Arc::new(redis::init());
+    let redis_connection = match redis::init().await {
+        Ok(connection) => connection,
+        Err(err) => {
+            log::error!("{}", err);
+            std::process::exit(1)
+        }
+    };
 
-    let pg_pool_move = pg_pool.clone();
-    tokio::spawn(async move {
-        let result = amqp::lib::run(pg_pool_move).await;
-        if result.is_err() {
-            log::error!("{}", result.unwrap_err().to_string());
+    let shutdown = CancellationToken::new();
+    tokio::spawn(shutdown::listen(shutdown.clone()));
+    let shutdown_timeout = Duration::from_secs(api::config::get_config().shutdown_timeout);
+    let deadline = tokio::spawn(shutdown::deadline(shutdown.clone(), shutdown_timeout));
+
+    let amqp_connection = match amqp::lib::connect(shutdown.clone()).await {
+        Ok(connection) => Arc::new(connection),
+        Err(err) => {
+            log::error!("{}", err);
             std::process::exit(1)
         }
+    };
+
+    let pg_pool_move = pg_pool.clone();
+    let redis_connection_move = redis_connection.clone();
+    let amqp_connection_move = amqp_connection.clone();
+    let consumer_shutdown = shutdown.clone();
+    let consumer = tokio::spawn(async move {
+        let result = amqp::lib::run(
+            pg_pool_move,
+            redis_connection_move,
+            amqp_connection_move,
+            consumer_shutdown.clone(),
+        )
+        .await;
+        let failed = result.is_err();
+        if let Err(err) = result {
+            log::error!("{}", err);
+        }
+        consumer_shutdown.cancel();
+        failed
     });
 
-    let result = lib::run(pg_pool.clone(), redis_client.clone()).await;
-    if result.is_err() {
-        log::error!("{}", result.unwrap_err().to_string());
+    let mut failed = false;
+    if let Err(err) = lib::run(
+        pg_pool.clone(),
+        redis_connection.clone(),
+        amqp_connection.clone(),
+        shutdown.clone(),
+    )
+    .await
+    {
+        log::error!("{}", err);
+        failed = true;
+    }
+    shutdown.cancel();
+
+    let deadline = deadline
+        .await
+        .unwrap_or_else(|_| Instant::now() + shutdown_timeout);
+    match tokio::time::timeout_at(deadline, consumer).await {
+        Ok(Ok(consumer_failed)) => failed |= consumer_failed,
+        Ok(Err(err)) => {
+            log::error!("{}", err);
+            failed = true;
+        }
+        Err(_) => log::error!("AMQP consumer not stopped before the shutdown deadline"),
+    }
+    shutdown::close_amqp(&amqp_connection, deadline).await;
+    shutdown::drain_pool(&pg_pool, deadline).await;
+
+    telemetry::shutdown(tracer_provider);
+
+    if failed {
         std::process::exit(1)
     }
 
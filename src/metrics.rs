@@ -0,0 +1,166 @@
+use std::{future::Future, sync::Arc, time::Instant};
+
+use deadpool_postgres::Pool;
+use lazy_static::lazy_static;
+use prometheus::{
+    core::{Collector, Desc},
+    proto::MetricFamily,
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+lazy_static! {
+    static ref REGISTRY: Registry = Registry::new();
+    static ref HTTP_REQUESTS: IntCounterVec = register(
+        IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests handled"),
+            &["method", "route", "status"],
+        )
+        .unwrap()
+    );
+    static ref HTTP_REQUEST_DURATION: HistogramVec = register(
+        HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency in seconds"
+            ),
+            &["method", "route"],
+        )
+        .unwrap()
+    );
+    static ref REDIS_COMMAND_DURATION: HistogramVec = register(
+        HistogramVec::new(
+            HistogramOpts::new(
+                "redis_command_duration_seconds",
+                "Redis command latency in seconds"
+            )
+            .buckets(vec![
+                0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0
+            ]),
+            &["command", "outcome"],
+        )
+        .unwrap()
+    );
+    static ref AMQP_CONSUMER_MESSAGES: IntCounterVec = register(
+        IntCounterVec::new(
+            Opts::new(
+                "amqp_consumer_messages_total",
+                "AMQP messages consumed by outcome"
+            ),
+            &["outcome"],
+        )
+        .unwrap()
+    );
+}
+
+fn register<C: Collector + Clone + 'static>(collector: C) -> C {
+    REGISTRY
+        .register(Box::new(collector.clone()))
+        .expect("Error to register metric");
+    collector
+}
+
+/// Registers the pool gauges, read from the pool status on every scrape.
+pub fn register_pool(pool: Arc<Pool>) -> Result<(), prometheus::Error> {
+    REGISTRY.register(Box::new(PoolCollector::new(pool)?))
+}
+
+/// Renders every metric in the Prometheus text format.
+pub fn export() -> Result<(String, String), prometheus::Error> {
+    let encoder = TextEncoder::new();
+    let mut buffer = vec![];
+    encoder.encode(&REGISTRY.gather(), &mut buffer)?;
+
+    Ok((
+        encoder.format_type().to_owned(),
+        String::from_utf8_lossy(&buffer).to_string(),
+    ))
+}
+
+pub fn observe_http_request(method: &str, route: &str, status: u16, started_at: Instant) {
+    HTTP_REQUESTS
+        .with_label_values(&[method, route, &status.to_string()])
+        .inc();
+    HTTP_REQUEST_DURATION
+        .with_label_values(&[method, route])
+        .observe(started_at.elapsed().as_secs_f64());
+}
+
+/// Times a redis command, `command` names the operation rather than the key
+/// so the label cardinality stays low.
+pub async fn time_redis<T, E, F>(command: &str, future: F) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    let started_at = Instant::now();
+    let result = future.await;
+
+    let outcome = if result.is_ok() { "ok" } else { "error" };
+    REDIS_COMMAND_DURATION
+        .with_label_values(&[command, outcome])
+        .observe(started_at.elapsed().as_secs_f64());
+
+    result
+}
+
+/// Counts a message consumed from `categories.queue` by how it was settled:
+/// `ack`, `reject`, `nack`, `retry` or `dead_letter`.
+pub fn inc_amqp_consumer_message(outcome: &str) {
+    AMQP_CONSUMER_MESSAGES.with_label_values(&[outcome]).inc();
+}
+
+struct PoolCollector {
+    pool: Arc<Pool>,
+    connections: IntGaugeVec,
+}
+impl PoolCollector {
+    fn new(pool: Arc<Pool>) -> Result<Self, prometheus::Error> {
+        Ok(Self {
+            pool,
+            connections: IntGaugeVec::new(
+                Opts::new("db_pool_connections", "Postgres pool connections by state"),
+                &["state"],
+            )?,
+        })
+    }
+}
+
+impl Collector for PoolCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        self.connections.desc()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        let status = self.pool.status();
+
+        for (state, value) in [
+            ("max_size", status.max_size as i64),
+            ("size", status.size as i64),
+            ("available", status.available.max(0) as i64),
+            ("waiting", (-status.available).max(0) as i64),
+        ] {
+            self.connections.with_label_values(&[state]).set(value);
+        }
+
+        self.connections.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_export_observed_metrics() {
+        observe_http_request("GET", "/categories/{category_id}", 200, Instant::now());
+        inc_amqp_consumer_message("ack");
+
+        let (content_type, body) = export().unwrap();
+
+        assert!(content_type.starts_with("text/plain"));
+        assert!(body.contains(
+            r#"http_requests_total{method="GET",route="/categories/{category_id}",status="200"}"#
+        ));
+        assert!(body.contains("http_request_duration_seconds_bucket"));
+        assert!(body.contains(r#"amqp_consumer_messages_total{outcome="ack"}"#));
+    }
+}
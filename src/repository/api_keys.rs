@@ -0,0 +1,219 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use crate::domain::{
+    api_keys::{
+        model::{ApiKeyCreateModel, ApiKeyModel, ApiKeySecretModel},
+        repository::ApiKeyRepository,
+    },
+    error::DomainError,
+};
+
+const QUERY_FIND_API_KEY: &str = "
+    select
+        id as api_key_id,
+        name as api_key_name,
+        prefix as api_key_prefix,
+        scopes as api_key_scopes,
+        created_at as api_key_created_at,
+        updated_at as api_key_updated_at,
+        last_used_at as api_key_last_used_at,
+        revoked_at as api_key_revoked_at,
+        count(id) over ()::OID as count
+    from
+        api_key
+    order by
+        created_at desc
+    limit $1 offset $2;";
+
+const QUERY_FIND_API_KEY_BY_HASH: &str = "
+    select
+        id as api_key_id,
+        name as api_key_name,
+        prefix as api_key_prefix,
+        scopes as api_key_scopes,
+        created_at as api_key_created_at,
+        updated_at as api_key_updated_at,
+        last_used_at as api_key_last_used_at,
+        revoked_at as api_key_revoked_at
+    from
+        api_key
+    where
+        key_hash = $1
+        and revoked_at is null;";
+
+const QUERY_INSERT_API_KEY: &str = "
+    insert into api_key
+        (id, name, prefix, key_hash, scopes)
+    values
+        ($1,$2,$3,$4,$5)
+    returning
+        id as api_key_id,
+        name as api_key_name,
+        prefix as api_key_prefix,
+        scopes as api_key_scopes,
+        created_at as api_key_created_at,
+        updated_at as api_key_updated_at,
+        last_used_at as api_key_last_used_at,
+        revoked_at as api_key_revoked_at;";
+
+const QUERY_ROTATE_API_KEY_BY_ID: &str = "
+    update
+        api_key
+    set
+        prefix = $2,
+        key_hash = $3,
+        updated_at = now()
+    where
+        id = $1
+        and revoked_at is null
+    returning
+        id as api_key_id,
+        name as api_key_name,
+        prefix as api_key_prefix,
+        scopes as api_key_scopes,
+        created_at as api_key_created_at,
+        updated_at as api_key_updated_at,
+        last_used_at as api_key_last_used_at,
+        revoked_at as api_key_revoked_at;";
+
+const QUERY_REVOKE_API_KEY_BY_ID: &str = "
+    update
+        api_key
+    set
+        revoked_at = now(),
+        updated_at = now()
+    where
+        id = $1
+        and revoked_at is null;";
+
+/// Usage is recorded with a minute resolution so frequent callers do not
+/// write the row on every request.
+const QUERY_TOUCH_API_KEY_BY_ID: &str = "
+    update
+        api_key
+    set
+        last_used_at = now()
+    where
+        id = $1
+        and (last_used_at is null or last_used_at < now() - interval '1 minute');";
+
+pub struct PgApiKeyRepository {
+    pool: Arc<Pool>,
+}
+impl PgApiKeyRepository {
+    pub fn new(pool: Arc<Pool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for PgApiKeyRepository {
+    async fn find(
+        &self,
+        page: &u32,
+        page_size: &u32,
+    ) -> Result<Option<(Vec<ApiKeyModel>, u32)>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_FIND_API_KEY).await?;
+
+        let limit = *page_size as i64;
+        let offset = (page_size * (page - 1)) as i64;
+        let result = client.query(&stmt, &[&limit, &offset]).await?;
+
+        if !result.is_empty() {
+            let count: u32 = result.first().unwrap().get("count");
+
+            let api_keys: Vec<ApiKeyModel> = result.iter().map(|row| row.into()).collect();
+
+            return Ok(Some((api_keys, count)));
+        }
+
+        return Ok(None);
+    }
+
+    async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKeyModel>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_FIND_API_KEY_BY_HASH).await?;
+
+        if let Some(result) = client.query_opt(&stmt, &[&key_hash]).await? {
+            return Ok(Some((&result).into()));
+        }
+
+        return Ok(None);
+    }
+
+    async fn insert(
+        &self,
+        api_key_create_model: &ApiKeyCreateModel,
+    ) -> Result<ApiKeyModel, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_INSERT_API_KEY).await?;
+        let result = &client
+            .query_one(
+                &stmt,
+                &[
+                    &api_key_create_model.id,
+                    &api_key_create_model.name,
+                    &api_key_create_model.secret.prefix,
+                    &api_key_create_model.secret.key_hash,
+                    &api_key_create_model.scopes,
+                ],
+            )
+            .await?;
+
+        Ok(result.into())
+    }
+
+    async fn rotate_by_id(
+        &self,
+        id: &Uuid,
+        secret: &ApiKeySecretModel,
+    ) -> Result<Option<ApiKeyModel>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_ROTATE_API_KEY_BY_ID).await?;
+
+        let result = client
+            .query_opt(&stmt, &[id, &secret.prefix, &secret.key_hash])
+            .await?;
+
+        Ok(result.map(|result| (&result).into()))
+    }
+
+    async fn revoke_by_id(&self, id: &Uuid) -> Result<bool, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_REVOKE_API_KEY_BY_ID).await?;
+
+        let result = client.execute(&stmt, &[id]).await?;
+
+        Ok(result > 0)
+    }
+
+    async fn touch_by_id(&self, id: &Uuid) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_TOUCH_API_KEY_BY_ID).await?;
+
+        client.execute(&stmt, &[id]).await?;
+
+        Ok(())
+    }
+}
+
+impl From<&Row> for ApiKeyModel {
+    fn from(row: &Row) -> Self {
+        Self {
+            id: row.get("api_key_id"),
+            name: row.get("api_key_name"),
+            prefix: row.get("api_key_prefix"),
+            scopes: row.get("api_key_scopes"),
+            created_at: row.get("api_key_created_at"),
+            updated_at: row.get("api_key_updated_at"),
+            last_used_at: row.get("api_key_last_used_at"),
+            revoked_at: row.get("api_key_revoked_at"),
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        api_keys::{
+            model::{ApiKeyCreateModel, ApiKeyModel, ApiKeySecretModel},
+            repository::ApiKeyRepository,
+        },
+        error::DomainError,
+    },
+    metrics,
+};
+
+const CACHE_KEY_PREFIX: &str = "api_keys";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedApiKey {
+    id: Uuid,
+    name: String,
+    prefix: String,
+    scopes: Vec<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    last_used_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+impl From<&ApiKeyModel> for CachedApiKey {
+    fn from(value: &ApiKeyModel) -> Self {
+        Self {
+            id: value.id,
+            name: value.name.clone(),
+            prefix: value.prefix.clone(),
+            scopes: value.scopes.clone(),
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            last_used_at: value.last_used_at,
+            revoked_at: value.revoked_at,
+        }
+    }
+}
+impl From<CachedApiKey> for ApiKeyModel {
+    fn from(value: CachedApiKey) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            prefix: value.prefix,
+            scopes: value.scopes,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            last_used_at: value.last_used_at,
+            revoked_at: value.revoked_at,
+        }
+    }
+}
+
+/// Read-through cache of `find_by_hash` over another `ApiKeyRepository`, so
+/// authenticating a key does not hit the database on every request.
+///
+/// Each cached key also stores the hash under its id, which lets rotating or
+/// revoking a key evict the old secret right away. Redis failures are logged
+/// and the call falls back to the wrapped repository.
+pub struct CachedApiKeyRepository {
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+    redis_connection: ConnectionManager,
+    ttl: usize,
+}
+impl CachedApiKeyRepository {
+    pub fn new(
+        api_key_repository: Arc<dyn ApiKeyRepository>,
+        redis_connection: ConnectionManager,
+        ttl: usize,
+    ) -> Self {
+        Self {
+            api_key_repository,
+            redis_connection,
+            ttl,
+        }
+    }
+
+    fn hash_key(key_hash: &str) -> String {
+        format!("{CACHE_KEY_PREFIX}:hash:{key_hash}")
+    }
+
+    fn id_key(id: &Uuid) -> String {
+        format!("{CACHE_KEY_PREFIX}:id:{id}")
+    }
+
+    async fn get(&self, key_hash: &str) -> Result<Option<CachedApiKey>, DomainError> {
+        let mut con = self.redis_connection.clone();
+        let value: Option<String> =
+            metrics::time_redis("get", con.get(Self::hash_key(key_hash))).await?;
+
+        match value {
+            Some(value) => {
+                Ok(Some(serde_json::from_str(&value).map_err(|err| {
+                    DomainError::InternalServerError(err.to_string())
+                })?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn set(&self, key_hash: &str, api_key: &ApiKeyModel) -> Result<(), DomainError> {
+        let value = serde_json::to_string(&CachedApiKey::from(api_key))
+            .map_err(|err| DomainError::InternalServerError(err.to_string()))?;
+
+        let mut con = self.redis_connection.clone();
+        metrics::time_redis(
+            "set",
+            redis::pipe()
+                .set_ex(Self::hash_key(key_hash), value, self.ttl)
+                .set_ex(Self::id_key(&api_key.id), key_hash, self.ttl)
+                .query_async::<_, ()>(&mut con),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn invalidate(&self, id: &Uuid) {
+        let result: Result<(), DomainError> = async {
+            let mut con = self.redis_connection.clone();
+            let key_hash: Option<String> =
+                metrics::time_redis("get", con.get(Self::id_key(id))).await?;
+            if let Some(key_hash) = key_hash {
+                metrics::time_redis(
+                    "del",
+                    con.del::<_, ()>(&[Self::hash_key(&key_hash), Self::id_key(id)]),
+                )
+                .await?;
+            }
+            Ok(())
+        }
+        .await;
+
+        if let Err(err) = result {
+            log::error!("Error to invalidate API keys cache: {}", err);
+        }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for CachedApiKeyRepository {
+    async fn find(
+        &self,
+        page: &u32,
+        page_size: &u32,
+    ) -> Result<Option<(Vec<ApiKeyModel>, u32)>, DomainError> {
+        self.api_key_repository.find(page, page_size).await
+    }
+
+    async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKeyModel>, DomainError> {
+        match self.get(key_hash).await {
+            Ok(Some(cached)) => return Ok(Some(cached.into())),
+            Ok(None) => {}
+            Err(err) => log::warn!("Error to read API keys cache: {}", err),
+        }
+
+        let api_key = self.api_key_repository.find_by_hash(key_hash).await?;
+
+        if let Some(api_key) = &api_key {
+            if let Err(err) = self.set(key_hash, api_key).await {
+                log::warn!("Error to write API keys cache: {}", err);
+            }
+        }
+
+        Ok(api_key)
+    }
+
+    async fn insert(
+        &self,
+        api_key_create_model: &ApiKeyCreateModel,
+    ) -> Result<ApiKeyModel, DomainError> {
+        self.api_key_repository.insert(api_key_create_model).await
+    }
+
+    async fn rotate_by_id(
+        &self,
+        id: &Uuid,
+        secret: &ApiKeySecretModel,
+    ) -> Result<Option<ApiKeyModel>, DomainError> {
+        let api_key = self.api_key_repository.rotate_by_id(id, secret).await?;
+        self.invalidate(id).await;
+        Ok(api_key)
+    }
+
+    async fn revoke_by_id(&self, id: &Uuid) -> Result<bool, DomainError> {
+        let revoked = self.api_key_repository.revoke_by_id(id).await?;
+        self.invalidate(id).await;
+        Ok(revoked)
+    }
+
+    /// Evicts the key once its usage is recorded, so the next lookup reads the
+    /// new `last_used_at` and the key is not touched again for a minute.
+    async fn touch_by_id(&self, id: &Uuid) -> Result<(), DomainError> {
+        self.api_key_repository.touch_by_id(id).await?;
+        self.invalidate(id).await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::mock;
+
+    use crate::repository::redis;
+
+    use super::*;
+
+    mock! {
+        pub FakeApiKeyRepository { }
+
+        #[async_trait]
+        impl ApiKeyRepository for FakeApiKeyRepository {
+            async fn find(&self, page: &u32, page_size: &u32) -> Result<Option<(Vec<ApiKeyModel>, u32)>, DomainError>;
+            async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKeyModel>, DomainError>;
+            async fn insert(&self, api_key_create_model: &ApiKeyCreateModel) -> Result<ApiKeyModel, DomainError>;
+            async fn rotate_by_id(&self, id: &Uuid, secret: &ApiKeySecretModel) -> Result<Option<ApiKeyModel>, DomainError>;
+            async fn revoke_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+            async fn touch_by_id(&self, id: &Uuid) -> Result<(), DomainError>;
+        }
+    }
+
+    async fn get_repository(
+        api_key_repository: MockFakeApiKeyRepository,
+    ) -> CachedApiKeyRepository {
+        dotenv::from_filename(".env.test").ok();
+
+        CachedApiKeyRepository::new(
+            Arc::new(api_key_repository),
+            redis::init().await.unwrap(),
+            60,
+        )
+    }
+
+    #[tokio::test]
+    async fn it_should_read_api_key_from_cache_until_revoked() {
+        let secret = ApiKeySecretModel::generate();
+        let api_key = ApiKeyModel::mock_default();
+        let id = api_key.id;
+
+        let mut api_key_repository = MockFakeApiKeyRepository::new();
+        api_key_repository
+            .expect_find_by_hash()
+            .times(2)
+            .returning(move |_| Ok(Some(api_key.clone())));
+        api_key_repository
+            .expect_revoke_by_id()
+            .return_once(|_| Ok(true));
+
+        let repository = get_repository(api_key_repository).await;
+
+        repository.find_by_hash(&secret.key_hash).await.unwrap();
+        let cached = repository.find_by_hash(&secret.key_hash).await.unwrap();
+        assert_eq!(cached.unwrap().id, id);
+
+        repository.revoke_by_id(&id).await.unwrap();
+        repository.find_by_hash(&secret.key_hash).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_should_reload_api_key_after_touch() {
+        let secret = ApiKeySecretModel::generate();
+        let api_key = ApiKeyModel::mock_default();
+        let id = api_key.id;
+
+        let mut api_key_repository = MockFakeApiKeyRepository::new();
+        api_key_repository
+            .expect_find_by_hash()
+            .times(2)
+            .returning(move |_| Ok(Some(api_key.clone())));
+        api_key_repository
+            .expect_touch_by_id()
+            .return_once(|_| Ok(()));
+
+        let repository = get_repository(api_key_repository).await;
+
+        repository.find_by_hash(&secret.key_hash).await.unwrap();
+        repository.touch_by_id(&id).await.unwrap();
+        repository.find_by_hash(&secret.key_hash).await.unwrap();
+    }
+}
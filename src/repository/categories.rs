@@ -1,56 +1,209 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use deadpool_postgres::Pool;
+use deadpool_postgres::{Pool, Transaction};
+use serde_json::json;
 
 use tokio_postgres::{types::ToSql, Row};
 use uuid::Uuid;
 
-use crate::domain::{
-    categories::{
-        model::{CategoryCreateModel, CategoryModel, CategoryUpdateModel},
-        repository::CategoryRepository,
+use crate::{
+    domain::{
+        categories::{
+            model::{
+                CategoryCreateModel, CategoryCursorDirection, CategoryFindModel, CategoryModel,
+                CategoryPageModel, CategoryPaginationModel, CategoryPatchModel,
+                CategorySearchModel, CategorySortField, CategorySortModel, CategoryUpdateModel,
+            },
+            repository::CategoryRepository,
+        },
+        error::{DbOperation, DomainError},
     },
-    error::DomainError,
+    repository::{outbox, processed_messages},
 };
 
+const CATEGORY_AGGREGATE_TYPE: &str = "category";
+const CATEGORY_CREATED_EVENT: &str = "category.created";
+const CATEGORY_UPDATED_EVENT: &str = "category.updated";
+const CATEGORY_DELETED_EVENT: &str = "category.deleted";
+
 const QUERY_FIND_CATEGORY: &str = "
     select
         id as category_id,
         name as category_name,
         description as category_description,
+        parent_id as category_parent_id,
         is_active as category_is_active,
         created_at as category_created_at,
         updated_at as category_updated_at,
-        count(*) over ()::OID as count
+        deleted_at as category_deleted_at,
+        version as category_version
     from
         category";
 
+const QUERY_COUNT_CATEGORY: &str = "
+    select
+        count(*)::OID as count
+    from
+        category";
+
+const QUERY_SEARCH_CATEGORY: &str = "
+    select
+        category.id as category_id,
+        category.name as category_name,
+        category.description as category_description,
+        category.parent_id as category_parent_id,
+        category.is_active as category_is_active,
+        category.created_at as category_created_at,
+        category.updated_at as category_updated_at,
+        category.deleted_at as category_deleted_at,
+        category.version as category_version,
+        ts_rank(category.search, query.tsquery) + similarity(category.name, $1) as category_rank,
+        ts_headline('simple', html_escape(category.name), query.tsquery, 'StartSel=<mark>, StopSel=</mark>') as category_name_highlight,
+        ts_headline('simple', html_escape(category.description), query.tsquery, 'StartSel=<mark>, StopSel=</mark>') as category_description_highlight,
+        count(*) over ()::OID as count
+    from
+        category,
+        websearch_to_tsquery('simple', $1) as query(tsquery)
+    where
+        category.is_active = true
+        and (category.search @@ query.tsquery or category.name % $1)
+    order by
+        category_rank desc,
+        category.id
+    limit $2
+    offset $3;";
+
 const QUERY_FIND_CATEGORY_BY_ID: &str = "
     select
         id as category_id,
         name as category_name,
         description as category_description,
+        parent_id as category_parent_id,
         is_active as category_is_active,
         created_at as category_created_at,
-        updated_at as category_updated_at
+        updated_at as category_updated_at,
+        deleted_at as category_deleted_at,
+        version as category_version
     from
         category
     where 
-        id = $1;";
+        id = $1
+        and is_active = true;";
+
+const QUERY_FIND_CATEGORY_CHILDREN: &str = "
+    select
+        id as category_id,
+        name as category_name,
+        description as category_description,
+        parent_id as category_parent_id,
+        is_active as category_is_active,
+        created_at as category_created_at,
+        updated_at as category_updated_at,
+        deleted_at as category_deleted_at,
+        version as category_version
+    from
+        category
+    where
+        parent_id = $1
+        and is_active = true
+    order by
+        created_at;";
+
+/// Recursive queries track the visited ids in `path`, so cyclic `parent_id`
+/// data ends the walk instead of recursing until the statement times out.
+const QUERY_FIND_CATEGORY_ANCESTORS: &str = "
+    with recursive ancestors as (
+        select
+            category.*,
+            1 as depth,
+            array[$1, category.id] as path
+        from
+            category
+        where
+            id = (select parent_id from category where id = $1)
+        union all
+        select
+            category.*,
+            ancestors.depth + 1 as depth,
+            ancestors.path || category.id as path
+        from
+            category
+        inner join ancestors on
+            category.id = ancestors.parent_id
+        where
+            not category.id = any(ancestors.path)
+    )
+    select
+        id as category_id,
+        name as category_name,
+        description as category_description,
+        parent_id as category_parent_id,
+        is_active as category_is_active,
+        created_at as category_created_at,
+        updated_at as category_updated_at,
+        deleted_at as category_deleted_at,
+        version as category_version
+    from
+        ancestors
+    order by
+        depth;";
+
+const QUERY_FIND_CATEGORY_SUBTREE: &str = "
+    with recursive subtree as (
+        select
+            category.*,
+            0 as depth,
+            array[category.id] as path
+        from
+            category
+        where
+            id = $1
+            and is_active = true
+        union all
+        select
+            category.*,
+            subtree.depth + 1 as depth,
+            subtree.path || category.id as path
+        from
+            category
+        inner join subtree on
+            category.parent_id = subtree.id
+        where
+            category.is_active = true
+            and not category.id = any(subtree.path)
+    )
+    select
+        id as category_id,
+        name as category_name,
+        description as category_description,
+        parent_id as category_parent_id,
+        is_active as category_is_active,
+        created_at as category_created_at,
+        updated_at as category_updated_at,
+        deleted_at as category_deleted_at,
+        version as category_version
+    from
+        subtree
+    order by
+        depth,
+        created_at;";
 
 const QUERY_INSERT_CATEGORY: &str = "
     insert into category
-        (id, name, description)
+        (id, name, description, parent_id)
     values
-        ($1,$2,$3)
+        ($1,$2,$3,$4)
     returning
         id as category_id,
         name as category_name,
         description as category_description,
+        parent_id as category_parent_id,
         is_active as category_is_active,
         created_at as category_created_at,
-        updated_at as category_updated_at;";
+        updated_at as category_updated_at,
+        deleted_at as category_deleted_at,
+        version as category_version;";
 
 const QUERY_UPDATE_CATEGORY_BY_ID: &str = "
     update
@@ -58,46 +211,168 @@ const QUERY_UPDATE_CATEGORY_BY_ID: &str = "
     set
         name=$2,
         description=$3,
-        updated_at=now()
+        parent_id=$4,
+        updated_at=now(),
+        version=version + 1
     where
         id = $1
+        and is_active = true
+        and ($5::integer is null or version = $5)
     returning
         id as category_id,
         name as category_name,
         description as category_description,
+        parent_id as category_parent_id,
         is_active as category_is_active,
         created_at as category_created_at,
-        updated_at as category_updated_at;";
+        updated_at as category_updated_at,
+        deleted_at as category_deleted_at,
+        version as category_version;";
+
+const QUERY_PATCH_CATEGORY_BY_ID_CONDITION: &str = "
+    where
+        id = $1
+        and is_active = true
+        and ($2::integer is null or version = $2)
+    returning
+        id as category_id,
+        name as category_name,
+        description as category_description,
+        parent_id as category_parent_id,
+        is_active as category_is_active,
+        created_at as category_created_at,
+        updated_at as category_updated_at,
+        deleted_at as category_deleted_at,
+        version as category_version;";
 
 const QUERY_DELETE_CATEGORY_BY_ID: &str = "
-            delete from
-                category 
-            where
-                id = $1;";
+    update
+        category
+    set
+        is_active=false,
+        deleted_at=now(),
+        updated_at=now(),
+        version=version + 1
+    where
+        id = $1
+        and is_active = true
+        and ($2::integer is null or version = $2)
+    returning
+        id as category_id,
+        name as category_name,
+        description as category_description,
+        parent_id as category_parent_id,
+        is_active as category_is_active,
+        created_at as category_created_at,
+        updated_at as category_updated_at,
+        deleted_at as category_deleted_at,
+        version as category_version;";
+
+const QUERY_RESTORE_CATEGORY_BY_ID: &str = "
+    update
+        category
+    set
+        is_active=true,
+        deleted_at=null,
+        updated_at=now(),
+        version=version + 1
+    where
+        id = $1
+        and is_active = false
+    returning
+        id as category_id,
+        name as category_name,
+        description as category_description,
+        parent_id as category_parent_id,
+        is_active as category_is_active,
+        created_at as category_created_at,
+        updated_at as category_updated_at,
+        deleted_at as category_deleted_at,
+        version as category_version;";
+
+const QUERY_PURGE_CATEGORY_BY_ID: &str = "
+    delete from
+        category
+    where
+        id = $1
+    returning
+        id as category_id,
+        name as category_name,
+        description as category_description,
+        parent_id as category_parent_id,
+        is_active as category_is_active,
+        created_at as category_created_at,
+        updated_at as category_updated_at,
+        deleted_at as category_deleted_at,
+        version as category_version;";
 
 pub struct PgCategoryRepository {
     pool: Arc<Pool>,
+    message_id: Option<String>,
 }
 impl PgCategoryRepository {
     pub fn new(pool: Arc<Pool>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            message_id: None,
+        }
+    }
+
+    /// Scopes the repository to a consumed message. Every write then records
+    /// the message in the processed message ledger within its transaction.
+    pub fn with_message_id(mut self, message_id: String) -> Self {
+        self.message_id = Some(message_id);
+        self
+    }
+
+    /// Bulk operations run in a single transaction. Atomic batches are only
+    /// committed when every item succeeded, otherwise the successful items are
+    /// committed and the failed ones were already rolled back to their savepoint.
+    async fn finish_bulk(
+        &self,
+        transaction: Transaction<'_>,
+        atomic: &bool,
+        succeeded: bool,
+    ) -> Result<(), DomainError> {
+        if *atomic && !succeeded {
+            transaction.rollback().await?;
+            return Ok(());
+        }
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    /// Records the consumed message before any write, so a concurrent delivery
+    /// of the same message fails with `DomainError::AlreadyProcessed` instead of
+    /// tripping over the changes of the first one.
+    async fn record_message(&self, transaction: &Transaction<'_>) -> Result<(), DomainError> {
+        if let Some(message_id) = &self.message_id {
+            processed_messages::insert(transaction, message_id).await?;
+        }
+
+        Ok(())
     }
 }
 
 #[async_trait]
 impl CategoryRepository for PgCategoryRepository {
+    #[tracing::instrument(
+        name = "PgCategoryRepository::find",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
     async fn find(
         &self,
-        name: &Option<String>,
-        page: &u32,
-        page_size: &u32,
-    ) -> Result<Option<(Vec<CategoryModel>, u32)>, DomainError> {
+        category_find_model: &CategoryFindModel,
+    ) -> Result<Option<CategoryPageModel>, DomainError> {
         let client = self.pool.get().await?;
 
         let mut queries: Vec<String> = vec![];
         let mut params: Vec<&(dyn ToSql + Sync)> = Vec::new();
 
-        if let Some(name) = name {
+        if let Some(name) = &category_find_model.name {
             queries.push(format!(
                 "category.name like '%' || ${} || '%'",
                 params.len() + 1
@@ -105,21 +380,171 @@ impl CategoryRepository for PgCategoryRepository {
             params.push(name);
         }
 
+        if let Some(is_active) = &category_find_model.is_active {
+            queries.push(format!("category.is_active = ${}", params.len() + 1));
+            params.push(is_active);
+        } else if !category_find_model.include_inactive {
+            queries.push(String::from("category.is_active = true"));
+        }
+
+        if let Some(ids) = &category_find_model.ids {
+            queries.push(format!("category.id = any(${})", params.len() + 1));
+            params.push(ids);
+        }
+
+        let ranges = [
+            (
+                "category.created_at >=",
+                &category_find_model.created_at_from,
+            ),
+            ("category.created_at <=", &category_find_model.created_at_to),
+            (
+                "category.updated_at >=",
+                &category_find_model.updated_at_from,
+            ),
+            ("category.updated_at <=", &category_find_model.updated_at_to),
+        ];
+        for (condition, value) in ranges {
+            if let Some(value) = value {
+                queries.push(format!("{condition} ${}", params.len() + 1));
+                params.push(value);
+            }
+        }
+
+        let mut count = None;
+        if category_find_model.with_count {
+            let mut query = String::from(QUERY_COUNT_CATEGORY);
+            if !queries.is_empty() {
+                query = format!("{} where {}", query, queries.join(" and "));
+            }
+
+            let stmt = client.prepare(&query).await?;
+            count = Some(client.query_one(&stmt, &params[..]).await?.get("count"));
+        }
+
+        let mut sort = category_find_model.sort.clone();
+        if sort.is_empty() {
+            sort.push(CategorySortModel::new(CategorySortField::CreatedAt, false));
+        }
+
+        // Every key is read in the opposite order when paging backwards and
+        // the rows are reversed afterwards.
+        let backward = matches!(
+            &category_find_model.pagination,
+            CategoryPaginationModel::Cursor(cursor) if cursor.direction == CategoryCursorDirection::Prev
+        );
+        // `None` is the id, which breaks ties.
+        let mut keys: Vec<(Option<CategorySortField>, bool)> = sort
+            .iter()
+            .map(|sort| (Some(sort.field), sort.descending != backward))
+            .collect();
+        keys.push((None, backward));
+
+        let page_size = category_find_model.page_size;
+        let mut offset: u64 = 0;
+        match &category_find_model.pagination {
+            CategoryPaginationModel::Page(page) => {
+                offset = page_size as u64 * page.saturating_sub(1) as u64
+            }
+            CategoryPaginationModel::Cursor(cursor) => {
+                let mut conditions: Vec<String> = vec![];
+                let mut equals: Vec<String> = vec![];
+                for (field, descending) in &keys {
+                    params.push(match field {
+                        Some(CategorySortField::Name) => &cursor.name,
+                        Some(CategorySortField::CreatedAt) => &cursor.created_at,
+                        Some(CategorySortField::UpdatedAt) => &cursor.updated_at,
+                        None => &cursor.id,
+                    });
+
+                    let column = sort_column(field);
+                    let operator = if *descending { "<" } else { ">" };
+                    let condition = format!("{column} {operator} ${}", params.len());
+                    conditions.push(
+                        equals
+                            .iter()
+                            .chain([&condition])
+                            .cloned()
+                            .collect::<Vec<_>>()
+                            .join(" and "),
+                    );
+                    equals.push(format!("{column} = ${}", params.len()));
+                }
+                queries.push(format!("(({}))", conditions.join(") or (")));
+            }
+        }
+
         let mut query = String::from(QUERY_FIND_CATEGORY);
         if !queries.is_empty() {
             query = format!("{} where {}", query, queries.join(" and "));
         }
 
-        let offset = page_size * (page - 1);
-        query = format!("{query} limit {page_size} offset {offset}");
+        let order = keys
+            .iter()
+            .map(|(field, descending)| match descending {
+                true => format!("{} desc", sort_column(field)),
+                false => format!("{} asc", sort_column(field)),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // One extra row tells whether there is a page after this one.
+        query = format!(
+            "{query} order by {order} limit {} offset {offset}",
+            page_size + 1
+        );
 
         let stmt = client.prepare(&query).await?;
         let result = client.query(&stmt, &params[..]).await?;
 
+        if result.is_empty() {
+            return Ok(None);
+        }
+
+        let has_more = result.len() > page_size as usize;
+        let mut categories: Vec<CategoryModel> = result
+            .iter()
+            .take(page_size as usize)
+            .map(|row| row.into())
+            .collect();
+
+        let (has_next, has_prev) = match &category_find_model.pagination {
+            CategoryPaginationModel::Page(page) => (has_more, *page > 1),
+            CategoryPaginationModel::Cursor(_) if backward => {
+                categories.reverse();
+                (true, has_more)
+            }
+            CategoryPaginationModel::Cursor(_) => (has_more, true),
+        };
+
+        Ok(Some(CategoryPageModel::new(
+            categories, count, has_next, has_prev,
+        )))
+    }
+
+    #[tracing::instrument(
+        name = "PgCategoryRepository::search",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn search(
+        &self,
+        query: &str,
+        page: &u32,
+        page_size: &u32,
+    ) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_SEARCH_CATEGORY).await?;
+
+        let limit = *page_size as i64;
+        let offset = *page_size as i64 * page.saturating_sub(1) as i64;
+        let result = client.query(&stmt, &[&query, &limit, &offset]).await?;
+
         if !result.is_empty() {
             let count: u32 = result.first().unwrap().get("count");
 
-            let categories: Vec<CategoryModel> = result.iter().map(|row| row.into()).collect();
+            let categories: Vec<CategorySearchModel> =
+                result.iter().map(|row| row.into()).collect();
 
             return Ok(Some((categories, count)));
         }
@@ -127,6 +552,11 @@ impl CategoryRepository for PgCategoryRepository {
         return Ok(None);
     }
 
+    #[tracing::instrument(
+        name = "PgCategoryRepository::find_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
     async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError> {
         let client = self.pool.get().await?;
         let stmt = client.prepare(QUERY_FIND_CATEGORY_BY_ID).await?;
@@ -138,53 +568,438 @@ impl CategoryRepository for PgCategoryRepository {
         return Ok(None);
     }
 
+    #[tracing::instrument(
+        name = "PgCategoryRepository::find_children",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_FIND_CATEGORY_CHILDREN).await?;
+        let result = client.query(&stmt, &[id]).await?;
+
+        Ok(result.iter().map(|row| row.into()).collect())
+    }
+
+    #[tracing::instrument(
+        name = "PgCategoryRepository::find_ancestors",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_FIND_CATEGORY_ANCESTORS).await?;
+        let result = client.query(&stmt, &[id]).await?;
+
+        Ok(result.iter().map(|row| row.into()).collect())
+    }
+
+    #[tracing::instrument(
+        name = "PgCategoryRepository::find_subtree",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_FIND_CATEGORY_SUBTREE).await?;
+        let result = client.query(&stmt, &[id]).await?;
+
+        Ok(result.iter().map(|row| row.into()).collect())
+    }
+
+    #[tracing::instrument(
+        name = "PgCategoryRepository::insert",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
     async fn insert(
         &self,
         category_create_model: &CategoryCreateModel,
     ) -> Result<CategoryModel, DomainError> {
-        let client = self.pool.get().await?;
-        let stmt = client.prepare(QUERY_INSERT_CATEGORY).await?;
-        let result = &client
-            .query_one(
-                &stmt,
-                &[
-                    &category_create_model.id,
-                    &category_create_model.name,
-                    &category_create_model.description,
-                ],
-            )
-            .await?;
+        let mut client = self.pool.get().await?;
+        let transaction = client.transaction().await?;
+        self.record_message(&transaction).await?;
 
-        Ok(result.into())
+        let category = insert_category(&transaction, category_create_model).await?;
+        transaction.commit().await?;
+
+        Ok(category)
     }
 
+    #[tracing::instrument(
+        name = "PgCategoryRepository::update_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
     async fn update_by_id(
         &self,
         id: &Uuid,
         category_update_model: &CategoryUpdateModel,
+        version: &Option<i32>,
     ) -> Result<CategoryModel, DomainError> {
-        let client = self.pool.get().await?;
-        let stmt = client.prepare(QUERY_UPDATE_CATEGORY_BY_ID).await?;
-        let result = &client
-            .query_one(
-                &stmt,
-                &[
-                    id,
-                    &category_update_model.name,
-                    &category_update_model.description,
-                ],
-            )
-            .await?;
+        let mut client = self.pool.get().await?;
+        let transaction = client.transaction().await?;
+        self.record_message(&transaction).await?;
+
+        let category = update_category(&transaction, id, category_update_model, version)
+            .await?
+            .ok_or_else(|| unmatched(version))?;
+        transaction.commit().await?;
 
-        Ok(result.into())
+        Ok(category)
     }
 
-    async fn delete_by_id(&self, id: &Uuid) -> Result<(), DomainError> {
-        let client = self.pool.get().await?;
-        let stmt = client.prepare(QUERY_DELETE_CATEGORY_BY_ID).await?;
-        client.execute(&stmt, &[id]).await?;
+    #[tracing::instrument(
+        name = "PgCategoryRepository::patch_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn patch_by_id(
+        &self,
+        id: &Uuid,
+        category_patch_model: &CategoryPatchModel,
+        version: &Option<i32>,
+    ) -> Result<CategoryModel, DomainError> {
+        let mut client = self.pool.get().await?;
+        let transaction = client.transaction().await?;
+        self.record_message(&transaction).await?;
+
+        let mut sets: Vec<String> = vec![];
+        let mut params: Vec<&(dyn ToSql + Sync)> = vec![id, version];
+
+        if let Some(name) = &category_patch_model.name {
+            params.push(name);
+            sets.push(format!("name=${}", params.len()));
+        }
+
+        if let Some(description) = &category_patch_model.description {
+            params.push(description);
+            sets.push(format!("description=${}", params.len()));
+        }
+
+        if let Some(parent_id) = &category_patch_model.parent_id {
+            params.push(parent_id);
+            sets.push(format!("parent_id=${}", params.len()));
+        }
+
+        sets.push(String::from("updated_at=now()"));
+        sets.push(String::from("version=version + 1"));
+        let query = format!(
+            "update category set {} {}",
+            sets.join(", "),
+            QUERY_PATCH_CATEGORY_BY_ID_CONDITION
+        );
+
+        let stmt = transaction.prepare(&query).await?;
+        let result = transaction
+            .query_opt(&stmt, &params[..])
+            .await?
+            .ok_or_else(|| unmatched(version))?;
+        let category: CategoryModel = (&result).into();
+
+        insert_outbox_event(&transaction, CATEGORY_UPDATED_EVENT, &category).await?;
+        transaction.commit().await?;
+
+        Ok(category)
+    }
+
+    #[tracing::instrument(
+        name = "PgCategoryRepository::delete_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError> {
+        let mut client = self.pool.get().await?;
+        let transaction = client.transaction().await?;
+        self.record_message(&transaction).await?;
+
+        let deleted = delete_category(&transaction, id, version).await?;
+        if !deleted && version.is_some() {
+            return Err(precondition_failed());
+        }
+        transaction.commit().await?;
+
         Ok(())
     }
+
+    #[tracing::instrument(
+        name = "PgCategoryRepository::insert_many",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn insert_many(
+        &self,
+        category_create_models: &[CategoryCreateModel],
+        atomic: &bool,
+    ) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError> {
+        let mut client = self.pool.get().await?;
+        let mut transaction = client.transaction().await?;
+        self.record_message(&transaction).await?;
+
+        let mut results = vec![];
+        for category_create_model in category_create_models {
+            let savepoint = transaction.savepoint("bulk_item").await?;
+            let result = insert_category(&savepoint, category_create_model).await;
+            finish_savepoint(savepoint, result.is_ok()).await?;
+            results.push(result);
+        }
+
+        self.finish_bulk(
+            transaction,
+            atomic,
+            results.iter().all(|result| result.is_ok()),
+        )
+        .await?;
+
+        Ok(results)
+    }
+
+    #[tracing::instrument(
+        name = "PgCategoryRepository::update_many",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn update_many(
+        &self,
+        category_update_models: &[(Uuid, CategoryUpdateModel)],
+        atomic: &bool,
+    ) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError> {
+        let mut client = self.pool.get().await?;
+        let mut transaction = client.transaction().await?;
+        self.record_message(&transaction).await?;
+
+        let mut results = vec![];
+        for (id, category_update_model) in category_update_models {
+            let savepoint = transaction.savepoint("bulk_item").await?;
+            let result = update_category(&savepoint, id, category_update_model, &None)
+                .await
+                .and_then(|category| category.ok_or_else(not_found));
+            finish_savepoint(savepoint, result.is_ok()).await?;
+            results.push(result);
+        }
+
+        self.finish_bulk(
+            transaction,
+            atomic,
+            results.iter().all(|result| result.is_ok()),
+        )
+        .await?;
+
+        Ok(results)
+    }
+
+    #[tracing::instrument(
+        name = "PgCategoryRepository::delete_many",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn delete_many(
+        &self,
+        ids: &[Uuid],
+        atomic: &bool,
+    ) -> Result<Vec<Result<(), DomainError>>, DomainError> {
+        let mut client = self.pool.get().await?;
+        let mut transaction = client.transaction().await?;
+        self.record_message(&transaction).await?;
+
+        let mut results = vec![];
+        for id in ids {
+            let savepoint = transaction.savepoint("bulk_item").await?;
+            let result = delete_category(&savepoint, id, &None)
+                .await
+                .and_then(|deleted| if deleted { Ok(()) } else { Err(not_found()) });
+            finish_savepoint(savepoint, result.is_ok()).await?;
+            results.push(result);
+        }
+
+        self.finish_bulk(
+            transaction,
+            atomic,
+            results.iter().all(|result| result.is_ok()),
+        )
+        .await?;
+
+        Ok(results)
+    }
+
+    #[tracing::instrument(
+        name = "PgCategoryRepository::restore_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError> {
+        let mut client = self.pool.get().await?;
+        let transaction = client.transaction().await?;
+        self.record_message(&transaction).await?;
+
+        let stmt = transaction.prepare(QUERY_RESTORE_CATEGORY_BY_ID).await?;
+        let category: Option<CategoryModel> = transaction
+            .query_opt(&stmt, &[id])
+            .await?
+            .map(|result| (&result).into());
+
+        if let Some(category) = &category {
+            insert_outbox_event(&transaction, CATEGORY_UPDATED_EVENT, category).await?;
+        }
+        transaction.commit().await?;
+
+        Ok(category)
+    }
+
+    #[tracing::instrument(
+        name = "PgCategoryRepository::purge_by_id",
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql")
+    )]
+    async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError> {
+        let mut client = self.pool.get().await?;
+        let transaction = client.transaction().await?;
+        self.record_message(&transaction).await?;
+
+        let stmt = transaction.prepare(QUERY_PURGE_CATEGORY_BY_ID).await?;
+        let result = transaction
+            .query_opt(&stmt, &[id])
+            .await
+            .map_err(|err| DomainError::from_db(err, DbOperation::Delete))?;
+
+        if let Some(result) = &result {
+            insert_outbox_event(&transaction, CATEGORY_DELETED_EVENT, &result.into()).await?;
+        }
+        transaction.commit().await?;
+
+        Ok(result.is_some())
+    }
+}
+
+async fn insert_category(
+    transaction: &Transaction<'_>,
+    category_create_model: &CategoryCreateModel,
+) -> Result<CategoryModel, DomainError> {
+    let stmt = transaction.prepare(QUERY_INSERT_CATEGORY).await?;
+    let result = &transaction
+        .query_one(
+            &stmt,
+            &[
+                &category_create_model.id,
+                &category_create_model.name,
+                &category_create_model.description,
+                &category_create_model.parent_id,
+            ],
+        )
+        .await?;
+    let category: CategoryModel = result.into();
+
+    insert_outbox_event(transaction, CATEGORY_CREATED_EVENT, &category).await?;
+
+    Ok(category)
+}
+
+async fn update_category(
+    transaction: &Transaction<'_>,
+    id: &Uuid,
+    category_update_model: &CategoryUpdateModel,
+    version: &Option<i32>,
+) -> Result<Option<CategoryModel>, DomainError> {
+    let stmt = transaction.prepare(QUERY_UPDATE_CATEGORY_BY_ID).await?;
+    let result = transaction
+        .query_opt(
+            &stmt,
+            &[
+                id,
+                &category_update_model.name,
+                &category_update_model.description,
+                &category_update_model.parent_id,
+                version,
+            ],
+        )
+        .await?;
+
+    if let Some(result) = result {
+        let category: CategoryModel = (&result).into();
+        insert_outbox_event(transaction, CATEGORY_UPDATED_EVENT, &category).await?;
+        return Ok(Some(category));
+    }
+
+    Ok(None)
+}
+
+async fn delete_category(
+    transaction: &Transaction<'_>,
+    id: &Uuid,
+    version: &Option<i32>,
+) -> Result<bool, DomainError> {
+    let stmt = transaction.prepare(QUERY_DELETE_CATEGORY_BY_ID).await?;
+    if let Some(result) = transaction.query_opt(&stmt, &[id, version]).await? {
+        insert_outbox_event(transaction, CATEGORY_DELETED_EVENT, &(&result).into()).await?;
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
+async fn finish_savepoint(savepoint: Transaction<'_>, succeeded: bool) -> Result<(), DomainError> {
+    if succeeded {
+        savepoint.commit().await?;
+    } else {
+        savepoint.rollback().await?;
+    }
+
+    Ok(())
+}
+
+fn sort_column(field: &Option<CategorySortField>) -> &'static str {
+    match field {
+        Some(CategorySortField::Name) => "category.name",
+        Some(CategorySortField::CreatedAt) => "category.created_at",
+        Some(CategorySortField::UpdatedAt) => "category.updated_at",
+        None => "category.id",
+    }
+}
+
+fn not_found() -> DomainError {
+    DomainError::NotFound(String::from("Category id not found"))
+}
+
+/// Conditional writes match no row when the category changed since the
+/// version the client read.
+fn precondition_failed() -> DomainError {
+    DomainError::PreconditionFailed(String::from("Category version does not match"))
+}
+
+/// Only a conditional write can miss because of the version, an
+/// unconditional one misses when the category is gone.
+fn unmatched(version: &Option<i32>) -> DomainError {
+    match version {
+        Some(_) => precondition_failed(),
+        None => not_found(),
+    }
+}
+
+async fn insert_outbox_event(
+    transaction: &Transaction<'_>,
+    event_type: &str,
+    category: &CategoryModel,
+) -> Result<(), DomainError> {
+    let payload = json!({
+        "id": category.id,
+        "name": category.name,
+        "description": category.description,
+        "parent_id": category.parent_id,
+        "is_active": category.is_active,
+        "created_at": category.created_at,
+        "updated_at": category.updated_at,
+        "deleted_at": category.deleted_at,
+        "version": category.version,
+    });
+
+    outbox::insert(
+        transaction,
+        CATEGORY_AGGREGATE_TYPE,
+        &category.id,
+        event_type,
+        payload,
+    )
+    .await
 }
 
 impl From<&Row> for CategoryModel {
@@ -193,9 +1008,100 @@ impl From<&Row> for CategoryModel {
             id: row.get("category_id"),
             name: row.get("category_name"),
             description: row.get("category_description"),
+            parent_id: row.get("category_parent_id"),
             is_active: row.get("category_is_active"),
             created_at: row.get("category_created_at"),
             updated_at: row.get("category_updated_at"),
+            deleted_at: row.get("category_deleted_at"),
+            version: row.get("category_version"),
+        }
+    }
+}
+
+impl From<&Row> for CategorySearchModel {
+    fn from(row: &Row) -> Self {
+        Self {
+            category: row.into(),
+            rank: row.get("category_rank"),
+            name_highlight: row.get("category_name_highlight"),
+            description_highlight: row.get("category_description_highlight"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::tests::utils::setup,
+        domain::categories::model::{CategoryCreateModel, CategoryUpdateModel},
+        repository::postgres,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_should_stop_walking_cyclic_parents() {
+        setup().await;
+        let pool = Arc::new(postgres::init().unwrap());
+        let category_repository = PgCategoryRepository::new(pool.clone());
+
+        let root_model = CategoryCreateModel::mock_default();
+        let child_model = CategoryCreateModel::mock_default().with_parent_id(root_model.id);
+        for category_model in [&root_model, &child_model] {
+            category_repository.insert(category_model).await.unwrap();
         }
+        pool.get()
+            .await
+            .unwrap()
+            .execute(
+                "update category set parent_id = $2 where id = $1",
+                &[&root_model.id, &child_model.id],
+            )
+            .await
+            .unwrap();
+
+        let ancestors = category_repository
+            .find_ancestors(&root_model.id)
+            .await
+            .unwrap();
+        let subtree = category_repository
+            .find_subtree(&root_model.id)
+            .await
+            .unwrap();
+
+        assert_eq!(ancestors.len(), 1);
+        assert_eq!(subtree.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn it_should_not_update_deleted_categories() {
+        setup().await;
+        let pool = Arc::new(postgres::init().unwrap());
+        let category_repository = PgCategoryRepository::new(pool);
+
+        let category_model = CategoryCreateModel::mock_default();
+        category_repository.insert(&category_model).await.unwrap();
+        category_repository
+            .delete_by_id(&category_model.id, &None)
+            .await
+            .unwrap();
+
+        let result = category_repository
+            .update_by_id(
+                &category_model.id,
+                &CategoryUpdateModel::mock_default(),
+                &None,
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::NotFound(_))));
+
+        let result = category_repository
+            .patch_by_id(
+                &category_model.id,
+                &CategoryPatchModel::new(Some(String::from("Fries")), None, None),
+                &Some(2),
+            )
+            .await;
+        assert!(matches!(result, Err(DomainError::PreconditionFailed(_))));
     }
 }
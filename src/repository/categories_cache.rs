@@ -0,0 +1,449 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        categories::{
+            model::{
+                CategoryCreateModel, CategoryFindModel, CategoryModel, CategoryPageModel,
+                CategoryPatchModel, CategorySearchModel, CategoryUpdateModel,
+            },
+            repository::CategoryRepository,
+        },
+        error::DomainError,
+    },
+    metrics,
+};
+
+const CACHE_KEY_PREFIX: &str = "categories";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedCategory {
+    id: Uuid,
+    name: String,
+    description: Option<String>,
+    parent_id: Option<Uuid>,
+    is_active: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    deleted_at: Option<DateTime<Utc>>,
+    version: i32,
+}
+impl From<&CategoryModel> for CachedCategory {
+    fn from(value: &CategoryModel) -> Self {
+        Self {
+            id: value.id,
+            name: value.name.clone(),
+            description: value.description.clone(),
+            parent_id: value.parent_id,
+            is_active: value.is_active,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            deleted_at: value.deleted_at,
+            version: value.version,
+        }
+    }
+}
+impl From<CachedCategory> for CategoryModel {
+    fn from(value: CachedCategory) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            description: value.description,
+            parent_id: value.parent_id,
+            is_active: value.is_active,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            deleted_at: value.deleted_at,
+            version: value.version,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedCategoryPage {
+    categories: Vec<CachedCategory>,
+    count: Option<u32>,
+    has_next: bool,
+    has_prev: bool,
+}
+impl From<&CategoryPageModel> for CachedCategoryPage {
+    fn from(value: &CategoryPageModel) -> Self {
+        Self {
+            categories: value.categories.iter().map(CachedCategory::from).collect(),
+            count: value.count,
+            has_next: value.next_cursor.is_some(),
+            has_prev: value.prev_cursor.is_some(),
+        }
+    }
+}
+impl From<CachedCategoryPage> for CategoryPageModel {
+    fn from(value: CachedCategoryPage) -> Self {
+        Self::new(
+            value.categories.into_iter().map(|i| i.into()).collect(),
+            value.count,
+            value.has_next,
+            value.has_prev,
+        )
+    }
+}
+
+/// Read-through cache over another `CategoryRepository`.
+///
+/// `find_by_id` results are cached per id and `find` pages are cached under a
+/// generation number that is bumped on every write, so stale pages are never
+/// read again and simply expire. Redis failures are logged and the call falls
+/// back to the wrapped repository.
+pub struct CachedCategoryRepository {
+    category_repository: Arc<dyn CategoryRepository>,
+    redis_connection: ConnectionManager,
+    ttl: usize,
+}
+impl CachedCategoryRepository {
+    pub fn new(
+        category_repository: Arc<dyn CategoryRepository>,
+        redis_connection: ConnectionManager,
+        ttl: usize,
+    ) -> Self {
+        Self {
+            category_repository,
+            redis_connection,
+            ttl,
+        }
+    }
+
+    fn category_key(id: &Uuid) -> String {
+        format!("{CACHE_KEY_PREFIX}:{id}")
+    }
+
+    fn find_generation_key() -> String {
+        format!("{CACHE_KEY_PREFIX}:find:generation")
+    }
+
+    fn find_key(generation: u64, category_find_model: &CategoryFindModel) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(format!("{category_find_model:?}"));
+        format!(
+            "{CACHE_KEY_PREFIX}:find:{generation}:{}",
+            hex::encode(hasher.finalize())
+        )
+    }
+
+    async fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, DomainError> {
+        let mut con = self.redis_connection.clone();
+        let value: Option<String> = metrics::time_redis("get", con.get(key)).await?;
+
+        match value {
+            Some(value) => {
+                Ok(Some(serde_json::from_str(&value).map_err(|err| {
+                    DomainError::InternalServerError(err.to_string())
+                })?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn set<T: Serialize>(&self, key: &str, value: &T) -> Result<(), DomainError> {
+        let value = serde_json::to_string(value)
+            .map_err(|err| DomainError::InternalServerError(err.to_string()))?;
+
+        let mut con = self.redis_connection.clone();
+        metrics::time_redis("set", con.set_ex::<_, _, ()>(key, value, self.ttl)).await?;
+        Ok(())
+    }
+
+    async fn find_generation(&self) -> Result<u64, DomainError> {
+        let mut con = self.redis_connection.clone();
+        let generation: Option<u64> =
+            metrics::time_redis("get", con.get(Self::find_generation_key())).await?;
+        Ok(generation.unwrap_or_default())
+    }
+
+    async fn invalidate(&self, ids: &[Uuid]) {
+        let result: Result<(), DomainError> = async {
+            let mut con = self.redis_connection.clone();
+            if !ids.is_empty() {
+                let keys: Vec<String> = ids.iter().map(Self::category_key).collect();
+                metrics::time_redis("del", con.del::<_, ()>(keys)).await?;
+            }
+            metrics::time_redis("incr", con.incr::<_, _, ()>(Self::find_generation_key(), 1))
+                .await?;
+            Ok(())
+        }
+        .await;
+
+        if let Err(err) = result {
+            log::error!("Error to invalidate categories cache: {}", err);
+        }
+    }
+}
+
+#[async_trait]
+impl CategoryRepository for CachedCategoryRepository {
+    async fn find(
+        &self,
+        category_find_model: &CategoryFindModel,
+    ) -> Result<Option<CategoryPageModel>, DomainError> {
+        let key = match self.find_generation().await {
+            Ok(generation) => Some(Self::find_key(generation, category_find_model)),
+            Err(err) => {
+                log::warn!("Error to read categories cache: {}", err);
+                None
+            }
+        };
+
+        if let Some(key) = &key {
+            match self.get::<Option<CachedCategoryPage>>(key).await {
+                Ok(Some(cached)) => return Ok(cached.map(|page| page.into())),
+                Ok(None) => {}
+                Err(err) => log::warn!("Error to read categories cache: {}", err),
+            }
+        }
+
+        let result = self.category_repository.find(category_find_model).await?;
+
+        if let Some(key) = &key {
+            let cached = result.as_ref().map(CachedCategoryPage::from);
+            if let Err(err) = self.set(key, &cached).await {
+                log::warn!("Error to write categories cache: {}", err);
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        page: &u32,
+        page_size: &u32,
+    ) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError> {
+        self.category_repository
+            .search(query, page, page_size)
+            .await
+    }
+
+    async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError> {
+        let key = Self::category_key(id);
+
+        match self.get::<CachedCategory>(&key).await {
+            Ok(Some(cached)) => return Ok(Some(cached.into())),
+            Ok(None) => {}
+            Err(err) => log::warn!("Error to read categories cache: {}", err),
+        }
+
+        let category = self.category_repository.find_by_id(id).await?;
+
+        if let Some(category) = &category {
+            if let Err(err) = self.set(&key, &CachedCategory::from(category)).await {
+                log::warn!("Error to write categories cache: {}", err);
+            }
+        }
+
+        Ok(category)
+    }
+
+    async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError> {
+        self.category_repository.find_children(id).await
+    }
+
+    async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError> {
+        self.category_repository.find_ancestors(id).await
+    }
+
+    async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError> {
+        self.category_repository.find_subtree(id).await
+    }
+
+    async fn insert(
+        &self,
+        category_create_model: &CategoryCreateModel,
+    ) -> Result<CategoryModel, DomainError> {
+        let category = self
+            .category_repository
+            .insert(category_create_model)
+            .await?;
+        self.invalidate(&[category.id]).await;
+        Ok(category)
+    }
+
+    async fn update_by_id(
+        &self,
+        id: &Uuid,
+        category_update_model: &CategoryUpdateModel,
+        version: &Option<i32>,
+    ) -> Result<CategoryModel, DomainError> {
+        let category = self
+            .category_repository
+            .update_by_id(id, category_update_model, version)
+            .await?;
+        self.invalidate(&[*id]).await;
+        Ok(category)
+    }
+
+    async fn patch_by_id(
+        &self,
+        id: &Uuid,
+        category_patch_model: &CategoryPatchModel,
+        version: &Option<i32>,
+    ) -> Result<CategoryModel, DomainError> {
+        let category = self
+            .category_repository
+            .patch_by_id(id, category_patch_model, version)
+            .await?;
+        self.invalidate(&[*id]).await;
+        Ok(category)
+    }
+
+    async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError> {
+        self.category_repository.delete_by_id(id, version).await?;
+        self.invalidate(&[*id]).await;
+        Ok(())
+    }
+
+    async fn insert_many(
+        &self,
+        category_create_models: &[CategoryCreateModel],
+        atomic: &bool,
+    ) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError> {
+        let results = self
+            .category_repository
+            .insert_many(category_create_models, atomic)
+            .await?;
+        self.invalidate(&[]).await;
+        Ok(results)
+    }
+
+    async fn update_many(
+        &self,
+        category_update_models: &[(Uuid, CategoryUpdateModel)],
+        atomic: &bool,
+    ) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError> {
+        let results = self
+            .category_repository
+            .update_many(category_update_models, atomic)
+            .await?;
+        let ids: Vec<Uuid> = category_update_models.iter().map(|(id, _)| *id).collect();
+        self.invalidate(&ids).await;
+        Ok(results)
+    }
+
+    async fn delete_many(
+        &self,
+        ids: &[Uuid],
+        atomic: &bool,
+    ) -> Result<Vec<Result<(), DomainError>>, DomainError> {
+        let results = self.category_repository.delete_many(ids, atomic).await?;
+        self.invalidate(ids).await;
+        Ok(results)
+    }
+
+    async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError> {
+        let category = self.category_repository.restore_by_id(id).await?;
+        self.invalidate(&[*id]).await;
+        Ok(category)
+    }
+
+    async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError> {
+        let purged = self.category_repository.purge_by_id(id).await?;
+        self.invalidate(&[*id]).await;
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::mock;
+
+    use crate::repository::redis;
+
+    use super::*;
+
+    mock! {
+        pub FakeCategoryRepository { }
+
+        #[async_trait]
+        impl CategoryRepository for FakeCategoryRepository {
+            async fn find(&self,category_find_model: &CategoryFindModel) -> Result<Option<CategoryPageModel>, DomainError>;
+            async fn search(&self,query: &str,page: &u32,page_size: &u32) -> Result<Option<(Vec<CategorySearchModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn find_children(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_ancestors(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn find_subtree(&self, id: &Uuid) -> Result<Vec<CategoryModel>, DomainError>;
+            async fn insert(&self,category_create_model: &CategoryCreateModel) -> Result<CategoryModel, DomainError>;
+            async fn update_by_id(&self,id: &Uuid,category_update_model: &CategoryUpdateModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn patch_by_id(&self,id: &Uuid,category_patch_model: &CategoryPatchModel,version: &Option<i32>) -> Result<CategoryModel, DomainError>;
+            async fn delete_by_id(&self, id: &Uuid, version: &Option<i32>) -> Result<(), DomainError>;
+            async fn insert_many(&self,category_create_models: &[CategoryCreateModel],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn update_many(&self,category_update_models: &[(Uuid, CategoryUpdateModel)],atomic: &bool) -> Result<Vec<Result<CategoryModel, DomainError>>, DomainError>;
+            async fn delete_many(&self,ids: &[Uuid],atomic: &bool) -> Result<Vec<Result<(), DomainError>>, DomainError>;
+            async fn restore_by_id(&self, id: &Uuid) -> Result<Option<CategoryModel>, DomainError>;
+            async fn purge_by_id(&self, id: &Uuid) -> Result<bool, DomainError>;
+        }
+    }
+
+    async fn get_repository(
+        category_repository: MockFakeCategoryRepository,
+    ) -> CachedCategoryRepository {
+        dotenv::from_filename(".env.test").ok();
+
+        CachedCategoryRepository::new(
+            Arc::new(category_repository),
+            redis::init().await.unwrap(),
+            60,
+        )
+    }
+
+    #[tokio::test]
+    async fn it_should_return_cached_category_without_hitting_repository() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        let mock_category_model = CategoryModel::mock_default();
+        let category_id = mock_category_model.id;
+
+        category_repository
+            .expect_find_by_id()
+            .times(1)
+            .return_once(|_| Ok(Some(mock_category_model)));
+
+        let repository = get_repository(category_repository).await;
+
+        let first = repository.find_by_id(&category_id).await.unwrap().unwrap();
+        let second = repository.find_by_id(&category_id).await.unwrap().unwrap();
+
+        assert_eq!(first.id, category_id);
+        assert_eq!(second.id, category_id);
+        assert_eq!(second.name, first.name);
+    }
+
+    #[tokio::test]
+    async fn it_should_invalidate_cached_category_when_updated() {
+        let mut category_repository = MockFakeCategoryRepository::new();
+
+        let mock_category_model = CategoryModel::mock_default();
+        let category_id = mock_category_model.id;
+
+        category_repository
+            .expect_find_by_id()
+            .times(2)
+            .returning(move |_| Ok(Some(mock_category_model.clone())));
+        category_repository
+            .expect_update_by_id()
+            .return_once(|_, _, _| Ok(CategoryModel::mock_default()));
+
+        let repository = get_repository(category_repository).await;
+
+        repository.find_by_id(&category_id).await.unwrap();
+        repository
+            .update_by_id(&category_id, &CategoryUpdateModel::mock_default(), &None)
+            .await
+            .unwrap();
+        repository.find_by_id(&category_id).await.unwrap();
+    }
+}
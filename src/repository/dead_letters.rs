@@ -0,0 +1,317 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use lapin::{
+    message::Delivery,
+    options::{
+        BasicAckOptions, BasicGetOptions, BasicNackOptions, BasicPublishOptions,
+        ConfirmSelectOptions, QueueDeclareOptions, QueuePurgeOptions,
+    },
+    types::{AMQPValue, FieldTable},
+    BasicProperties, Channel, Connection,
+};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    amqp::retry::RETRY_COUNT_HEADER,
+    domain::{
+        dead_letters::{
+            model::{DeadLetterModel, DEAD_LETTERS_READ_MAX},
+            repository::DeadLetterRepository,
+        },
+        error::DomainError,
+    },
+};
+
+const DEAD_LETTER_QUEUE: &str = "categories-dead-letter.queue";
+const REPLAY_EXCHANGE: &str = "categories.exchange";
+
+/// Dead letters are read with `basic_get` without acking, so listing a page
+/// takes the messages out of the queue for the duration of the call and puts
+/// them back in their original order afterwards, flagged as redelivered. At
+/// most `DEAD_LETTERS_READ_MAX` messages are taken by a call.
+pub struct AmqpDeadLetterRepository {
+    connection: Arc<Connection>,
+}
+impl AmqpDeadLetterRepository {
+    pub fn new(connection: Arc<Connection>) -> Self {
+        Self { connection }
+    }
+
+    async fn message_count(&self, channel: &Channel) -> Result<u32, DomainError> {
+        let queue = channel
+            .queue_declare(
+                DEAD_LETTER_QUEUE,
+                QueueDeclareOptions {
+                    passive: true,
+                    ..Default::default()
+                },
+                FieldTable::default(),
+            )
+            .await?;
+
+        Ok(queue.message_count())
+    }
+
+    /// Takes up to `count` messages, never more than `DEAD_LETTERS_READ_MAX`,
+    /// out of the queue. Nothing is acked, so messages that are neither acked
+    /// nor nacked by the caller are requeued when the channel closes.
+    async fn take(&self, channel: &Channel, count: u32) -> Result<Vec<Delivery>, DomainError> {
+        let mut deliveries = vec![];
+        for _ in 0..count.min(DEAD_LETTERS_READ_MAX) {
+            match channel
+                .basic_get(DEAD_LETTER_QUEUE, BasicGetOptions::default())
+                .await?
+            {
+                Some(message) => deliveries.push(message.delivery),
+                None => break,
+            }
+        }
+
+        Ok(deliveries)
+    }
+
+    /// Requeues the kept deliveries with a single nack of every unsettled
+    /// delivery up to the last one, so they keep their original order.
+    async fn requeue(channel: Channel, deliveries: Vec<Delivery>) -> Result<(), DomainError> {
+        if let Some(last) = deliveries.last() {
+            channel
+                .basic_nack(
+                    last.delivery_tag,
+                    BasicNackOptions {
+                        multiple: true,
+                        requeue: true,
+                    },
+                )
+                .await?;
+        }
+        channel.close(0, "dead letters channel finished").await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DeadLetterRepository for AmqpDeadLetterRepository {
+    async fn find(
+        &self,
+        page: &u32,
+        page_size: &u32,
+    ) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError> {
+        let channel = self.connection.create_channel().await?;
+
+        let count = self.message_count(&channel).await?;
+        let offset = page_size * (page - 1);
+        let deliveries = self.take(&channel, count.min(offset + page_size)).await?;
+
+        let dead_letters: Vec<DeadLetterModel> = deliveries
+            .iter()
+            .skip(offset as usize)
+            .map(|delivery| delivery.into())
+            .collect();
+
+        Self::requeue(channel, deliveries).await?;
+
+        if !dead_letters.is_empty() {
+            return Ok(Some((dead_letters, count)));
+        }
+
+        Ok(None)
+    }
+
+    async fn replay(&self, message_ids: &Option<Vec<String>>) -> Result<u32, DomainError> {
+        let channel = self.connection.create_channel().await?;
+        channel
+            .confirm_select(ConfirmSelectOptions::default())
+            .await?;
+
+        let count = self.message_count(&channel).await?;
+        let deliveries = self.take(&channel, count).await?;
+
+        let mut replayed = 0;
+        let mut kept = vec![];
+        for delivery in deliveries {
+            if !is_selected(&delivery, message_ids) {
+                kept.push(delivery);
+                continue;
+            }
+
+            let mut headers = delivery.properties.headers().clone().unwrap_or_default();
+            headers.insert(RETRY_COUNT_HEADER.into(), AMQPValue::LongLongInt(0));
+
+            let confirmation = channel
+                .basic_publish(
+                    REPLAY_EXCHANGE,
+                    "",
+                    BasicPublishOptions::default(),
+                    &delivery.data,
+                    delivery.properties.clone().with_headers(headers),
+                )
+                .await?
+                .await?;
+
+            if confirmation.is_ack() {
+                delivery.ack(BasicAckOptions::default()).await?;
+                replayed += 1;
+            } else {
+                kept.push(delivery);
+            }
+        }
+
+        Self::requeue(channel, kept).await?;
+
+        Ok(replayed)
+    }
+
+    async fn purge(&self, message_ids: &Option<Vec<String>>) -> Result<u32, DomainError> {
+        let channel = self.connection.create_channel().await?;
+
+        if message_ids.is_none() {
+            let purged = channel
+                .queue_purge(DEAD_LETTER_QUEUE, QueuePurgeOptions::default())
+                .await?;
+            channel.close(0, "dead letters channel finished").await?;
+            return Ok(purged);
+        }
+
+        let count = self.message_count(&channel).await?;
+        let deliveries = self.take(&channel, count).await?;
+
+        let mut purged = 0;
+        let mut kept = vec![];
+        for delivery in deliveries {
+            if is_selected(&delivery, message_ids) {
+                delivery.ack(BasicAckOptions::default()).await?;
+                purged += 1;
+            } else {
+                kept.push(delivery);
+            }
+        }
+
+        Self::requeue(channel, kept).await?;
+
+        Ok(purged)
+    }
+}
+
+fn is_selected(delivery: &Delivery, message_ids: &Option<Vec<String>>) -> bool {
+    match message_ids {
+        None => true,
+        Some(message_ids) => {
+            message_ids.contains(&dead_letter_id(&delivery.properties, &delivery.data))
+        }
+    }
+}
+
+/// Messages rejected for missing a `message_id` are identified by the hash of
+/// their body, so they can still be replayed or purged one by one.
+fn dead_letter_id(properties: &BasicProperties, data: &[u8]) -> String {
+    match properties.message_id() {
+        Some(message_id) if !message_id.as_str().is_empty() => message_id.to_string(),
+        _ => format!("sha256:{}", hex::encode(Sha256::digest(data))),
+    }
+}
+
+/// RabbitMQ records why a message was dead-lettered in `x-death`. Messages
+/// that exhausted their retries are published directly, so for those the last
+/// error of `x-retry-history` is used instead.
+fn death_reason(headers: &FieldTable) -> Option<String> {
+    let x_death = headers
+        .inner()
+        .get("x-death")
+        .and_then(|value| value.as_array())
+        .and_then(|deaths| deaths.as_slice().first())
+        .and_then(|death| death.as_field_table())
+        .and_then(|death| death.inner().get("reason"))
+        .map(to_json);
+
+    let retry_history = headers
+        .inner()
+        .get("x-retry-history")
+        .and_then(|value| value.as_array())
+        .and_then(|history| history.as_slice().last())
+        .and_then(|attempt| attempt.as_field_table())
+        .and_then(|attempt| attempt.inner().get("error"))
+        .map(to_json);
+
+    x_death
+        .or(retry_history)
+        .and_then(|reason| reason.as_str().map(String::from))
+}
+
+fn to_json(value: &AMQPValue) -> Value {
+    match value {
+        AMQPValue::Boolean(value) => Value::from(*value),
+        AMQPValue::ShortShortInt(value) => Value::from(*value),
+        AMQPValue::ShortShortUInt(value) => Value::from(*value),
+        AMQPValue::ShortInt(value) => Value::from(*value),
+        AMQPValue::ShortUInt(value) => Value::from(*value),
+        AMQPValue::LongInt(value) => Value::from(*value),
+        AMQPValue::LongUInt(value) => Value::from(*value),
+        AMQPValue::LongLongInt(value) => Value::from(*value),
+        AMQPValue::Float(value) => Value::from(*value),
+        AMQPValue::Double(value) => Value::from(*value),
+        AMQPValue::Timestamp(value) => Value::from(*value),
+        AMQPValue::ShortString(value) => Value::from(value.as_str()),
+        AMQPValue::LongString(value) => Value::from(value.to_string()),
+        AMQPValue::ByteArray(value) => Value::from(String::from_utf8_lossy(value.as_slice())),
+        AMQPValue::FieldArray(value) => {
+            Value::from(value.as_slice().iter().map(to_json).collect::<Vec<Value>>())
+        }
+        AMQPValue::FieldTable(value) => Value::Object(to_json_map(value)),
+        AMQPValue::DecimalValue(value) => {
+            Value::from(value.value as f64 / 10f64.powi(value.scale as i32))
+        }
+        AMQPValue::Void => Value::Null,
+    }
+}
+
+fn to_json_map(headers: &FieldTable) -> Map<String, Value> {
+    headers
+        .inner()
+        .iter()
+        .map(|(key, value)| (key.to_string(), to_json(value)))
+        .collect()
+}
+
+impl From<&Delivery> for DeadLetterModel {
+    fn from(delivery: &Delivery) -> Self {
+        let headers = delivery.properties.headers().clone().unwrap_or_default();
+
+        Self {
+            id: dead_letter_id(&delivery.properties, &delivery.data),
+            message_id: delivery
+                .properties
+                .message_id()
+                .as_ref()
+                .map(|message_id| message_id.to_string()),
+            exchange: delivery.exchange.to_string(),
+            routing_key: delivery.routing_key.to_string(),
+            body: String::from_utf8_lossy(&delivery.data).to_string(),
+            headers: Value::Object(to_json_map(&headers)),
+            death_reason: death_reason(&headers),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_derive_id_from_body_without_message_id() {
+        let data = br#"{"type":"create","name":"Burgers"}"#;
+
+        let properties = BasicProperties::default().with_message_id("message".into());
+        assert_eq!(dead_letter_id(&properties, data), "message");
+
+        let id = dead_letter_id(&BasicProperties::default(), data);
+        assert!(id.starts_with("sha256:"));
+        assert_eq!(id, dead_letter_id(&BasicProperties::default(), data));
+        assert_ne!(id, dead_letter_id(&BasicProperties::default(), b"{}"));
+
+        let properties = BasicProperties::default().with_message_id("".into());
+        assert_eq!(dead_letter_id(&properties, data), id);
+    }
+}
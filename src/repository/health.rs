@@ -2,16 +2,30 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use deadpool_postgres::Pool;
+use lapin::Connection;
+use redis::aio::ConnectionManager;
 
-use crate::domain::{error::DomainError, health::repository::HealthRepository};
+use crate::{
+    domain::{error::DomainError, health::repository::HealthRepository},
+    metrics,
+};
 
 pub struct PgHealthRepository {
     pool: Arc<Pool>,
-    redis_client: Arc<redis::Client>,
+    redis_connection: ConnectionManager,
+    amqp_connection: Arc<Connection>,
 }
 impl PgHealthRepository {
-    pub fn new(pool: Arc<Pool>, redis_client: Arc<redis::Client>) -> Self {
-        Self { pool, redis_client }
+    pub fn new(
+        pool: Arc<Pool>,
+        redis_connection: ConnectionManager,
+        amqp_connection: Arc<Connection>,
+    ) -> Self {
+        Self {
+            pool,
+            redis_connection,
+            amqp_connection,
+        }
     }
 }
 
@@ -26,8 +40,17 @@ impl HealthRepository for PgHealthRepository {
     }
 
     async fn ping(&self) -> Result<String, DomainError> {
-        let mut con = self.redis_client.get_async_connection().await?;
-        let pong: String = redis::cmd("PING").query_async(&mut con).await?;
+        let mut con = self.redis_connection.clone();
+        let pong: String =
+            metrics::time_redis("ping", redis::cmd("PING").query_async(&mut con)).await?;
         Ok(pong)
     }
+
+    /// Opens and closes a channel, so a broker that stopped answering is
+    /// reported even while the connection looks open.
+    async fn get_amqp_status(&self) -> Result<String, DomainError> {
+        let channel = self.amqp_connection.create_channel().await?;
+        channel.close(200, "OK").await?;
+        Ok(format!("{:?}", self.amqp_connection.status().state()))
+    }
 }
@@ -1,4 +1,10 @@
+pub mod api_keys;
+pub mod api_keys_cache;
 pub mod categories;
+pub mod categories_cache;
+pub mod dead_letters;
 pub mod health;
+pub mod outbox;
 pub mod postgres;
+pub mod processed_messages;
 pub mod redis;
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use deadpool_postgres::{Pool, Transaction};
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+use crate::{domain::error::DomainError, telemetry};
+
+const QUERY_INSERT_OUTBOX: &str = "
+    insert into outbox
+        (id, aggregate_type, aggregate_id, event_type, payload, trace_context)
+    values
+        ($1,$2,$3,$4,$5,$6);";
+
+const QUERY_FIND_PENDING_OUTBOX: &str = "
+    select
+        id as outbox_id,
+        aggregate_type as outbox_aggregate_type,
+        aggregate_id as outbox_aggregate_id,
+        event_type as outbox_event_type,
+        payload as outbox_payload,
+        trace_context as outbox_trace_context,
+        created_at as outbox_created_at
+    from
+        outbox
+    where
+        sent_at is null
+    order by
+        created_at
+    limit $1
+    for update skip locked;";
+
+const QUERY_MARK_OUTBOX_SENT: &str = "
+    update
+        outbox
+    set
+        sent_at=now()
+    where
+        id = any($1);";
+
+const QUERY_DELETE_SENT_OUTBOX: &str = "
+    delete from
+        outbox
+    where
+        id in (
+            select
+                id
+            from
+                outbox
+            where
+                sent_at < now() - make_interval(days => $1)
+            limit $2
+        );";
+
+#[derive(Debug, Clone)]
+pub struct OutboxEvent {
+    pub id: Uuid,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    /// W3C trace headers of the span that recorded the event.
+    pub trace_context: HashMap<String, String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Records an event in the outbox. It must run in the same transaction as the
+/// change it describes so the event is only relayed if the change commits.
+/// The current trace is stored with it so consumers continue the same trace.
+pub async fn insert(
+    transaction: &Transaction<'_>,
+    aggregate_type: &str,
+    aggregate_id: &Uuid,
+    event_type: &str,
+    payload: serde_json::Value,
+) -> Result<(), DomainError> {
+    let trace_context = serde_json::to_value(telemetry::current_trace_context())
+        .map_err(|err| DomainError::InternalServerError(err.to_string()))?;
+
+    let stmt = transaction.prepare(QUERY_INSERT_OUTBOX).await?;
+    transaction
+        .execute(
+            &stmt,
+            &[
+                &Uuid::new_v4(),
+                &aggregate_type,
+                aggregate_id,
+                &event_type,
+                &payload,
+                &trace_context,
+            ],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Locks the oldest pending events, skipping rows already locked by another
+/// relay, until the transaction ends.
+pub async fn find_pending(
+    transaction: &Transaction<'_>,
+    limit: i64,
+) -> Result<Vec<OutboxEvent>, DomainError> {
+    let stmt = transaction.prepare(QUERY_FIND_PENDING_OUTBOX).await?;
+    let result = transaction.query(&stmt, &[&limit]).await?;
+
+    Ok(result.iter().map(|row| row.into()).collect())
+}
+
+pub async fn mark_sent(transaction: &Transaction<'_>, ids: &[Uuid]) -> Result<(), DomainError> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let stmt = transaction.prepare(QUERY_MARK_OUTBOX_SENT).await?;
+    transaction.execute(&stmt, &[&ids]).await?;
+
+    Ok(())
+}
+
+/// Deletes the events sent more than `retention_days` ago, at most
+/// `batch_size` at a time. Pending events are never deleted.
+pub async fn delete_sent(
+    pool: &Pool,
+    retention_days: i32,
+    batch_size: i64,
+) -> Result<u64, DomainError> {
+    let client = pool.get().await?;
+    let stmt = client.prepare(QUERY_DELETE_SENT_OUTBOX).await?;
+
+    let mut deleted = 0;
+    loop {
+        let batch = client
+            .execute(&stmt, &[&retention_days, &batch_size])
+            .await?;
+        deleted += batch;
+        if batch < batch_size as u64 {
+            return Ok(deleted);
+        }
+    }
+}
+
+impl From<&Row> for OutboxEvent {
+    fn from(row: &Row) -> Self {
+        Self {
+            id: row.get("outbox_id"),
+            aggregate_type: row.get("outbox_aggregate_type"),
+            aggregate_id: row.get("outbox_aggregate_id"),
+            event_type: row.get("outbox_event_type"),
+            payload: row.get("outbox_payload"),
+            trace_context: serde_json::from_value(row.get("outbox_trace_context"))
+                .unwrap_or_default(),
+            created_at: row.get("outbox_created_at"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{api::tests::utils::setup, repository::postgres};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_should_delete_sent_events_only() {
+        setup().await;
+        let pool = postgres::init().unwrap();
+        let (sent, recent, pending) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let client = pool.get().await.unwrap();
+        client
+            .execute(
+                "insert into outbox (id, aggregate_type, aggregate_id, event_type, payload, created_at, sent_at) values
+                    ($1, 'category', $1, 'category.created', '{}', now() - interval '9 days', now() - interval '8 days'),
+                    ($2, 'category', $2, 'category.created', '{}', now(), now()),
+                    ($3, 'category', $3, 'category.created', '{}', now() - interval '9 days', null)",
+                &[&sent, &recent, &pending],
+            )
+            .await
+            .unwrap();
+
+        delete_sent(&pool, 7, 1).await.unwrap();
+
+        let ids: Vec<Uuid> = client
+            .query(
+                "select id from outbox where id = any($1)",
+                &[&vec![sent, recent, pending]],
+            )
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.get("id"))
+            .collect();
+        assert!(!ids.contains(&sent));
+        assert!(ids.contains(&recent));
+        assert!(ids.contains(&pending));
+    }
+}
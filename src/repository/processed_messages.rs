@@ -0,0 +1,178 @@
+use deadpool_postgres::{Pool, Transaction};
+
+use crate::domain::error::DomainError;
+
+const QUERY_INSERT_PROCESSED_MESSAGE: &str = "
+    insert into processed_message
+        (message_id)
+    values
+        ($1)
+    on conflict (message_id) do nothing;";
+
+const QUERY_DELETE_EXPIRED_PROCESSED_MESSAGE: &str = "
+    delete from
+        processed_message
+    where
+        message_id in (
+            select
+                message_id
+            from
+                processed_message
+            where
+                processed_at < now() - make_interval(days => $1)
+            limit $2
+        );";
+
+const QUERY_EXISTS_PROCESSED_MESSAGE: &str = "
+    select exists(
+        select 1 from processed_message where message_id = $1
+    ) as processed_message_exists;";
+
+/// Records a message in the ledger. It must run in the same transaction as the
+/// side effects of the message, so a redelivery after a crash before the ack
+/// either finds the message recorded or finds nothing applied. A concurrent
+/// delivery of the same message waits for the first one to commit and fails
+/// with `DomainError::AlreadyProcessed`, so its transaction rolls back.
+pub async fn insert(transaction: &Transaction<'_>, message_id: &str) -> Result<(), DomainError> {
+    let stmt = transaction.prepare(QUERY_INSERT_PROCESSED_MESSAGE).await?;
+    let inserted = transaction.execute(&stmt, &[&message_id]).await?;
+
+    if inserted == 0 {
+        return Err(DomainError::AlreadyProcessed(message_id.to_owned()));
+    }
+
+    Ok(())
+}
+
+/// Deletes the messages recorded more than `retention_days` ago, at most
+/// `batch_size` at a time so a large backlog does not hold one long delete.
+/// A message redelivered after its row is deleted is applied again.
+pub async fn delete_expired(
+    pool: &Pool,
+    retention_days: i32,
+    batch_size: i64,
+) -> Result<u64, DomainError> {
+    let client = pool.get().await?;
+    let stmt = client
+        .prepare(QUERY_DELETE_EXPIRED_PROCESSED_MESSAGE)
+        .await?;
+
+    let mut deleted = 0;
+    loop {
+        let batch = client
+            .execute(&stmt, &[&retention_days, &batch_size])
+            .await?;
+        deleted += batch;
+        if batch < batch_size as u64 {
+            return Ok(deleted);
+        }
+    }
+}
+
+pub async fn exists(pool: &Pool, message_id: &str) -> Result<bool, DomainError> {
+    let client = pool.get().await?;
+    let stmt = client.prepare(QUERY_EXISTS_PROCESSED_MESSAGE).await?;
+    let result = client.query_one(&stmt, &[&message_id]).await?;
+
+    Ok(result.get("processed_message_exists"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use uuid::Uuid;
+
+    use crate::{
+        api::tests::utils::setup,
+        domain::categories::{model::CategoryCreateModel, repository::CategoryRepository},
+        repository::{categories::PgCategoryRepository, postgres},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_should_record_message_with_the_category_insert() {
+        setup().await;
+        let pool = Arc::new(postgres::init().unwrap());
+        let message_id = Uuid::new_v4().to_string();
+
+        assert!(!exists(&pool, &message_id).await.unwrap());
+
+        let category_repository =
+            PgCategoryRepository::new(pool.clone()).with_message_id(message_id.clone());
+        let category_create_model = CategoryCreateModel::mock_default();
+        category_repository
+            .insert(&category_create_model)
+            .await
+            .unwrap();
+
+        assert!(exists(&pool, &message_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn it_should_rollback_category_insert_when_message_already_processed() {
+        setup().await;
+        let pool = Arc::new(postgres::init().unwrap());
+        let message_id = Uuid::new_v4().to_string();
+
+        let category_repository =
+            PgCategoryRepository::new(pool.clone()).with_message_id(message_id);
+        category_repository
+            .insert(&CategoryCreateModel::mock_default())
+            .await
+            .unwrap();
+
+        let category_create_model = CategoryCreateModel::mock_default();
+        let result = category_repository.insert(&category_create_model).await;
+
+        assert!(matches!(result, Err(DomainError::AlreadyProcessed(_))));
+        assert!(category_repository
+            .find_by_id(&category_create_model.id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn it_should_return_already_processed_before_the_name_conflict() {
+        setup().await;
+        let pool = Arc::new(postgres::init().unwrap());
+        let message_id = Uuid::new_v4().to_string();
+
+        let category_repository =
+            PgCategoryRepository::new(pool.clone()).with_message_id(message_id);
+        let category_create_model = CategoryCreateModel::mock_default();
+        category_repository
+            .insert(&category_create_model)
+            .await
+            .unwrap();
+
+        let mut duplicated = CategoryCreateModel::mock_default();
+        duplicated.name = category_create_model.name.clone();
+        let result = category_repository.insert(&duplicated).await;
+
+        assert!(matches!(result, Err(DomainError::AlreadyProcessed(_))));
+    }
+
+    #[tokio::test]
+    async fn it_should_delete_expired_messages() {
+        setup().await;
+        let pool = Arc::new(postgres::init().unwrap());
+        let (expired, recent) = (Uuid::new_v4().to_string(), Uuid::new_v4().to_string());
+
+        let client = pool.get().await.unwrap();
+        client
+            .execute(
+                "insert into processed_message (message_id, processed_at) values ($1, now() - interval '31 days'), ($2, now())",
+                &[&expired, &recent],
+            )
+            .await
+            .unwrap();
+
+        delete_expired(&pool, 30, 1).await.unwrap();
+
+        assert!(!exists(&pool, &expired).await.unwrap());
+        assert!(exists(&pool, &recent).await.unwrap());
+    }
+}
@@ -1,8 +1,11 @@
 use std::env;
 
-use redis::Client;
+use redis::{aio::ConnectionManager, RedisError};
 
-pub fn init() -> Client {
+/// Connects once, every clone of the manager multiplexes its commands over the
+/// same connection, which is reestablished when it drops.
+pub async fn init() -> Result<ConnectionManager, RedisError> {
     let redis_url = env::var("REDIS_URL").expect("REDIS_URL must be set");
-    redis::Client::open(redis_url).expect("Error to init redis client")
+    let client = redis::Client::open(redis_url)?;
+    ConnectionManager::new(client).await
 }
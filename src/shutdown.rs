@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use deadpool_postgres::Pool;
+use lapin::Connection;
+use tokio::{
+    signal::{
+        self,
+        unix::{self, SignalKind},
+    },
+    time::Instant,
+};
+use tokio_util::sync::CancellationToken;
+
+/// Cancels `shutdown` on the first SIGTERM or SIGINT. Returns without
+/// cancelling when `shutdown` was cancelled for another reason.
+pub async fn listen(shutdown: CancellationToken) {
+    let terminate = async {
+        match unix::signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(err) => {
+                log::error!("Error to listen SIGTERM {}", err);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = signal::ctrl_c() => log::info!("SIGINT received, shutting down"),
+        _ = terminate => log::info!("SIGTERM received, shutting down"),
+        _ = shutdown.cancelled() => return,
+    }
+
+    shutdown.cancel();
+}
+
+/// Resolves once `shutdown` is cancelled to the instant every shutdown phase
+/// must finish by, so the phases share one `timeout` instead of each getting
+/// their own.
+pub async fn deadline(shutdown: CancellationToken, timeout: Duration) -> Instant {
+    shutdown.cancelled().await;
+    Instant::now() + timeout
+}
+
+/// Closes the AMQP connection, its channels are expected to be closed by
+/// then so no delivery is left unsettled.
+pub async fn close_amqp(connection: &Connection, deadline: Instant) {
+    match tokio::time::timeout_at(deadline, connection.close(200, "shutdown")).await {
+        Ok(Ok(())) => log::info!("AMQP connection closed"),
+        Ok(Err(err)) => log::error!("Error to close AMQP connection {}", err),
+        Err(_) => log::error!("AMQP connection not closed before the shutdown deadline"),
+    }
+}
+
+/// Closes the pool and waits until the connections still in use are
+/// returned, or the deadline.
+pub async fn drain_pool(pool: &Pool, deadline: Instant) {
+    pool.close();
+
+    loop {
+        let size = pool.status().size;
+        if size == 0 {
+            log::info!("Postgres pool drained");
+            return;
+        }
+        if Instant::now() >= deadline {
+            log::error!(
+                "{} Postgres connections still in use at the shutdown deadline",
+                size
+            );
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use deadpool_postgres::{Manager, ManagerConfig, RecyclingMethod};
+    use tokio_postgres::NoTls;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_should_close_pool_without_connections_in_use() {
+        let manager = Manager::from_config(
+            tokio_postgres::Config::new(),
+            NoTls,
+            ManagerConfig {
+                recycling_method: RecyclingMethod::Fast,
+            },
+        );
+        let pool = Pool::builder(manager).max_size(4).build().unwrap();
+
+        drain_pool(&pool, Instant::now() + Duration::from_secs(1)).await;
+
+        assert!(pool.is_closed());
+        assert_eq!(pool.status().size, 0);
+    }
+
+    #[tokio::test]
+    async fn it_should_take_deadline_when_cancelled() {
+        let shutdown = CancellationToken::new();
+        let timeout = Duration::from_secs(30);
+        let deadline = tokio::spawn(deadline(shutdown.clone(), timeout));
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let cancelled_at = Instant::now();
+        shutdown.cancel();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let deadline = deadline.await.unwrap();
+        assert!(deadline >= cancelled_at + timeout);
+        assert!(deadline < cancelled_at + timeout + Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn it_should_stop_listening_when_cancelled_elsewhere() {
+        let shutdown = CancellationToken::new();
+        shutdown.cancel();
+
+        tokio::time::timeout(Duration::from_secs(1), listen(shutdown))
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,308 @@
+use std::{collections::HashMap, env, error::Error};
+
+use actix_web::http::header::HeaderMap;
+use lapin::types::{AMQPValue, FieldTable};
+use opentelemetry::{
+    global,
+    propagation::{Extractor, Injector},
+    trace::TracerProvider,
+    Context,
+};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{propagation::TraceContextPropagator, trace::SdkTracerProvider, Resource};
+use tracing::{level_filters::LevelFilter, Span, Subscriber};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{
+    filter::Targets, fmt::MakeWriter, layer::SubscriberExt, registry::LookupSpan,
+    util::SubscriberInitExt, EnvFilter, Layer,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LogFormat {
+    Text,
+    Json,
+}
+
+struct TelemetryConfig {
+    otlp_endpoint: Option<String>,
+    service_name: String,
+    log_format: LogFormat,
+}
+impl TelemetryConfig {
+    fn from_env() -> Result<Self, Box<dyn Error>> {
+        let log_format = match env::var("LOG_FORMAT").as_deref() {
+            Ok("json") => LogFormat::Json,
+            Ok("text") | Err(_) => LogFormat::Text,
+            Ok(log_format) => {
+                return Err(format!("LOG_FORMAT must be text or json, got {log_format}").into())
+            }
+        };
+
+        Ok(Self {
+            otlp_endpoint: env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok(),
+            service_name: env::var("OTEL_SERVICE_NAME")
+                .unwrap_or_else(|_| String::from(env!("CARGO_PKG_NAME"))),
+            log_format,
+        })
+    }
+}
+
+/// Installs the tracing subscriber. Logs, including the ones of the `log`
+/// crate, are written to stdout filtered by `RUST_LOG`, as text or as one JSON
+/// object per line when `LOG_FORMAT=json`. When `OTEL_EXPORTER_OTLP_ENDPOINT`
+/// is set, the spans of this crate are also exported over OTLP/HTTP, and the
+/// returned provider must be shut down to flush the last spans.
+pub fn init() -> Result<Option<SdkTracerProvider>, Box<dyn Error>> {
+    let config = TelemetryConfig::from_env()?;
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let tracer_provider = match &config.otlp_endpoint {
+        Some(endpoint) => Some(tracer_provider(endpoint, &config.service_name)?),
+        None => None,
+    };
+
+    let otel_layer = tracer_provider.as_ref().map(|tracer_provider| {
+        tracing_opentelemetry::layer()
+            .with_tracer(tracer_provider.tracer(config.service_name.clone()))
+            .with_filter(Targets::new().with_target(env!("CARGO_CRATE_NAME"), LevelFilter::INFO))
+    });
+
+    let env_filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::ERROR.into())
+        .from_env_lossy();
+
+    tracing_subscriber::registry()
+        .with(fmt_layer(config.log_format, std::io::stdout).with_filter(env_filter))
+        .with(otel_layer)
+        .try_init()?;
+
+    Ok(tracer_provider)
+}
+
+pub fn shutdown(tracer_provider: Option<SdkTracerProvider>) {
+    if let Some(tracer_provider) = tracer_provider {
+        if let Err(err) = tracer_provider.shutdown() {
+            log::error!("Error to flush spans {}", err);
+        }
+    }
+}
+
+/// Log lines carry the fields of their spans, such as the request id, route,
+/// status and latency of the `http_request` span. JSON lines list them under
+/// `spans`, from the outermost span.
+fn fmt_layer<S, W>(log_format: LogFormat, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer().with_writer(writer);
+    match log_format {
+        LogFormat::Text => layer.boxed(),
+        LogFormat::Json => layer.json().flatten_event(true).boxed(),
+    }
+}
+
+fn tracer_provider(
+    endpoint: &str,
+    service_name: &str,
+) -> Result<SdkTracerProvider, Box<dyn Error>> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+        .build()?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_resource(
+            Resource::builder()
+                .with_service_name(service_name.to_owned())
+                .build(),
+        )
+        .with_batch_exporter(exporter)
+        .build())
+}
+
+/// Context of the W3C `traceparent` and `tracestate` headers of a request.
+pub fn extract_http(headers: &HeaderMap) -> Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&HttpHeaders(headers)))
+}
+
+/// Context of the W3C trace headers of an AMQP message.
+pub fn extract_amqp(headers: Option<&FieldTable>) -> Context {
+    match headers {
+        Some(headers) => {
+            global::get_text_map_propagator(|propagator| propagator.extract(&AmqpHeaders(headers)))
+        }
+        None => Context::new(),
+    }
+}
+
+/// Context of trace headers stored with a message, see `current_trace_context`.
+pub fn extract_map(trace_context: &HashMap<String, String>) -> Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(trace_context))
+}
+
+/// Trace headers of the current span, to be carried with a message.
+pub fn current_trace_context() -> HashMap<String, String> {
+    let mut trace_context = HashMap::new();
+    let context = Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut trace_context)
+    });
+    trace_context
+}
+
+/// Adds the trace headers of the current span to AMQP message headers.
+pub fn inject_amqp(headers: &mut FieldTable) {
+    let context = Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut AmqpHeadersMut(headers))
+    });
+}
+
+struct HttpHeaders<'a>(&'a HeaderMap);
+impl Extractor for HttpHeaders<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+struct AmqpHeaders<'a>(&'a FieldTable);
+impl Extractor for AmqpHeaders<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        match self.0.inner().get(key) {
+            Some(AMQPValue::LongString(value)) => std::str::from_utf8(value.as_bytes()).ok(),
+            Some(AMQPValue::ShortString(value)) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.inner().keys().map(|key| key.as_str()).collect()
+    }
+}
+
+struct AmqpHeadersMut<'a>(&'a mut FieldTable);
+impl Injector for AmqpHeadersMut<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        self.0
+            .insert(key.to_owned().into(), AMQPValue::LongString(value.into()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::{mpsc, Arc, Mutex},
+        thread,
+    };
+
+    use actix_web::http::header::{HeaderName, HeaderValue};
+    use opentelemetry::trace::{TraceContextExt, Tracer};
+
+    use super::*;
+
+    const TRACEPARENT: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+
+    #[test]
+    fn it_should_propagate_traceparent_from_http_to_amqp_headers() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+
+        let mut http_headers = HeaderMap::new();
+        http_headers.insert(
+            HeaderName::from_static("traceparent"),
+            HeaderValue::from_static(TRACEPARENT),
+        );
+        let context = extract_http(&http_headers);
+        assert_eq!(
+            context.span().span_context().trace_id().to_string(),
+            "0af7651916cd43dd8448eb211c80319c"
+        );
+
+        let mut amqp_headers = FieldTable::default();
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&context, &mut AmqpHeadersMut(&mut amqp_headers))
+        });
+        let context = extract_amqp(Some(&amqp_headers));
+        assert_eq!(
+            context.span().span_context().trace_id().to_string(),
+            "0af7651916cd43dd8448eb211c80319c"
+        );
+    }
+
+    struct LogBuffer(Arc<Mutex<Vec<u8>>>);
+    impl Write for LogBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn it_should_write_span_fields_in_json_log_lines() {
+        let buffer = Arc::new(Mutex::new(vec![]));
+        let writer = {
+            let buffer = buffer.clone();
+            move || LogBuffer(buffer.clone())
+        };
+
+        let subscriber = tracing_subscriber::registry().with(fmt_layer(LogFormat::Json, writer));
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!(
+                "http_request",
+                request_id = "5b0c1d2e-request",
+                http.route = "/categories/{category_id}",
+                http.response.status_code = tracing::field::Empty,
+            );
+            let _entered = span.enter();
+            span.record("http.response.status_code", 404);
+            tracing::error!("Category not found");
+        });
+
+        let buffer = buffer.lock().unwrap();
+        let line: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(line["message"], "Category not found");
+        assert_eq!(line["level"], "ERROR");
+        assert_eq!(line["spans"][0]["request_id"], "5b0c1d2e-request");
+        assert_eq!(line["spans"][0]["http.route"], "/categories/{category_id}");
+        assert_eq!(line["spans"][0]["http.response.status_code"], 404);
+    }
+
+    #[test]
+    fn it_should_export_spans_to_otlp_endpoint() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![0; 64 * 1024];
+            let size = stream.read(&mut request).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                .unwrap();
+            sender
+                .send(String::from_utf8_lossy(&request[..size]).to_string())
+                .unwrap();
+        });
+
+        let tracer_provider = tracer_provider(&endpoint, "collector-stub").unwrap();
+        tracer_provider
+            .tracer("collector-stub")
+            .in_span("categories::create", |_| {});
+        tracer_provider.force_flush().unwrap();
+
+        let request = receiver.recv().unwrap();
+        assert!(request.starts_with("POST /v1/traces"));
+        assert!(request.contains("application/x-protobuf"));
+    }
+}